@@ -45,5 +45,6 @@ function publish() {
 
 echo "Publishing all packages for $project ${new_version}"
 
+publish sequent-derive
 publish sequent
 publish sequent-repl
\ No newline at end of file
@@ -0,0 +1,314 @@
+//! Derive macros for [Sequent](https://docs.rs/sequent) events.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::BTreeSet;
+use syn::{parse_macro_input, Attribute, Data, DataEnum, DeriveInput, Error, Fields, LitStr};
+
+/// Derives the boilerplate of an event type: [`StaticNamed`], the string encoding ([`Display`], and
+/// thereby [`ToString`]) and decoding ([`FromStr`]) of the event's fields, and a `parser()` function
+/// that returns a [`Parser`] for registering the event with a [`Decoder`]. The type must still implement
+/// [`Event`] (and [`Debug`]) by hand.
+///
+/// The event is named after its type, in kebab case (e.g., `ReversibleAppend` is named
+/// `reversible-append`), unless renamed with `#[event(name = "...")]`. The fields are encoded in
+/// declaration order, separated by a single space, using their [`Display`] and [`FromStr`]
+/// implementations. A field that encodes to an empty string, or to one containing whitespace or a
+/// double quote, is enclosed in double quotes and escaped, as described in [`fields`]; so every field
+/// round-trips, provided its own [`Display`] and [`FromStr`] do. The variants of an enum are encoded
+/// by prefixing the fields with the kebab-cased name of the variant, which may also be renamed with
+/// `#[event(name = "...")]`.
+///
+/// # Examples
+/// ```
+/// use sequent::{Decoder, Event, Queue, StaticNamed, TransitionError};
+/// use sequent_derive::Event;
+///
+/// #[derive(Debug, Default)]
+/// struct Account {
+///     balance: i64,
+/// }
+///
+/// #[derive(Debug, PartialEq, Event)]
+/// #[event(name = "tx")]
+/// enum Transaction {
+///     Deposit { amount: i64 },
+///     Withdraw(i64),
+///     Close,
+/// }
+///
+/// impl Event for Transaction {
+///     type State = Account;
+///
+///     fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+///         match self {
+///             Self::Deposit { amount } => state.balance += amount,
+///             Self::Withdraw(amount) => state.balance -= amount,
+///             Self::Close => state.balance = 0,
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// assert_eq!("tx", <Transaction as StaticNamed>::name());
+/// assert_eq!("withdraw 10", Transaction::Withdraw(10).to_string());
+/// assert_eq!(Transaction::Deposit { amount: 5 }, "deposit 5".parse().unwrap());
+///
+/// #[derive(Debug, PartialEq, Event)]
+/// struct Rename {
+///     from: String,
+///     to: String,
+/// }
+/// # impl Event for Rename {
+/// #     type State = Account;
+/// #
+/// #     fn apply(&self, _: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+/// #         Ok(())
+/// #     }
+/// # }
+///
+/// let rename = Rename { from: "cheque account".into(), to: String::new() };
+/// assert_eq!(r#""cheque account" """#, rename.to_string());
+/// assert_eq!(rename, rename.to_string().parse().unwrap());
+///
+/// let decoder = Decoder::new(vec![Transaction::parser()]);
+/// assert_eq!("close", decoder.decode("tx", "close").unwrap().to_string());
+/// ```
+///
+/// [`StaticNamed`]: https://docs.rs/sequent/latest/sequent/trait.StaticNamed.html
+/// [`Event`]: https://docs.rs/sequent/latest/sequent/trait.Event.html
+/// [`Parser`]: https://docs.rs/sequent/latest/sequent/struct.Parser.html
+/// [`Decoder`]: https://docs.rs/sequent/latest/sequent/struct.Decoder.html
+/// [`fields`]: https://docs.rs/sequent/latest/sequent/fields/index.html
+/// [`Display`]: std::fmt::Display
+/// [`FromStr`]: std::str::FromStr
+/// [`Debug`]: std::fmt::Debug
+#[proc_macro_derive(Event, attributes(event))]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let ident = &input.ident;
+    let name = name_attr(&input.attrs)?.unwrap_or_else(|| kebab_case(&ident.to_string()));
+    let (encode, decode) = match &input.data {
+        Data::Struct(data) => (
+            encode_struct(&data.fields),
+            decode_fields(&name, quote! { Self }, &data.fields, 0),
+        ),
+        Data::Enum(data) => codec_enum(&name, data)?,
+        Data::Union(_) => return Err(Error::new_spanned(ident, "events cannot be derived for unions")),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::sequent::StaticNamed for #ident #ty_generics #where_clause {
+            fn name() -> &'static str {
+                #name
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #encode
+            }
+        }
+
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = ::sequent::ParseEventError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                let args = ::sequent::fields::split_fields(s)?;
+                #decode
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// A parser for this event type, for registering with a [`::sequent::Decoder`].
+            #[must_use]
+            pub fn parser() -> ::std::boxed::Box<dyn ::sequent::NamedEventParser<State = <Self as ::sequent::Event>::State>>
+            where
+                Self: ::sequent::Event + 'static,
+            {
+                ::std::boxed::Box::new(::sequent::Parser::<Self>::default())
+            }
+        }
+    })
+}
+
+/// Obtains the value of the `name` key from the `#[event(...)]` attributes, if one is present.
+fn name_attr(attrs: &[Attribute]) -> Result<Option<String>, Error> {
+    let mut name = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                let value = lit.value();
+                if value.is_empty() || value.contains(|ch: char| ch.is_whitespace() || ch == '"') {
+                    return Err(Error::new_spanned(lit, "event name must be non-empty and free of whitespace and quotes"));
+                }
+                name = Some(value);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported event attribute; expected 'name'"))
+            }
+        })?;
+    }
+    Ok(name)
+}
+
+/// Converts a camel-cased identifier to kebab case; e.g., `ReversibleAppend` becomes `reversible-append`
+/// and `HTTPRequest` becomes `http-request`.
+fn kebab_case(ident: &str) -> String {
+    let chars = ident.trim_start_matches("r#").chars().collect::<Vec<_>>();
+    let mut kebab = String::with_capacity(chars.len() + 4);
+    for (index, &ch) in chars.iter().enumerate() {
+        if ch == '_' {
+            if !kebab.is_empty() && !kebab.ends_with('-') {
+                kebab.push('-');
+            }
+            continue;
+        }
+        if ch.is_uppercase() && index > 0 && !kebab.ends_with('-') {
+            let prev = chars[index - 1];
+            let next_is_lower = matches!(chars.get(index + 1), Some(next) if next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower) {
+                kebab.push('-');
+            }
+        }
+        kebab.extend(ch.to_lowercase());
+    }
+    kebab.trim_end_matches('-').into()
+}
+
+/// Generates the body of [`std::fmt::Display::fmt()`] for a struct, writing out its fields.
+fn encode_struct(fields: &Fields) -> TokenStream2 {
+    let values = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => quote! { self.#ident },
+            None => {
+                let index = syn::Index::from(index);
+                quote! { self.#index }
+            }
+        })
+        .collect::<Vec<_>>();
+    encode_values(None, &values)
+}
+
+/// Generates a sequence of writes of the optional `prefix` followed by the `values`, separated by
+/// spaces, quoting each value as necessary.
+fn encode_values(prefix: Option<&str>, values: &[TokenStream2]) -> TokenStream2 {
+    let mut writes = prefix
+        .map(|prefix| quote! { f.write_str(#prefix)?; })
+        .into_iter()
+        .collect::<Vec<_>>();
+    for value in values {
+        if !writes.is_empty() {
+            writes.push(quote! { f.write_str(" ")?; });
+        }
+        writes.push(quote! { ::sequent::fields::write_field(f, &#value)?; });
+    }
+    quote! {
+        {
+            #(#writes)*
+            ::std::result::Result::Ok(())
+        }
+    }
+}
+
+/// Generates an expression that constructs `constructor` from the `fields`, parsing the arguments
+/// starting at `offset`, or returns early if the number of arguments is wrong or an argument could
+/// not be parsed. `context` names the event (or the variant) in error messages.
+fn decode_fields(context: &str, constructor: TokenStream2, fields: &Fields, offset: usize) -> TokenStream2 {
+    let expected = fields.len();
+    let values = fields.iter().enumerate().map(|(index, field)| {
+        let ty = &field.ty;
+        let position = offset + index;
+        let value = quote! {
+            <#ty as ::std::str::FromStr>::from_str(&args[#position]).map_err(|err| {
+                ::sequent::ParseEventError(
+                    ::std::format!("invalid argument '{}' to '{}': {}", args[#position], #context, err).into(),
+                )
+            })?
+        };
+        match &field.ident {
+            Some(ident) => quote! { #ident: #value },
+            None => value,
+        }
+    });
+    let construct = match fields {
+        Fields::Named(_) => quote! { #constructor { #(#values),* } },
+        Fields::Unnamed(_) => quote! { #constructor ( #(#values),* ) },
+        Fields::Unit => constructor,
+    };
+
+    quote! {
+        {
+            if args.len() != #offset + #expected {
+                return ::std::result::Result::Err(::sequent::ParseEventError(
+                    ::std::format!("expected {} argument(s) to '{}', got {}", #expected, #context, args.len() - #offset).into(),
+                ));
+            }
+            ::std::result::Result::Ok(#construct)
+        }
+    }
+}
+
+/// Generates the bodies of [`std::fmt::Display::fmt()`] and [`std::str::FromStr::from_str()`] for an
+/// enum, wherein the fields of each variant are preceded by the name of the variant.
+fn codec_enum(name: &str, data: &DataEnum) -> Result<(TokenStream2, TokenStream2), Error> {
+    let mut tokens = BTreeSet::new();
+    let mut encode_arms = Vec::with_capacity(data.variants.len());
+    let mut decode_arms = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        let ident = &variant.ident;
+        let token = name_attr(&variant.attrs)?.unwrap_or_else(|| kebab_case(&ident.to_string()));
+        if !tokens.insert(token.clone()) {
+            return Err(Error::new_spanned(variant, format!("duplicate variant name '{token}'")));
+        }
+
+        let bindings = (0..variant.fields.len())
+            .map(|index| format_ident!("field_{}", index))
+            .collect::<Vec<_>>();
+        let pattern = match &variant.fields {
+            Fields::Named(fields) => {
+                let idents = fields.named.iter().map(|field| &field.ident);
+                quote! { Self::#ident { #(#idents: #bindings),* } }
+            }
+            Fields::Unnamed(_) => quote! { Self::#ident ( #(#bindings),* ) },
+            Fields::Unit => quote! { Self::#ident },
+        };
+        let values = bindings.iter().map(|binding| quote! { #binding }).collect::<Vec<_>>();
+        let encode = encode_values(Some(&token), &values);
+        encode_arms.push(quote! { #pattern => #encode });
+
+        let decode = decode_fields(&format!("{name} {token}"), quote! { Self::#ident }, &variant.fields, 1);
+        decode_arms.push(quote! { ::std::option::Option::Some(#token) => #decode });
+    }
+
+    let expected = tokens.iter().map(|token| format!("'{token}'")).collect::<Vec<_>>().join(", ");
+    let encode = if encode_arms.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! { match self { #(#encode_arms),* } }
+    };
+    let decode = quote! {
+        match args.first().map(::std::convert::AsRef::<str>::as_ref) {
+            #(#decode_arms,)*
+            ::std::option::Option::Some(other) => ::std::result::Result::Err(::sequent::ParseEventError(
+                ::std::format!("unknown variant '{}' of '{}', expected one of {}", other, #name, #expected).into(),
+            )),
+            ::std::option::Option::None => ::std::result::Result::Err(::sequent::ParseEventError(
+                ::std::format!("missing variant of '{}', expected one of {}", #name, #expected).into(),
+            )),
+        }
+    };
+    Ok((encode, decode))
+}
+
+#[cfg(test)]
+mod tests;
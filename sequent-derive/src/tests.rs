@@ -0,0 +1,21 @@
+// $coverage:ignore-start
+
+use crate::kebab_case;
+
+#[test]
+fn kebab_case_conversions() {
+    for (ident, expected) in [
+        ("Climb", "climb"),
+        ("ReversibleAppend", "reversible-append"),
+        ("HTTPRequest", "http-request"),
+        ("SendHTTP", "send-http"),
+        ("Stage2Complete", "stage2-complete"),
+        ("snake_case", "snake-case"),
+        ("Mixed_Case", "mixed-case"),
+        ("r#Type", "type"),
+        ("Trailing_", "trailing"),
+        ("A", "a"),
+    ] {
+        assert_eq!(expected, kebab_case(ident), "for {ident}");
+    }
+}
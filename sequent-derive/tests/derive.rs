@@ -0,0 +1,238 @@
+// $coverage:ignore-start
+
+use flanker_temp::TempPath;
+use sequent::persistence::yaml::{read_from_file, write_to_file};
+use sequent::{Decoder, Event, Named, ParseEventError, Queue, Scenario, StaticNamed, Timeline, TransitionError};
+use sequent_derive::Event;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct TestState {
+    balance: i64,
+    log: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Event)]
+struct Deposit {
+    account: String,
+    amount: i64,
+}
+
+impl Event for Deposit {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.balance += self.amount;
+        state.log.push(self.to_string());
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Event)]
+#[event(name = "fee")]
+struct ChargeFee(i64, f64);
+
+impl Event for ChargeFee {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.balance -= self.0;
+        state.log.push(self.to_string());
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Event)]
+struct CloseAccount;
+
+impl Event for CloseAccount {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.balance = 0;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Event)]
+enum Adjustment {
+    Credit { amount: i64 },
+    Debit(i64),
+    #[event(name = "zero")]
+    ResetToZero,
+}
+
+impl Event for Adjustment {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        match self {
+            Self::Credit { amount } => state.balance += amount,
+            Self::Debit(amount) => state.balance -= amount,
+            Self::ResetToZero => state.balance = 0,
+        }
+        state.log.push(self.to_string());
+        Ok(())
+    }
+}
+
+fn decoder() -> Decoder<TestState> {
+    Decoder::new(vec![Deposit::parser(), ChargeFee::parser(), CloseAccount::parser(), Adjustment::parser()])
+}
+
+#[test]
+fn names() {
+    assert_eq!("deposit", <Deposit as StaticNamed>::name());
+    assert_eq!("fee", <ChargeFee as StaticNamed>::name());
+    assert_eq!("close-account", <CloseAccount as StaticNamed>::name());
+    assert_eq!("adjustment", <Adjustment as StaticNamed>::name());
+    assert_eq!("fee", Named::name(&ChargeFee(1, 0.5)));
+}
+
+#[test]
+fn encode_and_decode_struct() {
+    let deposit = Deposit { account: "savings".into(), amount: -42 };
+    assert_eq!("savings -42", deposit.to_string());
+    assert_eq!(deposit, Deposit::from_str("savings -42").unwrap());
+    assert_eq!(deposit, Deposit::from_str("  savings \t -42 ").unwrap());
+}
+
+#[test]
+fn encode_and_decode_quoted_fields() {
+    for (account, encoded) in [
+        (String::new(), r#""" 1"#),
+        ("a b".into(), r#""a b" 1"#),
+        (r#"say "hi""#.into(), r#""say \"hi\"" 1"#),
+    ] {
+        let deposit = Deposit { account, amount: 1 };
+        assert_eq!(encoded, deposit.to_string());
+        assert_eq!(deposit, Deposit::from_str(encoded).unwrap());
+    }
+}
+
+#[test]
+fn decode_malformed_quotes() {
+    assert_eq!(
+        ParseEventError(r#"unterminated quoted field in '"a b 1'"#.into()),
+        Deposit::from_str(r#""a b 1"#).unwrap_err()
+    );
+}
+
+#[test]
+fn encode_and_decode_tuple_struct() {
+    let fee = ChargeFee(5, 0.25);
+    assert_eq!("5 0.25", fee.to_string());
+    assert_eq!(fee, ChargeFee::from_str("5 0.25").unwrap());
+}
+
+#[test]
+fn encode_and_decode_unit_struct() {
+    assert_eq!("", CloseAccount.to_string());
+    assert_eq!(CloseAccount, CloseAccount::from_str("").unwrap());
+}
+
+#[test]
+fn encode_and_decode_enum() {
+    for (adjustment, encoded) in [
+        (Adjustment::Credit { amount: 10 }, "credit 10"),
+        (Adjustment::Debit(7), "debit 7"),
+        (Adjustment::ResetToZero, "zero"),
+    ] {
+        assert_eq!(encoded, adjustment.to_string());
+        assert_eq!(adjustment, Adjustment::from_str(encoded).unwrap());
+    }
+}
+
+#[test]
+fn decode_wrong_number_of_arguments() {
+    assert_eq!(
+        ParseEventError("expected 2 argument(s) to 'deposit', got 1".into()),
+        Deposit::from_str("savings").unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError("expected 0 argument(s) to 'close-account', got 1".into()),
+        CloseAccount::from_str("now").unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError("expected 1 argument(s) to 'adjustment debit', got 2".into()),
+        Adjustment::from_str("debit 1 2").unwrap_err()
+    );
+}
+
+#[test]
+fn decode_invalid_argument() {
+    assert_eq!(
+        ParseEventError("invalid argument 'lots' to 'deposit': invalid digit found in string".into()),
+        Deposit::from_str("savings lots").unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError("invalid argument 'x' to 'fee': invalid float literal".into()),
+        ChargeFee::from_str("1 x").unwrap_err()
+    );
+}
+
+#[test]
+fn decode_unknown_variant() {
+    assert_eq!(
+        ParseEventError("unknown variant 'reset-to-zero' of 'adjustment', expected one of 'credit', 'debit', 'zero'".into()),
+        Adjustment::from_str("reset-to-zero").unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError("missing variant of 'adjustment', expected one of 'credit', 'debit', 'zero'".into()),
+        Adjustment::from_str(" ").unwrap_err()
+    );
+}
+
+#[test]
+fn decode_with_parsers() {
+    let decoder = decoder();
+    assert_eq!(
+        vec!["adjustment", "close-account", "deposit", "fee"],
+        decoder.parsers().map(|parser| parser.name()).collect::<Vec<_>>()
+    );
+    let event = decoder.decode("adjustment", "credit 3").unwrap();
+    assert_eq!("adjustment", event.name());
+    assert_eq!("credit 3", event.to_string());
+}
+
+#[test]
+fn yaml_round_trip() {
+    let scenario = Scenario {
+        initial: TestState::default(),
+        timeline: Timeline::from(vec![
+            Box::new(Deposit { account: "savings".into(), amount: 100 }) as Box<dyn Event<State = _>>,
+            Box::new(Deposit { account: String::new(), amount: 1 }),
+            Box::new(Deposit { account: "a b".into(), amount: 2 }),
+            Box::new(ChargeFee(3, 0.5)),
+            Box::new(Adjustment::Debit(20)),
+            Box::new(Adjustment::ResetToZero),
+            Box::new(CloseAccount),
+        ]),
+        seed: 0,
+    };
+    let path = TempPath::with_extension("yaml");
+    write_to_file(&scenario, &path).unwrap();
+
+    let decoded = read_from_file(&decoder(), &path).unwrap();
+    assert_eq!(scenario.initial, decoded.initial);
+    let encode = |timeline: &Timeline<TestState>| {
+        timeline
+            .iter()
+            .map(|event| (event.name().to_string(), event.to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(encode(&scenario.timeline), encode(&decoded.timeline));
+    assert_eq!(
+        vec![
+            ("deposit".to_string(), "savings 100".to_string()),
+            ("deposit".into(), r#""" 1"#.into()),
+            ("deposit".into(), r#""a b" 2"#.into()),
+            ("fee".into(), "3 0.5".into()),
+            ("adjustment".into(), "debit 20".into()),
+            ("adjustment".into(), "zero".into()),
+            ("close-account".into(), "".into()),
+        ],
+        encode(&decoded.timeline)
+    );
+}
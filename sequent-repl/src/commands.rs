@@ -1,16 +1,25 @@
 //! Commands used by the simulation.
 
+pub mod breakpoint;
+pub mod breaks;
+pub mod delete;
 pub mod event_proxy;
+pub mod graph;
 pub mod jump;
 pub mod load;
+pub mod r#move;
 pub mod next;
+pub mod prev;
 pub mod print;
 pub mod prompt;
+pub mod replace;
 pub mod reset;
 pub mod run;
 pub mod save;
+pub mod stats;
 pub mod timeline;
 pub mod truncate;
+pub mod unbreak;
 
 #[cfg(test)]
 pub mod test_fixtures;
@@ -0,0 +1,118 @@
+//! Setting of breakpoints.
+
+use crate::Context;
+use sequent::{Breakpoint, Condition, SimulationError};
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Command to set a breakpoint on an event name or a timeline location. The breakpoint will
+/// suspend subsequent runs either before (the default) or after the matching event.
+pub struct Break<S, C> {
+    breakpoint: Option<Breakpoint<S>>,
+    __phantom_data: PhantomData<C>
+}
+
+impl<S, C> Break<S, C> {
+    pub fn new(breakpoint: Breakpoint<S>) -> Self {
+        Self {
+            breakpoint: Some(breakpoint),
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S, C: Context<State = S>, T: Terminal> Command<T> for Break<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let breakpoint = self.breakpoint.take().unwrap();
+        let description = breakpoint.to_string();
+        let id = looper.context().sim().add_breakpoint(breakpoint);
+        looper
+            .terminal()
+            .print_line(&format!("Breakpoint {id} set {description}."))?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Break`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        if s.is_empty() {
+            return Err(ParseCommandError("empty arguments to 'break'".into()));
+        }
+        let mut args = s.split_whitespace().peekable();
+        let after = match args.peek() {
+            Some(&"before") => {
+                args.next();
+                false
+            }
+            Some(&"after") => {
+                args.next();
+                true
+            }
+            _ => false
+        };
+        let condition = match (args.next(), args.next(), args.next()) {
+            (Some("event"), Some(name), None) => Condition::Event(name.into()),
+            (Some("index"), Some(index), None) => Condition::Index(index.parse().map_err(ParseCommandError::convert)?),
+            _ => return Err(ParseCommandError(format!("invalid arguments to 'break': '{s}'").into()))
+        };
+        let breakpoint = if after {
+            Breakpoint::after(condition)
+        } else {
+            Breakpoint::before(condition)
+        };
+        Ok(Box::new(Break::new(breakpoint)))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        Some("b".into())
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "break".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Sets a breakpoint on an event name or a timeline location.".into(),
+            usage: "[before|after] <event <name>|index <location>>".into(),
+            examples: vec![
+                Example {
+                    scenario: "break before any 'append' event".into(),
+                    command: "event append".into(),
+                },
+                Example {
+                    scenario: "break after the event at location 2".into(),
+                    command: "after index 2".into(),
+                },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
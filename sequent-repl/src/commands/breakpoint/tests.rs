@@ -0,0 +1,62 @@
+// $coverage:ignore-start
+
+use crate::commands::breakpoint::{Break, Parser};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+use sequent::{Breakpoint, Condition, SimulationError};
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut command = Break::new(Breakpoint::after(Condition::Index(2)));
+    assert_eq!(ApplyOutcome::Applied, command.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Breakpoint 0 set after index 2.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(1, looper.context().sim().breakpoints().count());
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("break event append").unwrap();
+    commander.parse("break before event append").unwrap();
+    commander.parse("break after index 2").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "empty arguments to 'break'")]
+fn parse_empty_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("break").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "invalid arguments to 'break': 'after state'")]
+fn parse_invalid_condition_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("break after state").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "invalid digit found in string")]
+fn parse_invalid_index_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("break index two").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
@@ -0,0 +1,84 @@
+//! Listing of breakpoints.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use sequent::SimulationError;
+use revolver::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use crate::Context;
+
+/// Command that prints the registered breakpoints, along with their IDs, to the terminal device.
+pub struct Breaks<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Breaks<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S, C: Context<State = S>, T: Terminal> Command<T> for Breaks<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let (terminal, _, context) = looper.split();
+        let lines = context
+            .sim()
+            .breakpoints()
+            .map(|(id, breakpoint)| format!("{id}: {breakpoint}"))
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            terminal.print_line("No breakpoints.")?;
+        }
+        for line in lines {
+            terminal.print_line(&line)?;
+        }
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Breaks`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        self.parse_no_args(s, Breaks::default)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "breaks".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Lists the breakpoints.".into(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
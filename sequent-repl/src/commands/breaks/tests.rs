@@ -0,0 +1,58 @@
+// $coverage:ignore-start
+
+use sequent::{Breakpoint, Condition, SimulationError};
+use revolver::command::{ApplyOutcome, assert_pedantic, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+use crate::commands::breaks::{Breaks, Parser};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec! [
+        Box::new(Parser::default())
+    ]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().add_breakpoint(Breakpoint::before(Condition::Event("append".into())));
+    looper.context().sim().add_breakpoint(Breakpoint::after(Condition::Index(3)));
+    assert_eq!(ApplyOutcome::Applied, Breaks::default().apply(&mut looper).unwrap());
+    assert_eq!(
+        "0: before event 'append'\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(
+        "1: after index 3\n",
+        looper.terminal().invocations()[1].print().unwrap_output()
+    );
+}
+
+#[test]
+fn apply_none() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ApplyOutcome::Applied, Breaks::default().apply(&mut looper).unwrap());
+    assert_eq!(
+        "No breakpoints.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("breaks").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
@@ -0,0 +1,107 @@
+//! Removal of an event from the timeline.
+
+use crate::commands::prompt::confirm_divergence;
+use crate::Context;
+use sequent::{SimulationError, Validation};
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Command to delete the event at a specific location in the timeline. If the location precedes
+/// the cursor, the current state is re-derived without the deleted event. The user is asked to
+/// confirm the deletion if it would alter the behaviour of the subsequent events.
+pub struct Delete<S, C> {
+    location: usize,
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Delete<S, C> {
+    fn new(location: usize) -> Self {
+        Self {
+            location,
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S>, T: Terminal> Command<T> for Delete<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<C, SimulationError<S>, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        match looper.context().sim().remove_event(self.location, Validation::Checked) {
+            Ok(()) => {}
+            Err(SimulationError::Diverged(divergence)) => {
+                if !confirm_divergence(looper.terminal(), &divergence)? {
+                    return Ok(ApplyOutcome::Skipped);
+                }
+                looper.context().sim().remove_event(self.location, Validation::Unchecked).map_err(ApplyCommandError::Application)?;
+            }
+            Err(err) => return Err(ApplyCommandError::Application(err)),
+        }
+        let (terminal, _, context) = looper.split();
+        context.print_state(terminal)?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Delete`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        if s.is_empty() {
+            return Err(ParseCommandError(
+                "empty arguments to 'delete'".into(),
+            ));
+        }
+        let location = s.parse().map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Delete::new(location)))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "delete".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Deletes the event at a specified location in the timeline.".into(),
+            usage: "<location>".into(),
+            examples: vec![Example {
+                scenario: "delete the event at location 2".into(),
+                command: "2".into(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
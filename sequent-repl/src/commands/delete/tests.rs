@@ -0,0 +1,75 @@
+// $coverage:ignore-start
+
+use crate::commands::delete::{Delete, Parser};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+use sequent::SimulationError;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut delete = Delete::new(1);
+    assert_eq!(ApplyOutcome::Applied, delete.apply(&mut looper).unwrap());
+    assert!(!looper.terminal().invocations()[0].print().unwrap_output().is_empty());
+    assert_eq!(3, looper.context().sim().scenario().timeline.len());
+    assert_eq!(0, looper.context().sim().cursor());
+}
+
+#[test]
+fn apply_before_cursor() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().jump(2).unwrap();
+    let mut delete = Delete::new(0);
+    assert_eq!(ApplyOutcome::Applied, delete.apply(&mut looper).unwrap());
+    assert_eq!(1, looper.context().sim().cursor());
+    assert_eq!(vec![1], looper.context().sim().current_state().transitions);
+}
+
+#[test]
+fn apply_at_invalid_location() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut delete = Delete::new(4);
+    assert_eq!(
+        Some(4),
+        delete
+            .apply(&mut looper)
+            .unwrap_err()
+            .application()
+            .unwrap()
+            .invalid_location()
+    );
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("delete 2").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "empty arguments to 'delete'")]
+fn parse_empty_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("delete").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
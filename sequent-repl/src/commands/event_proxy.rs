@@ -1,13 +1,14 @@
 //! Evaluation of a specific event.
 
-use crate::commands::prompt::YesNo;
+use crate::commands::prompt::{InsertChoice, YesNo};
 use crate::Context;
-use sequent::{Event, SimulationError, StaticNamed};
+use sequent::{Acceptance, Event, SimulationError, StaticNamed};
 use revolver::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
 use revolver::looper::Looper;
 use revolver::terminal::{Terminal};
 use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::rc::Rc;
 use std::str::FromStr;
 
 /// Command that delegates its evaluation to that of an [`Event`] object.
@@ -25,41 +26,82 @@ impl<S, C> EventProxy<S, C> {
     }
 }
 
-impl<S, C: Context<State = S>, T: Terminal> Command<T> for EventProxy<S, C> {
+impl<S: Clone, C: Context<State = S>, T: Terminal> Command<T> for EventProxy<S, C> {
     type Context = C;
     type Error = SimulationError<S>;
 
     fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
-        let mut event = self.event.take().unwrap();
-        loop {
-            let result = looper.context().sim().push_event(event);
-            match result {
-                Ok(_) => {
-                    let (terminal, _, context) = looper.split();
-                    context.sim().step().map_err(ApplyCommandError::Application)?;
-                    context.print_state(terminal)?;
-                    return Ok(ApplyOutcome::Applied);
-                }
-                Err(SimulationError::TruncationRequired(rejected)) => {
-                    event = rejected;
-                    let response = looper.terminal().read_from_str_default(
-                        "Inserting into the timeline requires truncation. Continue? [y/N]: ",
-                    )?;
-                    match response {
-                        YesNo::Yes => {
-                            looper.context().sim().truncate();
-                        }
-                        YesNo::No => {
+        let event = self.event.take().unwrap();
+        let result = looper.context().sim().push_event(event);
+        match result {
+            Ok(_) => {}
+            Err(SimulationError::TruncationRequired(rejected)) => {
+                let sim = looper.context().sim();
+                let future = sim.scenario().timeline.len() - sim.cursor();
+                looper.terminal().print_line(&format!("Inserting into the timeline ahead of {future} future event(s)."))?;
+                let response = looper.terminal().read_from_str_default(
+                    "Retain the future? [accept/keep/truncate/Abort]: ",
+                )?;
+                let acceptance = match response {
+                    InsertChoice::Accept => Acceptance::All,
+                    InsertChoice::Keep => Acceptance::UntilDivergence,
+                    InsertChoice::Truncate => {
+                        let sim = looper.context().sim();
+                        sim.truncate();
+                        sim.push_event(rejected).map_err(ApplyCommandError::Application)?;
+                        return step(looper);
+                    }
+                    InsertChoice::Abort => {
+                        return Ok(ApplyOutcome::Skipped);
+                    }
+                };
+
+                // only the retained future is validated, so that truncation remains possible if validation fails
+                let event: Rc<dyn Event<State = S>> = rejected.into();
+                let sim = looper.context().sim();
+                let insertion = match sim.insert_shared_event(sim.cursor(), event.clone()) {
+                    Ok(insertion) => insertion,
+                    Err(err) => {
+                        looper.terminal().print_line(&format!("Could not validate the insertion: {err}."))?;
+                        let response: YesNo = looper.terminal().read_from_str_default("Truncate the future instead? [y/N]: ")?;
+                        if response == YesNo::No {
                             return Ok(ApplyOutcome::Skipped);
                         }
+
+                        // with no future events, there is nothing to validate
+                        let sim = looper.context().sim();
+                        sim.truncate();
+                        let insertion = sim.insert_shared_event(sim.cursor(), event).map_err(ApplyCommandError::Application)?;
+                        sim.accept_insertion(insertion, Acceptance::All).map_err(ApplyCommandError::Application)?;
+                        return step(looper);
                     }
+                };
+                match insertion.divergence() {
+                    None => looper.terminal().print_line(&format!(
+                        "Inserting into the timeline does not affect the future ({} event(s) replayed).",
+                        insertion.replayed()
+                    ))?,
+                    Some(divergence) => looper.terminal().print_line(&format!(
+                        "Inserting into the timeline affects the future: {divergence}."
+                    ))?,
                 }
-                Err(_) => unreachable!()
+                let sim = looper.context().sim();
+                sim.accept_insertion(insertion, acceptance).map_err(ApplyCommandError::Application)?;
             }
+            Err(_) => unreachable!()
         }
+        step(looper)
     }
 }
 
+/// Evaluates the event at the cursor, which is the one just added, and prints the resulting state.
+fn step<S, C: Context<State = S>, T: Terminal>(looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+    let (terminal, _, context) = looper.split();
+    context.sim().step().map_err(ApplyCommandError::Application)?;
+    context.print_state(terminal)?;
+    Ok(ApplyOutcome::Applied)
+}
+
 /// Parser for [`EventProxy`] of some event type.
 pub struct Parser<S, C, E: Event<State = S>> {
     shorthand: Option<Cow<'static, str>>,
@@ -84,7 +126,7 @@ impl<S, C, E> Parser<S, C, E>
     }
 }
 
-impl<S: 'static, C: Context<State = S> + 'static, E, T: Terminal> NamedCommandParser<T> for Parser<S, C, E>
+impl<S: Clone + 'static, C: Context<State = S> + 'static, E, T: Terminal> NamedCommandParser<T> for Parser<S, C, E>
     where
         E: FromStr + Event<State = S> + 'static,
         E::Err: ToString
@@ -8,7 +8,7 @@ use revolver::command::{
 };
 use revolver::looper::Looper;
 use revolver::terminal::{lines, AccessTerminalError, Mock, PrintOutput};
-use sequent::{SimulationError, TransitionError};
+use sequent::{Limits, SimulationError, TransitionError};
 use std::borrow::Cow;
 use std::marker::PhantomData;
 
@@ -46,46 +46,154 @@ fn apply_at_cursor() {
 }
 
 #[test]
-fn apply_with_truncate_yes() {
-    let mut term = Mock::default().on_read_line(lines(&["yes"]));
+fn apply_with_insertion_accept() {
+    let mut term = Mock::default().on_read_line(lines(&["accept"]));
     let commander = Commander::new(command_parsers());
     let mut context = TestContext::default();
     let mut looper = Looper::new(&mut term, &commander, &mut context);
     let mut proxy = EventProxy {
-        event: Some(Box::new(Append { id: 0 })),
+        event: Some(Box::new(Append { id: 9 })),
         __phantom_data: PhantomData::default(),
     };
     assert_eq!(ApplyOutcome::Applied, proxy.apply(&mut looper).unwrap());
     assert_eq!(
-        "Inserting into the timeline requires truncation. Continue? [y/N]: ",
+        "Inserting into the timeline ahead of 4 future event(s).\n",
         looper.terminal().invocations()[0].print().unwrap_output()
     );
+    assert_eq!(
+        "Retain the future? [accept/keep/truncate/Abort]: ",
+        looper.terminal().invocations()[1].print().unwrap_output()
+    );
+    assert_eq!(
+        "Inserting into the timeline does not affect the future (5 event(s) replayed).\n",
+        looper.terminal().invocations()[3].print().unwrap_output()
+    );
+    assert_eq!(vec![9], looper.context().sim().current_state().transitions);
+    assert_eq!(5, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_with_insertion_keep() {
+    let mut term = Mock::default().on_read_line(lines(&["keep"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut proxy = EventProxy {
+        event: Some(Box::new(Append { id: 2 })),
+        __phantom_data: PhantomData::default(),
+    };
+    assert_eq!(ApplyOutcome::Applied, proxy.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Inserting into the timeline affects the future: event 'append' failed: duplicate ID 2 (location 3).\n",
+        looper.terminal().invocations()[3].print().unwrap_output()
+    );
+    assert_eq!(vec![2], looper.context().sim().current_state().transitions);
+    assert_eq!(3, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_with_insertion_truncate() {
+    let mut term = Mock::default().on_read_line(lines(&["truncate"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut proxy = EventProxy {
+        event: Some(Box::new(Append { id: 0 })),
+        __phantom_data: PhantomData::default(),
+    };
+    assert_eq!(ApplyOutcome::Applied, proxy.apply(&mut looper).unwrap());
     assert_eq!(vec![0], looper.context().sim().current_state().transitions);
+    assert_eq!(1, looper.context().sim().scenario().timeline.len());
 }
 
 #[test]
-fn apply_with_truncate_no() {
-    let mut term = Mock::default().on_read_line(lines(&["no"]));
+fn apply_with_insertion_truncate_without_validation() {
+    let mut term = Mock::default().on_read_line(lines(&["truncate"]));
     let commander = Commander::new(command_parsers());
     let mut context = TestContext::default();
+    context.sim().set_limits(Limits {
+        max_steps: Some(1),
+        ..Limits::default()
+    });
     let mut looper = Looper::new(&mut term, &commander, &mut context);
     let mut proxy = EventProxy {
         event: Some(Box::new(Append { id: 0 })),
         __phantom_data: PhantomData::default(),
     };
+    assert_eq!(ApplyOutcome::Applied, proxy.apply(&mut looper).unwrap());
+    assert_eq!(vec![0], looper.context().sim().current_state().transitions);
+    assert_eq!(1, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_with_insertion_failed_validation_truncate() {
+    let mut term = Mock::default().on_read_line(lines(&["keep", "y"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    context.sim().set_limits(Limits {
+        max_steps: Some(1),
+        ..Limits::default()
+    });
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut proxy = EventProxy {
+        event: Some(Box::new(Append { id: 9 })),
+        __phantom_data: PhantomData::default(),
+    };
+    assert_eq!(ApplyOutcome::Applied, proxy.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Could not validate the insertion: step limit of 1 exceeded.\n",
+        looper.terminal().invocations()[3].print().unwrap_output()
+    );
+    assert_eq!(
+        "Truncate the future instead? [y/N]: ",
+        looper.terminal().invocations()[4].print().unwrap_output()
+    );
+    assert_eq!(vec![9], looper.context().sim().current_state().transitions);
+    assert_eq!(1, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_with_insertion_failed_validation_abort() {
+    let mut term = Mock::default().on_read_line(lines(&["accept", ""]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    context.sim().set_limits(Limits {
+        max_steps: Some(1),
+        ..Limits::default()
+    });
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut proxy = EventProxy {
+        event: Some(Box::new(Append { id: 9 })),
+        __phantom_data: PhantomData::default(),
+    };
     assert_eq!(ApplyOutcome::Skipped, proxy.apply(&mut looper).unwrap());
     assert_eq!(
-        "Inserting into the timeline requires truncation. Continue? [y/N]: ",
-        looper.terminal().invocations()[0].print().unwrap_output()
+        vec![] as Vec<usize>,
+        looper.context().sim().current_state().transitions
     );
+    assert_eq!(4, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_with_insertion_abort() {
+    let mut term = Mock::default().on_read_line(lines(&["abort"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut proxy = EventProxy {
+        event: Some(Box::new(Append { id: 0 })),
+        __phantom_data: PhantomData::default(),
+    };
+    assert_eq!(ApplyOutcome::Skipped, proxy.apply(&mut looper).unwrap());
     assert_eq!(
         vec![] as Vec<usize>,
         looper.context().sim().current_state().transitions
     );
+    assert_eq!(4, looper.context().sim().scenario().timeline.len());
 }
 
 #[test]
-fn apply_with_truncate_terminal_error() {
+fn apply_with_insertion_terminal_error() {
     let mut term =
         Mock::default().on_read_line(|| Err(AccessTerminalError("terminal exploded".into())));
     let commander = Commander::new(command_parsers());
@@ -0,0 +1,120 @@
+//! Exporting of the causality graph of the current timeline.
+
+use crate::commands::prompt::YesNo;
+use crate::Context;
+use sequent::graph;
+use sequent::SimulationError;
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+
+/// Command to export the causality graph of the timeline to a user-specified output file. The format
+/// (DOT or Mermaid) is inferred from the file extension. If the file exists, a yes/no prompt will be
+/// presented before overwriting it.
+pub struct Graph<S, C> {
+    path: String,
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Graph<S, C> {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone, C: Context<State = S>, T: Terminal> Command<T> for Graph<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<C, SimulationError<S>, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let path = PathBuf::from(&self.path);
+        if path.exists() {
+            let response = looper
+                .terminal()
+                .read_from_str_default("Output file exists. Overwrite? [y/N]: ")?;
+
+            if let YesNo::No = response {
+                return Ok(ApplyOutcome::Skipped);
+            }
+        }
+        graph::write_to_file(&looper.context().sim().scenario().timeline, path)
+            .map_err(SimulationError::from)
+            .map_err(ApplyCommandError::Application)?;
+
+        looper
+            .terminal()
+            .print_line(&format!("Saved graph to '{}'.", self.path))?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Graph`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T>
+    for Parser<S, C>
+{
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        if s.is_empty() {
+            return Err(ParseCommandError("empty arguments to 'graph'".into()));
+        }
+        let path = s.into();
+        Ok(Box::new(Graph::new(path)))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "graph".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Exports the causality graph of the timeline to a DOT or Mermaid file.".into(),
+            usage: "<path>".into(),
+            examples: vec![
+                Example {
+                    scenario: "export a Graphviz graph to 'trixie.dot' in the working directory".into(),
+                    command: "trixie.dot".into(),
+                },
+                Example {
+                    scenario: "export a Mermaid flowchart to 'trixie.mmd' in the working directory".into(),
+                    command: "trixie.mmd".into(),
+                },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+// $coverage:ignore-start
+
+use crate::commands::graph::{Graph, Parser};
+use crate::commands::test_fixtures::{read_str_from_file, write_str_to_file, TestContext, TestState};
+use sequent::SimulationError;
+use flanker_temp::TempPath;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{lines, Mock, PrintOutput};
+
+fn command_parsers<'d>(
+) -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply_new_file() {
+    let temp = TempPath::with_extension("dot");
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut graph = Graph::new(path.clone());
+    assert_eq!(ApplyOutcome::Applied, graph.apply(&mut looper).unwrap());
+    assert_eq!(
+        format!("Saved graph to '{}'.\n", path),
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert!(read_str_from_file(&temp).starts_with("digraph timeline {\n"));
+    drop(temp);
+}
+
+#[test]
+fn apply_unsupported_format() {
+    let temp = TempPath::with_extension("yaml");
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut graph = Graph::new(path);
+    assert!(graph.apply(&mut looper)
+        .unwrap_err()
+        .application()
+        .unwrap()
+        .write_graph()
+        .unwrap()
+        .unsupported_file_format()
+        .is_some());
+}
+
+#[test]
+fn apply_existing_file_overwrite() {
+    const DUMMY_DATA: &str = "dummy data";
+    let temp = TempPath::with_extension("mmd");
+    write_str_to_file(&temp, DUMMY_DATA);
+
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default().on_read_line(lines(&["yes"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut graph = Graph::new(path.clone());
+    assert_eq!(ApplyOutcome::Applied, graph.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Output file exists. Overwrite? [y/N]: ",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(
+        format!("Saved graph to '{}'.\n", path),
+        looper.terminal().invocations()[2].print().unwrap_output()
+    );
+    assert!(read_str_from_file(&temp).starts_with("flowchart TD\n"));
+    drop(temp);
+}
+
+#[test]
+fn apply_existing_file_skip() {
+    const DUMMY_DATA: &str = "dummy data";
+    let temp = TempPath::with_extension("dot");
+    write_str_to_file(&temp, DUMMY_DATA);
+
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default().on_read_line(lines(&["no"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut graph = Graph::new(path.clone());
+    assert_eq!(ApplyOutcome::Skipped, graph.apply(&mut looper).unwrap());
+    assert_eq!(DUMMY_DATA, read_str_from_file(&temp));
+    drop(temp);
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("graph out.dot").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "empty arguments to 'graph'")]
+fn parse_empty_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("graph").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
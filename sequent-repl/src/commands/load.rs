@@ -1,7 +1,7 @@
-//! Loading of a simulation from a YAML document.
+//! Loading of a simulation from a YAML, JSON or binary document.
 
 use crate::{Context};
-use sequent::persistence::yaml;
+use sequent::persistence;
 use sequent::{SimulationError};
 use revolver::command::{
     ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
@@ -14,9 +14,9 @@ use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 
-/// Command that will load the simulation from a user-specified YAML file. Upon completion, the
-/// simulation will be reset to the initial state, as per the loaded file, and the cursor
-/// position reset to 0.
+/// Command that will load the simulation from a user-specified YAML, JSON or binary file, the format
+/// being implied by the file extension. Upon completion, the simulation will be reset to the initial
+/// state, as per the loaded file, and the cursor position reset to 0.
 pub struct Load<S, C> {
     path: String,
     __phantom_data: PhantomData<(S, C)>
@@ -44,7 +44,7 @@ where
     ) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
         let path = PathBuf::from(&self.path);
         let decoder = looper.context().decoder();
-        let scenario = yaml::read_from_file(decoder, path)
+        let scenario = persistence::read_from_file(decoder, path)
             .map_err(SimulationError::from)
             .map_err(ApplyCommandError::Application)?;
         looper.context().sim().set_scenario(scenario);
@@ -98,10 +98,20 @@ where
         Description {
             purpose: "Loads a scenario from a file.".into(),
             usage: "<path>".into(),
-            examples: vec![Example {
-                scenario: "load from a file named 'trixie.yaml' in the working directory".into(),
-                command: "trixie.yaml".into(),
-            }],
+            examples: vec![
+                Example {
+                    scenario: "load from a YAML file named 'trixie.yaml' in the working directory".into(),
+                    command: "trixie.yaml".into(),
+                },
+                Example {
+                    scenario: "load from a JSON file named 'trixie.json' in the working directory".into(),
+                    command: "trixie.json".into(),
+                },
+                Example {
+                    scenario: "load from a binary file named 'trixie.seqb' in the working directory".into(),
+                    command: "trixie.seqb".into(),
+                },
+            ],
         }
     }
 }
@@ -3,7 +3,7 @@
 use crate::commands::load::{Load, Parser};
 use crate::commands::test_fixtures::{write_str_to_file, TestContext, TestState};
 use crate::Context;
-use sequent::persistence::yaml::write_to_file;
+use sequent::persistence::write_to_file;
 use sequent::SimulationError;
 use flanker_temp::TempPath;
 use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
@@ -40,6 +40,71 @@ fn apply() {
     assert_eq!(8, looper.context().sim().scenario().timeline.len());
 }
 
+#[test]
+fn apply_json() {
+    let temp = TempPath::with_extension("json");
+    {
+        let mut context = TestContext::new(8);
+        write_to_file(context.sim().scenario(), &temp).unwrap();
+    }
+
+    let mut term =  Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::new(4);
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut load = Load::new(temp.as_ref().to_string_lossy().to_string());
+    assert_eq!(ApplyOutcome::Applied, load.apply(&mut looper).unwrap());
+    assert_eq!(8, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_binary() {
+    let temp = TempPath::with_extension("seqb");
+    {
+        let mut context = TestContext::new(8);
+        write_to_file(context.sim().scenario(), &temp).unwrap();
+    }
+
+    let mut term =  Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::new(4);
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut load = Load::new(temp.as_ref().to_string_lossy().to_string());
+    assert_eq!(ApplyOutcome::Applied, load.apply(&mut looper).unwrap());
+    assert_eq!(8, looper.context().sim().scenario().timeline.len());
+}
+
+#[test]
+fn apply_unsupported_extension() {
+    let mut term =  Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::new(4);
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut load = Load::new("in.txt".into());
+    assert!(load
+        .apply(&mut looper)
+        .unwrap_err()
+        .application()
+        .unwrap()
+        .read_scenario()
+        .unwrap()
+        .unsupported_file_format()
+        .is_some());
+    assert_eq!(4, looper.context().sim().scenario().timeline.len());
+}
+
 #[test]
 fn apply_corrupt_file() {
     const DUMMY_DATA: &str = "dummy data";
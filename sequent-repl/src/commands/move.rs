@@ -0,0 +1,111 @@
+//! Relocation of an event within the timeline.
+
+use crate::commands::prompt::confirm_divergence;
+use crate::Context;
+use sequent::{SimulationError, Validation};
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Command to move an event from one location in the timeline to another. If either location
+/// precedes the cursor, the current state is re-derived. The user is asked to confirm the move
+/// if it would alter the behaviour of the subsequent events.
+pub struct Move<S, C> {
+    from: usize,
+    to: usize,
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Move<S, C> {
+    fn new(from: usize, to: usize) -> Self {
+        Self {
+            from,
+            to,
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S>, T: Terminal> Command<T> for Move<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<C, SimulationError<S>, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        match looper.context().sim().move_event(self.from, self.to, Validation::Checked) {
+            Ok(()) => {}
+            Err(SimulationError::Diverged(divergence)) => {
+                if !confirm_divergence(looper.terminal(), &divergence)? {
+                    return Ok(ApplyOutcome::Skipped);
+                }
+                looper.context().sim().move_event(self.from, self.to, Validation::Unchecked).map_err(ApplyCommandError::Application)?;
+            }
+            Err(err) => return Err(ApplyCommandError::Application(err)),
+        }
+        let (terminal, _, context) = looper.split();
+        context.print_state(terminal)?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Move`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        let mut args = s.split_whitespace();
+        let (Some(from), Some(to), None) = (args.next(), args.next(), args.next()) else {
+            return Err(ParseCommandError(
+                "expected two arguments to 'move'".into(),
+            ));
+        };
+        let from = from.parse().map_err(ParseCommandError::convert)?;
+        let to = to.parse().map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Move::new(from, to)))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "move".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Moves the event at one location in the timeline to another location.".into(),
+            usage: "<from> <to>".into(),
+            examples: vec![Example {
+                scenario: "move the event at location 5 to location 2".into(),
+                command: "5 2".into(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+// $coverage:ignore-start
+
+use crate::commands::r#move::{Move, Parser};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+use sequent::SimulationError;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().jump(2).unwrap();
+    let mut r#move = Move::new(3, 0);
+    assert_eq!(ApplyOutcome::Applied, r#move.apply(&mut looper).unwrap());
+    assert!(!looper.terminal().invocations()[0].print().unwrap_output().is_empty());
+    assert_eq!(3, looper.context().sim().cursor());
+    assert_eq!(vec![3, 0, 1], looper.context().sim().current_state().transitions);
+}
+
+#[test]
+fn apply_at_invalid_location() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut r#move = Move::new(0, 4);
+    assert_eq!(
+        Some(4),
+        r#move
+            .apply(&mut looper)
+            .unwrap_err()
+            .application()
+            .unwrap()
+            .invalid_location()
+    );
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("move 3 0").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expected two arguments to 'move'")]
+fn parse_one_arg_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("move 3").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expected two arguments to 'move'")]
+fn parse_three_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("move 3 0 1").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
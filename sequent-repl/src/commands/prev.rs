@@ -0,0 +1,77 @@
+//! Stepping back over the previous event.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use sequent::SimulationError;
+use revolver::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use crate::Context;
+
+/// Command to step back over the previous event in the timeline. Upon completion, the current simulation
+/// state will reflect the sequential application of all events up to but not including the previous event,
+/// and the cursor location will retreat to the previous event.
+pub struct Prev<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Prev<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone, C: Context<State = S>, T: Terminal> Command<T> for Prev<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let (terminal, _, context) = looper.split();
+        context.sim().step_back().map_err(ApplyCommandError::Application)?;
+        context.print_state(terminal)?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Prev`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        self.parse_no_args(s, Prev::default)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        Some("pv".into())
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "prev".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Steps back over the previous event in the timeline.".into(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,53 @@
+// $coverage:ignore-start
+
+use sequent::SimulationError;
+use revolver::command::{ApplyOutcome, assert_pedantic, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+use crate::commands::prev::{Parser, Prev};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec! [
+        Box::new(Parser::default())
+    ]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().jump(2).unwrap();
+    assert_eq!(ApplyOutcome::Applied, Prev::default().apply(&mut looper).unwrap());
+    assert!(!looper.terminal().invocations()[0].print().unwrap_output().is_empty());
+    assert_eq!(1, looper.context().sim().cursor());
+    assert_eq!(vec![0], looper.context().sim().current_state().transitions);
+}
+
+#[test]
+fn apply_at_start_raises_timeline_exhausted() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert!(Prev::default()
+        .apply(&mut looper)
+        .unwrap_err()
+        .application()
+        .unwrap()
+        .is_timeline_exhausted());
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("prev").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
@@ -1,5 +1,7 @@
 //! Types for prompting the user over a terminal interface.
 
+use revolver::terminal::{AccessTerminalError, Terminal};
+use sequent::Divergence;
 use std::str::FromStr;
 
 /// A yes/no prompt. Defaults to 'no'.
@@ -26,5 +28,47 @@ impl FromStr for YesNo {
     }
 }
 
+/// A prompt for resolving an insertion into the timeline. Defaults to 'abort'.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InsertChoice {
+    /// Retain all future events.
+    Accept,
+
+    /// Retain the future events up to the divergence.
+    Keep,
+
+    /// Discard all future events.
+    Truncate,
+
+    /// Leave the timeline unchanged.
+    #[default]
+    Abort,
+}
+
+impl FromStr for InsertChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_lowercase()[..] {
+            "accept" => Ok(Self::Accept),
+            "keep" => Ok(Self::Keep),
+            "truncate" => Ok(Self::Truncate),
+            "abort" => Ok(Self::Abort),
+            other => Err(format!("'{other}' is not one of accept/keep/truncate/abort"))
+        }
+    }
+}
+
+/// Reports the divergence caused by a timeline edit, asking the user whether to apply the edit
+/// regardless. Returns `true` if the user confirmed.
+///
+/// # Errors
+/// [`AccessTerminalError`] if an error occurs while accessing the terminal.
+pub fn confirm_divergence(terminal: &mut impl Terminal, divergence: &Divergence) -> Result<bool, AccessTerminalError> {
+    terminal.print_line(&format!("Editing the timeline affects the future: {divergence}."))?;
+    let response: YesNo = terminal.read_from_str_default("Apply the edit anyway? [y/N]: ")?;
+    Ok(response == YesNo::Yes)
+}
+
 #[cfg(test)]
 mod tests;
\ No newline at end of file
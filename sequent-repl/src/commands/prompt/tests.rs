@@ -1,7 +1,7 @@
 // $coverage:ignore-start
 
 use std::str::FromStr;
-use crate::commands::prompt::YesNo;
+use crate::commands::prompt::{InsertChoice, YesNo};
 
 #[test]
 fn yes_no_from_str() {
@@ -19,4 +19,20 @@ fn yes_no_from_str() {
 #[test]
 fn yes_no_implements_default() {
     assert_eq!(YesNo::No, YesNo::default());
-}
\ No newline at end of file
+}
+#[test]
+fn insert_choice_from_str() {
+    assert_eq!(Ok(InsertChoice::Accept), "accept".parse());
+    assert_eq!(Ok(InsertChoice::Keep), "Keep".parse());
+    assert_eq!(Ok(InsertChoice::Truncate), "TRUNCATE".parse());
+    assert_eq!(Ok(InsertChoice::Abort), "abort".parse());
+    assert_eq!(
+        Err("'yes' is not one of accept/keep/truncate/abort".into()),
+        InsertChoice::from_str("yes")
+    );
+}
+
+#[test]
+fn insert_choice_implements_default() {
+    assert_eq!(InsertChoice::Abort, InsertChoice::default());
+}
@@ -0,0 +1,129 @@
+//! Replacement of an event in the timeline.
+
+use crate::commands::prompt::confirm_divergence;
+use crate::Context;
+use sequent::{Event, ParseEventError, SimulationError, Validation};
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Command to replace the event at a specific location in the timeline with a new event, decoded
+/// using the context's [`sequent::Decoder`]. If the location precedes the cursor, the current state
+/// is re-derived with the replacement. The user is asked to confirm the replacement if it would
+/// alter the behaviour of the subsequent events.
+pub struct Replace<S, C> {
+    location: usize,
+    name: String,
+    encoded: String,
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Replace<S, C> {
+    fn new(location: usize, name: String, encoded: String) -> Self {
+        Self {
+            location,
+            name,
+            encoded,
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S, C: Context<State = S>> Replace<S, C> {
+    fn decode(&self, context: &C) -> Result<Box<dyn Event<State = S>>, ParseEventError> {
+        context.decoder().decode(&self.name, &self.encoded)
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S>, T: Terminal> Command<T> for Replace<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<C, SimulationError<S>, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let event = match self.decode(looper.context()) {
+            Ok(event) => event,
+            Err(err) => {
+                looper.terminal().print_line(&format!("Invalid event: {err}."))?;
+                return Ok(ApplyOutcome::Skipped);
+            }
+        };
+        let event: Rc<dyn Event<State = S>> = event.into();
+        match looper.context().sim().replace_shared_event(self.location, event.clone(), Validation::Checked) {
+            Ok(()) => {}
+            Err(SimulationError::Diverged(divergence)) => {
+                if !confirm_divergence(looper.terminal(), &divergence)? {
+                    return Ok(ApplyOutcome::Skipped);
+                }
+                looper.context().sim().replace_shared_event(self.location, event, Validation::Unchecked).map_err(ApplyCommandError::Application)?;
+            }
+            Err(err) => return Err(ApplyCommandError::Application(err)),
+        }
+        let (terminal, _, context) = looper.split();
+        context.print_state(terminal)?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Replace`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Clone + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        let mut args = s.splitn(3, char::is_whitespace);
+        let (Some(location), Some(name)) = (args.next().filter(|arg| !arg.is_empty()), args.next()) else {
+            return Err(ParseCommandError(
+                "expected location and event arguments to 'replace'".into(),
+            ));
+        };
+        let location = location.parse().map_err(ParseCommandError::convert)?;
+        let encoded = args.next().unwrap_or_default().trim();
+        Ok(Box::new(Replace::new(location, name.into(), encoded.into())))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "replace".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Replaces the event at a specified location in the timeline.".into(),
+            usage: "<location> <event> [<arguments>]".into(),
+            examples: vec![Example {
+                scenario: "replace the event at location 2 with 'append 42'".into(),
+                command: "2 append 42".into(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
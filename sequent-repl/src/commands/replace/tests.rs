@@ -0,0 +1,99 @@
+// $coverage:ignore-start
+
+use crate::commands::replace::{Parser, Replace};
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::Context;
+use sequent::SimulationError;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{lines, Mock, PrintOutput};
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+fn timeline(context: &mut TestContext) -> Vec<String> {
+    context.sim().scenario().timeline.iter().map(|event| event.to_string()).collect()
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut replace = Replace::new(1, "append".into(), "9".into());
+    assert_eq!(ApplyOutcome::Applied, replace.apply(&mut looper).unwrap());
+    assert!(!looper.terminal().invocations()[0].print().unwrap_output().is_empty());
+    assert_eq!(vec!["0", "9", "2", "3"], timeline(looper.context()));
+}
+
+#[test]
+fn apply_with_divergence_yes() {
+    let mut term = Mock::default().on_read_line(lines(&["yes"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut replace = Replace::new(1, "append".into(), "3".into());
+    assert_eq!(ApplyOutcome::Applied, replace.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Editing the timeline affects the future: event 'append' failed: duplicate ID 3 (location 3).\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(
+        "Apply the edit anyway? [y/N]: ",
+        looper.terminal().invocations()[1].print().unwrap_output()
+    );
+    assert_eq!(vec!["0", "3", "2", "3"], timeline(looper.context()));
+}
+
+#[test]
+fn apply_with_divergence_no() {
+    let mut term = Mock::default().on_read_line(lines(&["no"]));
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut replace = Replace::new(1, "append".into(), "3".into());
+    assert_eq!(ApplyOutcome::Skipped, replace.apply(&mut looper).unwrap());
+    assert_eq!(vec!["0", "1", "2", "3"], timeline(looper.context()));
+}
+
+#[test]
+fn apply_with_invalid_event() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    let mut replace = Replace::new(1, "prepend".into(), "9".into());
+    assert_eq!(ApplyOutcome::Skipped, replace.apply(&mut looper).unwrap());
+    assert_eq!(
+        "Invalid event: no event parser for 'prepend'.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(vec!["0", "1", "2", "3"], timeline(looper.context()));
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("replace 1 append 42").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expected location and event arguments to 'replace'")]
+fn parse_empty_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("replace").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expected location and event arguments to 'replace'")]
+fn parse_missing_event_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("replace 1").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
@@ -2,14 +2,15 @@
 
 use std::borrow::Cow;
 use std::marker::PhantomData;
-use sequent::SimulationError;
+use sequent::{RunOptions, SimulationError};
 use revolver::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
 use revolver::looper::Looper;
 use revolver::terminal::Terminal;
 use crate::Context;
 
 /// Command to evaluate the remaining events in the timeline. By completion, the simulation state will
-/// reflect the sequential application of all events.
+/// reflect the sequential application of all events, unless the run was suspended by a breakpoint
+/// or halted by one of the simulation's limits.
 pub struct Run<S, C> {
     __phantom_data: PhantomData<(S, C)>
 }
@@ -28,7 +29,20 @@ impl<S, C: Context<State = S>, T: Terminal> Command<T> for Run<S, C> {
 
     fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
         let (terminal, _, context) = looper.split();
-        context.sim().run().map_err(ApplyCommandError::Application)?;
+        let report = match context.sim().run_with(&RunOptions::default()) {
+            Err(err) if err.is_limit_exceeded() => {
+                terminal.print_line(&format!("Run halted: {err}."))?;
+                context.print_state(terminal)?;
+                return Ok(ApplyOutcome::Applied);
+            }
+            result => result.map_err(ApplyCommandError::Application)?
+        };
+        if let Some(failure) = report.failures.into_iter().next() {
+            return Err(ApplyCommandError::Application(SimulationError::Transition(failure.error)));
+        }
+        if let Some(hit) = report.breakpoint {
+            terminal.print_line(&format!("Breakpoint {} hit {} location {}.", hit.id, hit.timing, hit.index))?;
+        }
         context.print_state(terminal)?;
         Ok(ApplyOutcome::Applied)
     }
@@ -65,7 +79,7 @@ impl<S: 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParse
 
     fn description(&self) -> Description {
         Description {
-            purpose: "Evaluates the remaining events in the timeline.".into(),
+            purpose: "Evaluates the remaining events in the timeline, stopping at breakpoints and limits.".into(),
             usage: Cow::default(),
             examples: Vec::default()
         }
@@ -1,11 +1,11 @@
 // $coverage:ignore-start
 
-use sequent::SimulationError;
+use sequent::{Breakpoint, Condition, Limits, SimulationError, TransitionError};
 use revolver::command::{ApplyOutcome, assert_pedantic, Command, Commander, NamedCommandParser};
 use revolver::looper::Looper;
 use revolver::terminal::{Mock, PrintOutput};
 use crate::commands::run::{Parser, Run};
-use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::commands::test_fixtures::{Append, TestContext, TestState};
 use crate::Context;
 
 fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
@@ -26,6 +26,62 @@ fn apply() {
     assert_eq!(4, looper.context().sim().cursor());
 }
 
+#[test]
+fn apply_with_breakpoint() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().add_breakpoint(Breakpoint::before(Condition::Index(2)));
+    assert_eq!(ApplyOutcome::Applied, Run::default().apply(&mut looper).unwrap());
+    assert_eq!(
+        "Breakpoint 0 hit before location 2.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert!(!looper.terminal().invocations()[1].print().unwrap_output().is_empty());
+    assert_eq!(2, looper.context().sim().cursor());
+}
+
+#[test]
+fn apply_with_duplicate_raises_transition_error() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().jump(4).unwrap();
+    looper.context().sim().push_event(Box::new(Append { id: 0 })).unwrap();
+    assert_eq!(
+        TransitionError("duplicate ID 0".into()),
+        Run::default()
+            .apply(&mut looper)
+            .unwrap_err()
+            .application()
+            .unwrap()
+            .transition()
+            .unwrap()
+    );
+    assert_eq!(4, looper.context().sim().cursor());
+}
+
+#[test]
+fn apply_with_step_limit() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().set_limits(Limits {
+        max_steps: Some(3),
+        ..Limits::default()
+    });
+    assert_eq!(ApplyOutcome::Applied, Run::default().apply(&mut looper).unwrap());
+    assert_eq!(
+        "Run halted: step limit of 3 exceeded.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert!(!looper.terminal().invocations()[1].print().unwrap_output().is_empty());
+    assert_eq!(3, looper.context().sim().cursor());
+}
+
 #[test]
 fn parse() {
     let commander = Commander::new(command_parsers());
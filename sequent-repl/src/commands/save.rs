@@ -1,8 +1,8 @@
-//! Saving of the current scenario to a YAML document.
+//! Saving of the current scenario to a YAML, JSON or binary document.
 
 use crate::commands::prompt::YesNo;
 use crate::Context;
-use sequent::persistence::yaml;
+use sequent::persistence;
 use sequent::SimulationError;
 use revolver::command::{
     ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
@@ -15,8 +15,8 @@ use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 
-/// Command to save the scenario to a user-specified output file. If the file exists, a yes/no prompt
-/// will be presented before overwriting it.
+/// Command to save the scenario to a user-specified output file, in the format implied by the file
+/// extension. If the file exists, a yes/no prompt will be presented before overwriting it.
 pub struct Save<S, C> {
     path: String,
     __phantom_data: PhantomData<(S, C)>
@@ -49,7 +49,7 @@ impl<S: Clone + Serialize, C: Context<State = S>, T: Terminal> Command<T> for Sa
                 return Ok(ApplyOutcome::Skipped);
             }
         }
-        yaml::write_to_file(looper.context().sim().scenario(), path)
+        persistence::write_to_file(looper.context().sim().scenario(), path)
             .map_err(SimulationError::from)
             .map_err(ApplyCommandError::Application)?;
 
@@ -102,10 +102,20 @@ impl<S: Clone + Serialize + 'static, C: Context<State = S> + 'static, T: Termina
         Description {
             purpose: "Saves the current scenario to a file.".into(),
             usage: "<path>".into(),
-            examples: vec![Example {
-                scenario: "save to a file named 'trixie.yaml' in the working directory".into(),
-                command: "trixie.yaml".into(),
-            }],
+            examples: vec![
+                Example {
+                    scenario: "save to a YAML file named 'trixie.yaml' in the working directory".into(),
+                    command: "trixie.yaml".into(),
+                },
+                Example {
+                    scenario: "save to a JSON file named 'trixie.json' in the working directory".into(),
+                    command: "trixie.json".into(),
+                },
+                Example {
+                    scenario: "save to a binary file named 'trixie.seqb' in the working directory".into(),
+                    command: "trixie.seqb".into(),
+                },
+            ],
         }
     }
 }
@@ -35,6 +35,54 @@ fn apply_new_file() {
     drop(temp);
 }
 
+#[test]
+fn apply_new_json_file() {
+    let temp = TempPath::with_extension("json");
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::new(2);
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut save = Save::new(path.clone());
+    assert_eq!(ApplyOutcome::Applied, save.apply(&mut looper).unwrap());
+    assert_eq!(
+        format!("Saved scenario to '{}'.\n", path),
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+
+    let written = read_str_from_file(&temp);
+    assert!(written.starts_with("{\n  \"initial\": "), "{written}");
+    drop(temp);
+}
+
+#[test]
+fn apply_new_binary_file() {
+    let temp = TempPath::with_extension("seqb");
+    let path = temp.as_ref().to_string_lossy().to_string();
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::new(2);
+    let mut looper = Looper::new(
+        &mut term,
+        &commander,
+        &mut context,
+    );
+    let mut save = Save::new(path.clone());
+    assert_eq!(ApplyOutcome::Applied, save.apply(&mut looper).unwrap());
+    assert_eq!(
+        format!("Saved scenario to '{}'.\n", path),
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+
+    let written = fs::read(&temp).unwrap();
+    assert_eq!(b"SEQB", &written[..4]);
+    drop(temp);
+}
+
 #[test]
 fn apply_existing_file_is_directory_io_error() {
     let temp = TempPath::with_extension("yaml");
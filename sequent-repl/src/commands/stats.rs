@@ -0,0 +1,189 @@
+//! Printing of the statistics carried by the current state.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use stanza::renderer::console::{Console, Decor};
+use stanza::renderer::Renderer;
+use stanza::style::{Bold, HAlign, MinWidth, Palette16, Styles, TextFg};
+use stanza::table::{Col, Row, Table};
+use sequent::stats::{Histogram, Measured, Statistics};
+use sequent::{SimulationError, Time};
+use revolver::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use crate::Context;
+
+/// Command to print the statistics of the current state as a table to the terminal device, followed
+/// by a table for each histogram. Time-weighted averages are taken up to the current simulation time.
+pub struct Stats<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Stats<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Measured, C: Context<State = S>, T: Terminal> Command<T> for Stats<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        let (terminal, _, context) = looper.split();
+        let sim = context.sim();
+        let statistics = sim.current_state().statistics();
+        if statistics.is_empty() {
+            terminal.print_line("No statistics.")?;
+            return Ok(ApplyOutcome::Applied);
+        }
+
+        let renderer = Console(
+            Decor::default()
+                .suppress_all_lines()
+                .suppress_outer_border(),
+        );
+        terminal.print_line(&renderer.render(&summary(statistics, sim.now())))?;
+        for (name, histogram) in statistics.histograms() {
+            terminal.print_line(&renderer.render(&bins(name, histogram)))?;
+        }
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+/// Parser for [`Stats`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: Measured + 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        self.parse_no_args(s, Stats::default)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "stats".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Displays the statistics collected up to the current simulation time.".into(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
+fn header(cells: Vec<&str>) -> Row {
+    Row::new(
+        Styles::default()
+            .with(Bold(true))
+            .with(TextFg(Palette16::Yellow)),
+        cells.into_iter().map(Into::into).collect(),
+    )
+}
+
+fn number(value: Option<f64>) -> String {
+    value.map(|value| format!("{value:.3}")).unwrap_or_default()
+}
+
+fn summary(statistics: &Statistics, now: Time) -> Table {
+    let mut table = Table::default()
+        .with_cols(vec![
+            Col::new(Styles::default().with(MinWidth(20))),
+            Col::new(Styles::default().with(MinWidth(13))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(8))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(10))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(10))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(10))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(10))),
+        ])
+        .with_row(header(vec!["Statistic", "Kind", "Count", "Mean", "Std dev", "Min", "Max"]));
+
+    for (name, counter) in statistics.counters() {
+        table.push_row(Row::new(Styles::default(), vec![
+            name.clone().into(),
+            "counter".into(),
+            counter.count().to_string().into(),
+        ]));
+    }
+    let tallies = statistics
+        .tallies()
+        .iter()
+        .map(|(name, tally)| (name, "tally", tally))
+        .chain(statistics.histograms().iter().map(|(name, histogram)| (name, "histogram", histogram.summary())));
+    for (name, kind, tally) in tallies {
+        table.push_row(Row::new(Styles::default(), vec![
+            name.clone().into(),
+            kind.into(),
+            tally.count().to_string().into(),
+            number(tally.mean()).into(),
+            number(tally.std_dev()).into(),
+            number(tally.min()).into(),
+            number(tally.max()).into(),
+        ]));
+    }
+    for (name, time_weighted) in statistics.time_weighted_accumulators() {
+        table.push_row(Row::new(Styles::default(), vec![
+            name.clone().into(),
+            "time-weighted".into(),
+            time_weighted.updates().to_string().into(),
+            number(time_weighted.mean(now)).into(),
+            "".into(),
+            number(time_weighted.min()).into(),
+            number(time_weighted.max()).into(),
+        ]));
+    }
+
+    table
+}
+
+fn bins(name: &str, histogram: &Histogram) -> Table {
+    let mut table = Table::default()
+        .with_cols(vec![
+            Col::new(Styles::default().with(MinWidth(20))),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(8))),
+        ])
+        .with_row(header(vec![name, "Count"]));
+
+    let len = histogram.bins().len();
+    let lower = histogram.bin_range(0).start;
+    let upper = histogram.bin_range(len - 1).end;
+    table.push_row(Row::new(Styles::default(), vec![
+        format!("< {lower}").into(),
+        histogram.underflow().to_string().into(),
+    ]));
+    for (index, count) in histogram.bins().iter().enumerate() {
+        let range = histogram.bin_range(index);
+        table.push_row(Row::new(Styles::default(), vec![
+            format!("[{}, {})", range.start, range.end).into(),
+            count.to_string().into(),
+        ]));
+    }
+    table.push_row(Row::new(Styles::default(), vec![
+        format!(">= {upper}").into(),
+        histogram.overflow().to_string().into(),
+    ]));
+
+    table
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,184 @@
+// $coverage:ignore-start
+
+use crate::commands::stats::{bins, summary, Parser, Stats};
+use crate::Context;
+use sequent::stats::{Histogram, Measured, Statistics};
+use sequent::{Decoder, Event, Queue, Scenario, Simulation, SimulationError, StaticNamed, Timeline, TransitionError};
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{AccessTerminalError, Mock, PrintOutput, Terminal};
+use stanza::renderer::console::{Console, Decor};
+use stanza::renderer::Renderer;
+
+#[derive(Debug, Clone, Default)]
+struct MeasuredState {
+    statistics: Statistics,
+}
+
+impl Measured for MeasuredState {
+    fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+}
+
+/// Records the time of arrival and the number of arrivals so far.
+#[derive(Debug)]
+struct Arrive;
+
+impl ToString for Arrive {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for Arrive {
+    fn name() -> &'static str {
+        "arrive"
+    }
+}
+
+impl Event for Arrive {
+    type State = MeasuredState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        let statistics = &mut state.statistics;
+        statistics.counter("arrivals").increment();
+        let arrivals = statistics.counter("arrivals").count() as f64;
+        statistics.tally("arrival time").record(queue.now());
+        statistics.time_weighted("arrived").record(queue.now(), arrivals);
+        Ok(())
+    }
+}
+
+struct MeasuredContext {
+    sim: Simulation<MeasuredState>,
+    decoder: Decoder<MeasuredState>,
+}
+
+impl Default for MeasuredContext {
+    fn default() -> Self {
+        let mut timeline = Timeline::default();
+        for time in [1.0, 2.0, 4.0] {
+            timeline.push_at(time, Box::new(Arrive) as Box<dyn Event<State = _>>);
+        }
+        Self {
+            sim: Simulation::from(Scenario {
+                initial: MeasuredState::default(),
+                timeline,
+                seed: 0,
+            }),
+            decoder: Decoder::new(vec![]),
+        }
+    }
+}
+
+impl Context for MeasuredContext {
+    type State = MeasuredState;
+
+    fn sim(&mut self) -> &mut Simulation<MeasuredState> {
+        &mut self.sim
+    }
+
+    fn print_state(&self, _: &mut impl Terminal) -> Result<(), AccessTerminalError> {
+        Ok(())
+    }
+
+    fn decoder(&self) -> &Decoder<MeasuredState> {
+        &self.decoder
+    }
+}
+
+fn command_parsers<'d>(
+) -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = MeasuredContext, Error = SimulationError<MeasuredState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply_without_statistics() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = MeasuredContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ApplyOutcome::Applied, Stats::default().apply(&mut looper).unwrap());
+    assert_eq!(
+        "No statistics.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = MeasuredContext::default();
+    context.sim().run().unwrap();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ApplyOutcome::Applied, Stats::default().apply(&mut looper).unwrap());
+    assert!(looper.terminal().invocations()[0]
+        .print()
+        .unwrap_output()
+        .contains("arrivals"));
+}
+
+#[test]
+fn summary_content() {
+    let mut context = MeasuredContext::default();
+    context.sim().run().unwrap();
+    let mut statistics = context.sim().current_state().statistics().clone();
+    statistics.histogram("wait", 0.0, 1.0, 2).record(0.25);
+    let renderer = Console(
+        Decor::default()
+            .suppress_escape_codes()
+            .suppress_inner_horizontal_border(),
+    );
+
+    let s = renderer.render(&summary(&statistics, 5.0)).to_string();
+    assert_eq!(
+        "\
+    ╔════════════════════╤═════════════╤════════╤══════════╤══════════╤══════════╤══════════╗\n\
+    ║Statistic           │Kind         │   Count│      Mean│   Std dev│       Min│       Max║\n\
+    ║arrivals            │counter      │       3│          │          │          │          ║\n\
+    ║arrival time        │tally        │       3│     2.333│     1.528│     1.000│     4.000║\n\
+    ║wait                │histogram    │       1│     0.250│          │     0.250│     0.250║\n\
+    ║arrived             │time-weighted│       3│     2.000│          │     1.000│     3.000║\n\
+    ╚════════════════════╧═════════════╧════════╧══════════╧══════════╧══════════╧══════════╝",
+        s
+    );
+}
+
+#[test]
+fn bins_content() {
+    let mut histogram = Histogram::new(0.0, 1.0, 2);
+    for value in [-1.0, 0.25, 0.5, 0.75, 1.0] {
+        histogram.record(value);
+    }
+    let renderer = Console(
+        Decor::default()
+            .suppress_escape_codes()
+            .suppress_inner_horizontal_border(),
+    );
+
+    let s = renderer.render(&bins("wait", &histogram)).to_string();
+    assert_eq!(
+        "\
+    ╔════════════════════╤════════╗\n\
+    ║wait                │   Count║\n\
+    ║< 0                 │       1║\n\
+    ║[0, 0.5)            │       1║\n\
+    ║[0.5, 1)            │       2║\n\
+    ║>= 1                │       1║\n\
+    ╚════════════════════╧════════╝",
+        s
+    );
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("stats").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<MeasuredContext, _, Mock>(&Parser::default());
+}
@@ -126,6 +126,7 @@ impl TestContext {
             timeline: (0..num_events)
                 .map(|id| Box::new(Append { id }) as Box<dyn Event<State = TestState>>)
                 .collect(),
+            seed: 0,
         };
         let sim = Simulation::from(scenario);
         let decoder = Decoder::new(event_parsers());
@@ -87,6 +87,8 @@ fn timeline<S>(simulation: &Simulation<S>) -> Table {
         .with_cols(vec![
             Col::new(Styles::default()),
             Col::new(Styles::default().with(HAlign::Right)),
+            Col::new(Styles::default().with(HAlign::Right).with(MinWidth(6))),
+            Col::new(Styles::default().with(MinWidth(15))),
             Col::new(Styles::default().with(MinWidth(15))),
             Col::new(Styles::default().with(MinWidth(40))),
         ])
@@ -97,12 +99,16 @@ fn timeline<S>(simulation: &Simulation<S>) -> Table {
             vec![
                 "".into(),
                 "".into(),
+                "Time".into(),
+                "Origin".into(),
                 "Event name".into(),
                 "Encoded event arguments".into(),
             ],
         ));
 
-    for (idx, event) in simulation.scenario().timeline.iter().enumerate() {
+    let timeline = &simulation.scenario().timeline;
+    let provenance = timeline.provenance();
+    for (idx, ((event, time), provenance)) in timeline.iter().zip(timeline.times()).zip(provenance).enumerate() {
         let on_cursor = idx == simulation.cursor();
         table.push_row(Row::new(
             Styles::default().with(Bold(on_cursor)),
@@ -114,6 +120,8 @@ fn timeline<S>(simulation: &Simulation<S>) -> Table {
                 }
                 .into(),
                 idx.into(),
+                time.to_string().into(),
+                provenance.to_string().into(),
                 event.name().into(),
                 event.to_string().into(),
             ],
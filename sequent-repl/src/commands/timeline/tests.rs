@@ -75,14 +75,15 @@ impl Event for SampleEvent {
 fn timeline_content() {
     let scenario = Scenario {
         initial: SampleState,
-        timeline: vec![
+        timeline: sequent::Timeline::from(vec![
             Box::new(SampleEvent {
                 args: vec!['a', 'b'],
-            }),
+            }) as Box<dyn Event<State = _>>,
             Box::new(SampleEvent {
                 args: vec!['c', 'd'],
             }),
-        ],
+        ]),
+        seed: 0,
     };
     let mut simulation = Simulation::from(scenario);
     let renderer = Console(
@@ -94,11 +95,11 @@ fn timeline_content() {
     let s = renderer.render(&timeline(&simulation)).to_string();
     assert_eq!(
         "\
-    ╔═╤═╤═══════════════╤════════════════════════════════════════╗\n\
-    ║ │ │Event name     │Encoded event arguments                 ║\n\
-    ║▶│0│test-event     │a b                                     ║\n\
-    ║ │1│test-event     │c d                                     ║\n\
-    ╚═╧═╧═══════════════╧════════════════════════════════════════╝",
+    ╔═╤═╤══════╤═══════════════╤═══════════════╤════════════════════════════════════════╗\n\
+    ║ │ │  Time│Origin         │Event name     │Encoded event arguments                 ║\n\
+    ║▶│0│     0│user           │test-event     │a b                                     ║\n\
+    ║ │1│     0│user           │test-event     │c d                                     ║\n\
+    ╚═╧═╧══════╧═══════════════╧═══════════════╧════════════════════════════════════════╝",
         s
     );
 
@@ -106,11 +107,11 @@ fn timeline_content() {
     let s = renderer.render(&timeline(&simulation)).to_string();
     assert_eq!(
         "\
-    ╔═╤═╤═══════════════╤════════════════════════════════════════╗\n\
-    ║ │ │Event name     │Encoded event arguments                 ║\n\
-    ║ │0│test-event     │a b                                     ║\n\
-    ║▶│1│test-event     │c d                                     ║\n\
-    ╚═╧═╧═══════════════╧════════════════════════════════════════╝",
+    ╔═╤═╤══════╤═══════════════╤═══════════════╤════════════════════════════════════════╗\n\
+    ║ │ │  Time│Origin         │Event name     │Encoded event arguments                 ║\n\
+    ║ │0│     0│user           │test-event     │a b                                     ║\n\
+    ║▶│1│     0│user           │test-event     │c d                                     ║\n\
+    ╚═╧═╧══════╧═══════════════╧═══════════════╧════════════════════════════════════════╝",
         s
     );
 
@@ -118,12 +119,12 @@ fn timeline_content() {
     let s = renderer.render(&timeline(&simulation)).to_string();
     assert_eq!(
         "\
-    ╔═╤═╤═══════════════╤════════════════════════════════════════╗\n\
-    ║ │ │Event name     │Encoded event arguments                 ║\n\
-    ║ │0│test-event     │a b                                     ║\n\
-    ║ │1│test-event     │c d                                     ║\n\
-    ║▶│2│               │                                        ║\n\
-    ╚═╧═╧═══════════════╧════════════════════════════════════════╝",
+    ╔═╤═╤══════╤═══════════════╤═══════════════╤════════════════════════════════════════╗\n\
+    ║ │ │  Time│Origin         │Event name     │Encoded event arguments                 ║\n\
+    ║ │0│     0│user           │test-event     │a b                                     ║\n\
+    ║ │1│     0│user           │test-event     │c d                                     ║\n\
+    ║▶│2│      │               │               │                                        ║\n\
+    ╚═╧═╧══════╧═══════════════╧═══════════════╧════════════════════════════════════════╝",
         s
     );
 }
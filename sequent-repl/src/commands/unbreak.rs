@@ -0,0 +1,97 @@
+//! Removal of breakpoints.
+
+use crate::Context;
+use sequent::SimulationError;
+use revolver::command::{
+    ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser,
+    ParseCommandError,
+};
+use revolver::looper::Looper;
+use revolver::terminal::Terminal;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Command to remove a previously set breakpoint, identified by its ID.
+pub struct Unbreak<S, C> {
+    id: usize,
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Unbreak<S, C> {
+    fn new(id: usize) -> Self {
+        Self {
+            id,
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S, C: Context<State = S>, T: Terminal> Command<T> for Unbreak<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn apply(&mut self, looper: &mut Looper<C, SimulationError<S>, T>) -> Result<ApplyOutcome, ApplyCommandError<SimulationError<S>>> {
+        match looper.context().sim().remove_breakpoint(self.id) {
+            Some(breakpoint) => {
+                looper
+                    .terminal()
+                    .print_line(&format!("Breakpoint {} removed ({breakpoint}).", self.id))?;
+                Ok(ApplyOutcome::Applied)
+            }
+            None => {
+                looper
+                    .terminal()
+                    .print_line(&format!("No breakpoint with ID {}.", self.id))?;
+                Ok(ApplyOutcome::Skipped)
+            }
+        }
+    }
+}
+
+/// Parser for [`Unbreak`].
+pub struct Parser<S, C> {
+    __phantom_data: PhantomData<(S, C)>
+}
+
+impl<S, C> Default for Parser<S, C> {
+    fn default() -> Self {
+        Self {
+            __phantom_data: PhantomData::default()
+        }
+    }
+}
+
+impl<S: 'static, C: Context<State = S> + 'static, T: Terminal> NamedCommandParser<T> for Parser<S, C> {
+    type Context = C;
+    type Error = SimulationError<S>;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C, Error = SimulationError<S>>>, ParseCommandError> {
+        if s.is_empty() {
+            return Err(ParseCommandError("empty arguments to 'unbreak'".into()));
+        }
+        let id = s.parse().map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Unbreak::new(id)))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "unbreak".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Removes a breakpoint.".into(),
+            usage: "<id>".into(),
+            examples: vec![Example {
+                scenario: "remove the breakpoint with ID 0".into(),
+                command: "0".into(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,59 @@
+// $coverage:ignore-start
+
+use crate::commands::test_fixtures::{TestContext, TestState};
+use crate::commands::unbreak::{Parser, Unbreak};
+use crate::Context;
+use revolver::command::{assert_pedantic, ApplyOutcome, Command, Commander, NamedCommandParser};
+use revolver::looper::Looper;
+use revolver::terminal::{Mock, PrintOutput};
+use sequent::{Breakpoint, Condition, SimulationError};
+
+fn command_parsers<'d>() -> Vec<Box<dyn NamedCommandParser<Mock<'d>, Context = TestContext, Error = SimulationError<TestState>>>> {
+    vec![Box::new(Parser::default())]
+}
+
+#[test]
+fn apply() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.context().sim().add_breakpoint(Breakpoint::before(Condition::Event("append".into())));
+    assert_eq!(ApplyOutcome::Applied, Unbreak::new(0).apply(&mut looper).unwrap());
+    assert_eq!(
+        "Breakpoint 0 removed (before event 'append').\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+    assert_eq!(0, looper.context().sim().breakpoints().count());
+}
+
+#[test]
+fn apply_nonexistent() {
+    let mut term = Mock::default();
+    let commander = Commander::new(command_parsers());
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ApplyOutcome::Skipped, Unbreak::new(3).apply(&mut looper).unwrap());
+    assert_eq!(
+        "No breakpoint with ID 3.\n",
+        looper.terminal().invocations()[0].print().unwrap_output()
+    );
+}
+
+#[test]
+fn parse() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("unbreak 0").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "empty arguments to 'unbreak'")]
+fn parse_empty_args_fails() {
+    let commander = Commander::new(command_parsers());
+    commander.parse("unbreak").unwrap();
+}
+
+#[test]
+fn parser_lints() {
+    assert_pedantic::<TestContext, _, Mock>(&Parser::default());
+}
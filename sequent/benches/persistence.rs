@@ -0,0 +1,154 @@
+//! Compares the time taken to write and read a large scenario in each of the persistence formats.
+//!
+//! Run with `cargo bench -p sequent --bench persistence`. The sizes of the files are printed before
+//! the measurements are taken.
+//!
+//! For reference, one run measured the following times, which vary from one machine to the next:
+//!
+//! | format         | size    | write  | read   |
+//! |----------------|---------|--------|--------|
+//! | yaml           | 6.9 MB  | 451 ms | 697 ms |
+//! | json           | 7.2 MB  | 77 ms  | 87 ms  |
+//! | binary         | 3.0 MB  | 43 ms  | 74 ms  |
+//! | binary-deflate | 0.5 MB  | 231 ms | 90 ms  |
+//!
+//! The binary format does not reliably beat YAML by an order of magnitude. In this run it wrote about
+//! 10 times faster and read only about 9 times faster; other runs have measured writes at about 8 times
+//! faster.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sequent::persistence::binary::Compression;
+use sequent::persistence::{binary, json, yaml};
+use sequent::{Decoder, Event, Queue, Scenario, Timeline, TransitionError};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const EVENTS: usize = 100_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct State {
+    arrivals: usize,
+    departures: usize,
+}
+
+#[derive(Debug, sequent_derive::Event)]
+struct Arrive {
+    customer: usize,
+}
+
+impl Event for Arrive {
+    type State = State;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.arrivals += 1;
+        Ok(())
+    }
+}
+
+#[derive(Debug, sequent_derive::Event)]
+struct Depart {
+    customer: usize,
+    teller: u8,
+}
+
+impl Event for Depart {
+    type State = State;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.departures += 1;
+        Ok(())
+    }
+}
+
+fn scenario() -> Scenario<State> {
+    let mut timeline = Timeline::default();
+    for customer in 0..EVENTS / 2 {
+        let time = customer as f64;
+        timeline.push_at(time, Box::new(Arrive { customer }));
+        timeline.push_at(time + 0.5, Box::new(Depart { customer, teller: (customer % 4) as u8 }));
+    }
+    Scenario {
+        initial: State::default(),
+        timeline,
+        seed: 0,
+    }
+}
+
+fn decoder() -> Decoder<State> {
+    Decoder::new(vec![Arrive::parser(), Depart::parser()])
+}
+
+/// A format under test, along with the means of writing and reading it.
+struct Format {
+    name: &'static str,
+    ext: &'static str,
+    write: fn(&Scenario<State>, &Path),
+    read: fn(&Decoder<State>, &Path) -> Scenario<State>,
+}
+
+const FORMATS: [Format; 4] = [
+    Format {
+        name: "yaml",
+        ext: "yaml",
+        write: |scenario, path| yaml::write_to_file(scenario, path).unwrap(),
+        read: |decoder, path| yaml::read_from_file(decoder, path).unwrap(),
+    },
+    Format {
+        name: "json",
+        ext: "json",
+        write: |scenario, path| json::write_to_file_with_style(scenario, path, json::Style::Compact).unwrap(),
+        read: |decoder, path| json::read_from_file(decoder, path).unwrap(),
+    },
+    Format {
+        name: "binary",
+        ext: "seqb",
+        write: |scenario, path| binary::write_to_file(scenario, path).unwrap(),
+        read: |decoder, path| binary::read_from_file(decoder, path).unwrap(),
+    },
+    Format {
+        name: "binary-deflate",
+        ext: "seqb",
+        write: |scenario, path| binary::write_to_file_with_compression(scenario, path, Compression::Deflate).unwrap(),
+        read: |decoder, path| binary::read_from_file(decoder, path).unwrap(),
+    },
+];
+
+fn path(format: &Format) -> PathBuf {
+    std::env::temp_dir().join(format!("sequent-bench-{}-{}.{}", std::process::id(), format.name, format.ext))
+}
+
+fn persistence(c: &mut Criterion) {
+    let scenario = scenario();
+    let decoder = decoder();
+    for format in &FORMATS {
+        (format.write)(&scenario, &path(format));
+        let size = fs::metadata(path(format)).unwrap().len();
+        println!("{}: {EVENTS} events in {size} bytes", format.name);
+    }
+
+    let mut group = c.benchmark_group("write");
+    group.sample_size(10).throughput(Throughput::Elements(EVENTS as u64));
+    for format in &FORMATS {
+        group.bench_with_input(BenchmarkId::from_parameter(format.name), format, |b, format| {
+            b.iter(|| (format.write)(&scenario, &path(format)));
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("read");
+    group.sample_size(10).throughput(Throughput::Elements(EVENTS as u64));
+    for format in &FORMATS {
+        group.bench_with_input(BenchmarkId::from_parameter(format.name), format, |b, format| {
+            b.iter(|| (format.read)(&decoder, &path(format)));
+        });
+    }
+    group.finish();
+
+    for format in &FORMATS {
+        fs::remove_file(path(format)).unwrap();
+    }
+}
+
+criterion_group!(benches, persistence);
+criterion_main!(benches);
@@ -0,0 +1,129 @@
+//! Customers arrive at a bank at random, at an average rate of 1.5 per minute, and are attended to by
+//! one of two tellers. Each teller takes an average of one minute to serve a customer, the service time
+//! being exponentially distributed. Customers who find both tellers busy wait in a single line.
+//!
+//! How long does the average customer wait before being served?
+//!
+//! This is an M/M/c queue, with c = 2, which has an analytical solution in the form of the Erlang C
+//! formula. This example uses a discrete-event simulation to estimate the waiting time, and compares
+//! the result with the analytical one. The tellers are modelled by a [`Server`] block, which keeps the
+//! waiting line and schedules the end of each service. There are two event types:
+//! [`Arrive`](arrive::Arrive), which admits a customer to the bank and schedules the arrival of the
+//! next customer, and [`Depart`](depart::Depart), which frees a teller for the next customer in line.
+
+use sequent::blocks::{Distribution, Server};
+use sequent::{Scenario, Simulation, Timeline};
+
+const CUSTOMERS: usize = 100_000;
+const ARRIVAL_RATE: f64 = 1.5;
+const SERVICE_RATE: f64 = 1.0;
+const TELLERS: usize = 2;
+
+fn main() {
+    let mut timeline = Timeline::default();
+    timeline.push(Box::new(arrive::Arrive { customer: 0 }));
+    let scenario = Scenario {
+        initial: State {
+            tellers: Server::new(TELLERS, Distribution::Exponential { rate: SERVICE_RATE }),
+        },
+        timeline,
+        seed: 0,
+    };
+
+    let mut simulation = Simulation::from(scenario);
+    simulation.run().unwrap();
+
+    let tellers = simulation.current_state().tellers.resource();
+    println!("served {} customers in {:.0} minutes", CUSTOMERS, simulation.now());
+    println!("teller utilisation: {:.3}", tellers.utilisation(simulation.now()));
+    println!("simulated mean wait: {:.3} minutes", tellers.wait().mean().unwrap());
+    println!("analytical mean wait: {:.3} minutes", erlang_c_wait());
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    tellers: Server<usize>,
+}
+
+/// The mean waiting time, according to the Erlang C formula.
+fn erlang_c_wait() -> f64 {
+    let offered = ARRIVAL_RATE / SERVICE_RATE;
+    let utilisation = offered / TELLERS as f64;
+    let mut term = 1.0;
+    let mut sum = 0.0;
+    for k in 0..TELLERS {
+        sum += term;
+        term *= offered / (k + 1) as f64;
+    }
+    let queued = term / (1.0 - utilisation);
+    queued / (sum + queued) / (TELLERS as f64 * SERVICE_RATE - ARRIVAL_RATE)
+}
+
+mod arrive {
+    use std::borrow::Cow;
+    use sequent::{Event, Named, Queue, TransitionError};
+    use crate::{ARRIVAL_RATE, CUSTOMERS, State};
+    use crate::depart::Depart;
+
+    #[derive(Debug)]
+    pub struct Arrive {
+        pub customer: usize,
+    }
+
+    impl ToString for Arrive {
+        fn to_string(&self) -> String {
+            self.customer.to_string()
+        }
+    }
+
+    impl Named for Arrive {
+        fn name(&self) -> Cow<'static, str> {
+            "arrive".into()
+        }
+    }
+
+    impl Event for Arrive {
+        type State = State;
+
+        fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+            if self.customer + 1 < CUSTOMERS {
+                let delay = queue.rng().exponential(ARRIVAL_RATE);
+                queue.schedule_in(delay, Box::new(Arrive { customer: self.customer + 1 }));
+            }
+            state.tellers.arrive(self.customer, queue, |customer| Box::new(Depart { customer }));
+            Ok(())
+        }
+    }
+}
+
+mod depart {
+    use std::borrow::Cow;
+    use sequent::{Event, Named, Queue, TransitionError};
+    use crate::State;
+
+    #[derive(Debug)]
+    pub struct Depart {
+        pub customer: usize,
+    }
+
+    impl ToString for Depart {
+        fn to_string(&self) -> String {
+            self.customer.to_string()
+        }
+    }
+
+    impl Named for Depart {
+        fn name(&self) -> Cow<'static, str> {
+            "depart".into()
+        }
+    }
+
+    impl Event for Depart {
+        type State = State;
+
+        fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+            state.tellers.depart(queue, |customer| Box::new(Depart { customer }));
+            Ok(())
+        }
+    }
+}
@@ -6,28 +6,31 @@
 //! This simple problem can easily be solved analytically, provided one doesn't forget that once the snail
 //! reaches the top, it will not slip again. This example uses a discrete-event simulation to solve the problem.
 //! There are two event types: [`Climb`](climb::Climb) and [`Slip`](slip::Slip). `Climb` advances the snail's
-//! progress by 2 units. If (and only if) it fails to complete the journey, it enqueues a subsequent `Slip`
-//! event. Conversely, `Slip` pushes the snail back by one unit and enqueues a subsequent `Climb` event.
-//! The simulation terminates when the event timeline is exhausted.
+//! progress by 2 units. If (and only if) it fails to complete the journey, it schedules a `Slip`
+//! event for the night. Conversely, `Slip` pushes the snail back by one unit and schedules a `Climb` event
+//! for the following morning. The simulation terminates when the event timeline is exhausted.
 //!
 //! The simulation [`State`] keeps track of the progress and the number of days the snail spent climbing. It is
-//! bootstrapped with a single `Climb` event. We print the state after the conclusion of the simulation to
-//! see how many days the poor bugger spent climbing.
+//! bootstrapped with a single `Climb` event. We print the state and the simulation time after the conclusion
+//! of the simulation to see how many days the poor bugger spent climbing.
+//!
+//! The naming and string encoding of the events is derived with `sequent_derive::Event`.
 
-use sequent::{Scenario, Simulation};
+use sequent::{Scenario, Simulation, Time, Timeline};
 
 fn main() {
+    let mut timeline = Timeline::default();
+    timeline.push(Box::new(climb::Climb));
     let scenario = Scenario {
         initial: State::default(),
-        timeline: vec![
-            Box::new(climb::Climb)
-        ]
+        timeline,
+        seed: 0,
     };
 
     let mut simulation = Simulation::from(scenario);
     simulation.run().unwrap();
 
-    println!("{:?}", simulation.current_state());
+    println!("{:?} at time {}", simulation.current_state(), simulation.now());
 }
 
 #[derive(Default, Clone, Debug)]
@@ -39,29 +42,16 @@ pub struct State {
 const WALL_HEIGHT: u16 = 10;
 const CLIMB_STEP: u16 = 2;
 const SLIP_STEP: u16 = 1;
+const HALF_DAY: Time = 0.5;
 
 mod climb {
-    use std::borrow::Cow;
-    use std::fmt::Debug;
-    use sequent::{Event, Named, Queue, TransitionError};
-    use crate::{CLIMB_STEP, State, WALL_HEIGHT};
+    use sequent::{Event, Queue, TransitionError};
+    use crate::{CLIMB_STEP, HALF_DAY, State, WALL_HEIGHT};
     use crate::slip::Slip;
 
-    #[derive(Debug)]
+    #[derive(Debug, sequent_derive::Event)]
     pub struct Climb;
 
-    impl ToString for Climb {
-        fn to_string(&self) -> String {
-            "".into()
-        }
-    }
-
-    impl Named for Climb {
-        fn name(&self) -> Cow<'static, str> {
-            "climb".into()
-        }
-    }
-
     impl Event for Climb {
         type State = State;
 
@@ -69,7 +59,7 @@ mod climb {
             state.progress += CLIMB_STEP;
             state.days += 1;
             if state.progress < WALL_HEIGHT {
-                queue.push_later(Box::new(Slip));
+                queue.schedule_in(HALF_DAY, Box::new(Slip));
             }
             Ok(())
         }
@@ -77,32 +67,19 @@ mod climb {
 }
 
 mod slip {
-    use std::borrow::Cow;
-    use sequent::{Event, Named, Queue, TransitionError};
-    use crate::{SLIP_STEP, State};
+    use sequent::{Event, Queue, TransitionError};
+    use crate::{HALF_DAY, SLIP_STEP, State};
     use crate::climb::Climb;
 
-    #[derive(Debug)]
+    #[derive(Debug, sequent_derive::Event)]
     pub struct Slip;
 
-    impl ToString for Slip {
-        fn to_string(&self) -> String {
-            "".into()
-        }
-    }
-
-    impl Named for Slip {
-        fn name(&self) -> Cow<'static, str> {
-            "slip".into()
-        }
-    }
-
     impl Event for Slip {
         type State = State;
 
         fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
             state.progress -= SLIP_STEP;
-            queue.push_later(Box::new(Climb));
+            queue.schedule_in(HALF_DAY, Box::new(Climb));
             Ok(())
         }
     }
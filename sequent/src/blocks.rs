@@ -0,0 +1,410 @@
+//! Reusable building blocks for queueing models: waiting lines, capacity-limited resources, servers
+//! and stores.
+//!
+//! Blocks are passive components that live in the simulation state. They are driven by the model's
+//! own events, which call into the blocks in the course of their transitions. Where a block resumes
+//! an entity that was waiting on it, it schedules an event for that entity via the [`Queue`], using
+//! a closure supplied by the caller to create the event. Being part of the state, blocks are
+//! serializable, and their statistics rewind along with the rest of the state (see [`crate::stats`]).
+//!
+//! Time-weighted statistics assume that the simulation starts at time zero.
+
+use crate::stats::{Tally, TimeWeighted};
+use crate::{Event, Queue, Rng, Time};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A probability distribution of durations, used to sample service times.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Distribution {
+    /// Always the given duration.
+    Fixed(Time),
+
+    /// Uniformly distributed over the half-open range `low..high`.
+    Uniform { low: Time, high: Time },
+
+    /// Exponentially distributed with the given rate (i.e., the reciprocal of the mean).
+    Exponential { rate: f64 },
+}
+
+impl Distribution {
+    /// Draws a duration from the distribution.
+    pub fn sample(&self, rng: &mut Rng) -> Time {
+        match self {
+            Distribution::Fixed(duration) => *duration,
+            Distribution::Uniform { low, high } => rng.uniform(*low, *high),
+            Distribution::Exponential { rate } => rng.exponential(*rate),
+        }
+    }
+
+    /// The mean duration.
+    pub fn mean(&self) -> Time {
+        match self {
+            Distribution::Fixed(duration) => *duration,
+            Distribution::Uniform { low, high } => (low + high) / 2.0,
+            Distribution::Exponential { rate } => 1.0 / rate,
+        }
+    }
+}
+
+/// A first-in, first-out waiting line of entities. Each entity is timestamped upon joining the line,
+/// so that the time it spent waiting is known when it leaves. Tracks the time-weighted length of the
+/// line and the waiting times of the entities that left it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fifo<T> {
+    entries: VecDeque<(Time, T)>,
+    length: TimeWeighted,
+    wait: Tally,
+}
+
+impl<T> Default for Fifo<T> {
+    fn default() -> Self {
+        let mut length = TimeWeighted::default();
+        length.record(0.0, 0.0);
+        Self {
+            entries: VecDeque::default(),
+            length,
+            wait: Tally::default(),
+        }
+    }
+}
+
+impl<T> Fifo<T> {
+    /// Appends an entity to the back of the line at the given time.
+    pub fn push(&mut self, now: Time, entity: T) {
+        self.entries.push_back((now, entity));
+        self.length.record(now, self.entries.len() as f64);
+    }
+
+    /// Removes the entity at the front of the line at the given time, returning it along with the
+    /// time it spent waiting, or [`None`] if the line is empty.
+    pub fn pop(&mut self, now: Time) -> Option<(T, Time)> {
+        let (joined, entity) = self.entries.pop_front()?;
+        self.length.record(now, self.entries.len() as f64);
+        let waited = now - joined;
+        self.wait.record(waited);
+        Some((entity, waited))
+    }
+
+    /// A reference to the entity at the front of the line, if any.
+    pub fn front(&self) -> Option<&T> {
+        self.entries.front().map(|(_, entity)| entity)
+    }
+
+    /// An iterator over the waiting entities, from front to back.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().map(|(_, entity)| entity)
+    }
+
+    /// The number of waiting entities.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no waiting entities.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The time-weighted length of the line.
+    pub fn length(&self) -> &TimeWeighted {
+        &self.length
+    }
+
+    /// The waiting times of the entities that left the line.
+    pub fn wait(&self) -> &Tally {
+        &self.wait
+    }
+}
+
+/// A resource comprising a fixed number of identical units, which are seized and released by entities.
+/// An entity that cannot seize a unit joins a [`Fifo`] line, and is granted a unit when one is released.
+/// Tracks the time-weighted number of units in use and the time that entities waited for a unit,
+/// including those that did not wait at all.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resource<T> {
+    capacity: usize,
+    in_use: usize,
+    line: Fifo<T>,
+    busy: TimeWeighted,
+    wait: Tally,
+}
+
+impl<T> Resource<T> {
+    /// Creates a resource with the given number of units.
+    ///
+    /// # Panics
+    /// If the capacity is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        let mut busy = TimeWeighted::default();
+        busy.record(0.0, 0.0);
+        Self {
+            capacity,
+            in_use: 0,
+            line: Fifo::default(),
+            busy,
+            wait: Tally::default(),
+        }
+    }
+
+    /// Attempts to seize a unit on behalf of an entity at the given time. Returns the entity if a unit
+    /// was granted immediately; otherwise, the entity joins the line and [`None`] is returned.
+    pub fn seize(&mut self, now: Time, entity: T) -> Option<T> {
+        if self.in_use < self.capacity {
+            self.in_use += 1;
+            self.busy.record(now, self.in_use as f64);
+            self.wait.record(0.0);
+            Some(entity)
+        } else {
+            self.line.push(now, entity);
+            None
+        }
+    }
+
+    /// Releases a unit at the given time. If entities are waiting, the unit is granted to the entity
+    /// at the front of the line, which is returned.
+    ///
+    /// # Panics
+    /// If no units are in use.
+    pub fn release(&mut self, now: Time) -> Option<T> {
+        assert!(self.in_use > 0, "no units are in use");
+        match self.line.pop(now) {
+            Some((entity, waited)) => {
+                self.wait.record(waited);
+                Some(entity)
+            }
+            None => {
+                self.in_use -= 1;
+                self.busy.record(now, self.in_use as f64);
+                None
+            }
+        }
+    }
+
+    /// The number of units.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of units in use.
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    /// The number of units available for seizing.
+    pub fn available(&self) -> usize {
+        self.capacity - self.in_use
+    }
+
+    /// The line of entities waiting for a unit.
+    pub fn line(&self) -> &Fifo<T> {
+        &self.line
+    }
+
+    /// The time-weighted number of units in use.
+    pub fn busy(&self) -> &TimeWeighted {
+        &self.busy
+    }
+
+    /// The time that entities waited to seize a unit.
+    pub fn wait(&self) -> &Tally {
+        &self.wait
+    }
+
+    /// The time-average fraction of units in use, from time zero until `now`.
+    ///
+    /// # Panics
+    /// If `now` precedes the last seizure or release.
+    pub fn utilisation(&self, now: Time) -> f64 {
+        self.busy.mean(now).unwrap_or_default() / self.capacity as f64
+    }
+}
+
+/// A multi-server station, comprising a [`Resource`] whose units are servers, and a service time
+/// distribution. An arriving entity is served immediately if a server is idle, or waits in line
+/// otherwise. When an entity enters service, the server schedules the completion of its service
+/// after a duration drawn from the distribution. The model's completion event must in turn call
+/// [`Server::depart()`], freeing the server for the next entity in line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Server<T> {
+    resource: Resource<T>,
+    service: Distribution,
+    served: u64,
+}
+
+impl<T> Server<T> {
+    /// Creates a station with the given number of servers and service time distribution.
+    ///
+    /// # Panics
+    /// If the number of servers is 0.
+    pub fn new(servers: usize, service: Distribution) -> Self {
+        Self {
+            resource: Resource::new(servers),
+            service,
+            served: 0,
+        }
+    }
+
+    /// Admits an entity at the current time, serving it immediately if a server is idle. The `done`
+    /// closure creates the event that completes the service of the given entity.
+    pub fn arrive<S>(&mut self, entity: T, queue: &mut Queue<S>, done: impl FnOnce(T) -> Box<dyn Event<State = S>>) {
+        if let Some(entity) = self.resource.seize(queue.now(), entity) {
+            self.serve(entity, queue, done);
+        }
+    }
+
+    /// Completes the service of an entity at the current time, freeing its server. If entities are
+    /// waiting, the server begins serving the entity at the front of the line, using the `done` closure
+    /// to create the event that completes its service.
+    pub fn depart<S>(&mut self, queue: &mut Queue<S>, done: impl FnOnce(T) -> Box<dyn Event<State = S>>) {
+        self.served += 1;
+        if let Some(entity) = self.resource.release(queue.now()) {
+            self.serve(entity, queue, done);
+        }
+    }
+
+    fn serve<S>(&mut self, entity: T, queue: &mut Queue<S>, done: impl FnOnce(T) -> Box<dyn Event<State = S>>) {
+        let duration = self.service.sample(queue.rng());
+        queue.schedule_in(duration, done(entity));
+    }
+
+    /// The underlying resource, whose units are servers.
+    pub fn resource(&self) -> &Resource<T> {
+        &self.resource
+    }
+
+    /// The service time distribution.
+    pub fn service(&self) -> &Distribution {
+        &self.service
+    }
+
+    /// The number of entities whose service has been completed.
+    pub fn served(&self) -> u64 {
+        self.served
+    }
+}
+
+/// A buffer of items, which producers put into and consumers get from, in first-in, first-out order.
+/// A consumer that finds the store empty waits until an item is put; a producer that finds the store
+/// full waits until an item is taken. Tracks the time-weighted number of items held.
+///
+/// `T` is the type of items and `E` is the type of the producing and consuming entities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Store<T, E> {
+    capacity: usize,
+    items: VecDeque<T>,
+    level: TimeWeighted,
+    getters: Fifo<E>,
+    putters: Fifo<(E, T)>,
+}
+
+/// Creates an unbounded store.
+impl<T, E> Default for Store<T, E> {
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+impl<T, E> Store<T, E> {
+    /// Creates a store that holds at most the given number of items.
+    ///
+    /// # Panics
+    /// If the capacity is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        let mut level = TimeWeighted::default();
+        level.record(0.0, 0.0);
+        Self {
+            capacity,
+            items: VecDeque::default(),
+            level,
+            getters: Fifo::default(),
+            putters: Fifo::default(),
+        }
+    }
+
+    /// Puts an item into the store at the current time on behalf of a producer. If consumers are
+    /// waiting, the item is handed to the consumer at the front of the line, using the `got` closure to
+    /// create the event that resumes the consumer with the item. Returns `true` if the put completed,
+    /// or `false` if the store is full, in which case the producer waits with its item until another
+    /// item is taken.
+    pub fn put<S>(
+        &mut self,
+        producer: E,
+        item: T,
+        queue: &mut Queue<S>,
+        got: impl FnOnce(E, T) -> Box<dyn Event<State = S>>,
+    ) -> bool {
+        let now = queue.now();
+        if let Some((consumer, _)) = self.getters.pop(now) {
+            queue.schedule_in(0.0, got(consumer, item));
+            true
+        } else if self.items.len() < self.capacity {
+            self.items.push_back(item);
+            self.level.record(now, self.items.len() as f64);
+            true
+        } else {
+            self.putters.push(now, (producer, item));
+            false
+        }
+    }
+
+    /// Takes an item from the store at the current time on behalf of a consumer. If producers are
+    /// waiting, the item of the producer at the front of the line is put into the store in place of the
+    /// taken item, using the `put` closure to create the event that resumes the producer. Returns the
+    /// taken item, or [`None`] if the store is empty, in which case the consumer waits until an item
+    /// is put.
+    pub fn get<S>(&mut self, consumer: E, queue: &mut Queue<S>, put: impl FnOnce(E) -> Box<dyn Event<State = S>>) -> Option<T> {
+        let now = queue.now();
+        let Some(item) = self.items.pop_front() else {
+            self.getters.push(now, consumer);
+            return None;
+        };
+        if let Some(((producer, pending), _)) = self.putters.pop(now) {
+            self.items.push_back(pending);
+            queue.schedule_in(0.0, put(producer));
+        }
+        self.level.record(now, self.items.len() as f64);
+        Some(item)
+    }
+
+    /// The maximum number of items held.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// An iterator over the held items, in the order in which they will be taken.
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    /// The number of held items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no items are held.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The time-weighted number of items held.
+    pub fn level(&self) -> &TimeWeighted {
+        &self.level
+    }
+
+    /// The line of consumers waiting for an item.
+    pub fn getters(&self) -> &Fifo<E> {
+        &self.getters
+    }
+
+    /// The line of producers waiting for space, along with their items.
+    pub fn putters(&self) -> &Fifo<(E, T)> {
+        &self.putters
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,399 @@
+// $coverage:ignore-start
+
+use crate::blocks::{Distribution, Fifo, Resource, Server, Store};
+use crate::{Event, Queue, Rng, Scenario, Simulation, StaticNamed, Timeline, TransitionError};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn distribution_sample() {
+    let mut rng = Rng::new(0);
+    assert_eq!(2.5, Distribution::Fixed(2.5).sample(&mut rng));
+    assert_eq!(2.5, Distribution::Fixed(2.5).mean());
+
+    let uniform = Distribution::Uniform { low: 1.0, high: 2.0 };
+    assert_eq!(1.5, uniform.mean());
+    for _ in 0..100 {
+        let sample = uniform.sample(&mut rng);
+        assert!((1.0..2.0).contains(&sample));
+    }
+
+    let exponential = Distribution::Exponential { rate: 4.0 };
+    assert_eq!(0.25, exponential.mean());
+    let mean = (0..10_000).map(|_| exponential.sample(&mut rng)).sum::<f64>() / 10_000.0;
+    assert!((mean - 0.25).abs() < 0.01, "mean {mean}");
+}
+
+#[test]
+fn fifo() {
+    let mut fifo = Fifo::default();
+    assert!(fifo.is_empty());
+    assert_eq!(None, fifo.pop(0.0));
+    fifo.push(1.0, 'a');
+    fifo.push(2.0, 'b');
+    assert_eq!(2, fifo.len());
+    assert_eq!(Some(&'a'), fifo.front());
+    assert_eq!(vec!['a', 'b'], fifo.iter().copied().collect::<Vec<_>>());
+
+    assert_eq!(Some(('a', 3.0)), fifo.pop(4.0));
+    assert_eq!(Some(('b', 3.0)), fifo.pop(5.0));
+    assert!(fifo.is_empty());
+    assert_eq!(2, fifo.wait().count());
+    assert_eq!(Some(3.0), fifo.wait().mean());
+    // empty for 1 time unit, 1 entity for 1 unit, 2 for 2 units, then 1 for 1 unit
+    assert_eq!(Some(6.0 / 5.0), fifo.length().mean(5.0));
+}
+
+#[test]
+fn resource_seize_release() {
+    let mut resource = Resource::new(2);
+    assert_eq!(Some('a'), resource.seize(0.0, 'a'));
+    assert_eq!(Some('b'), resource.seize(1.0, 'b'));
+    assert_eq!(None, resource.seize(1.0, 'c'));
+    assert_eq!(2, resource.in_use());
+    assert_eq!(0, resource.available());
+    assert_eq!(1, resource.line().len());
+
+    assert_eq!(Some('c'), resource.release(3.0));
+    assert_eq!(2, resource.in_use());
+    assert_eq!(None, resource.release(4.0));
+    assert_eq!(None, resource.release(4.0));
+    assert_eq!(0, resource.in_use());
+    assert_eq!(2, resource.capacity());
+
+    assert_eq!(3, resource.wait().count());
+    assert_eq!(Some(2.0 / 3.0), resource.wait().mean());
+    // 1 unit for 1 time unit, then 2 units for 3 units, then idle for 4 units
+    assert_eq!(7.0 / 8.0 / 2.0, resource.utilisation(8.0));
+    assert_eq!(Some(2.0), resource.busy().max());
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn resource_without_capacity() {
+    Resource::<()>::new(0);
+}
+
+#[test]
+#[should_panic(expected = "no units are in use")]
+fn resource_release_unseized() {
+    Resource::<()>::new(1).release(0.0);
+}
+
+/// A bank, where customers arrive as a Poisson process and are attended by one of several tellers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Bank {
+    customers: usize,
+    arrival_rate: f64,
+    tellers: Server<usize>,
+}
+
+#[derive(Debug)]
+struct Arrive {
+    customer: usize,
+}
+
+impl ToString for Arrive {
+    fn to_string(&self) -> String {
+        self.customer.to_string()
+    }
+}
+
+impl StaticNamed for Arrive {
+    fn name() -> &'static str {
+        "arrive"
+    }
+}
+
+impl Event for Arrive {
+    type State = Bank;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        if self.customer + 1 < state.customers {
+            let delay = queue.rng().exponential(state.arrival_rate);
+            queue.schedule_in(delay, Box::new(Arrive { customer: self.customer + 1 }));
+        }
+        state.tellers.arrive(self.customer, queue, |customer| Box::new(Depart { customer }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct Depart {
+    customer: usize,
+}
+
+impl ToString for Depart {
+    fn to_string(&self) -> String {
+        self.customer.to_string()
+    }
+}
+
+impl StaticNamed for Depart {
+    fn name() -> &'static str {
+        "depart"
+    }
+}
+
+impl Event for Depart {
+    type State = Bank;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.tellers.depart(queue, |customer| Box::new(Depart { customer }));
+        Ok(())
+    }
+}
+
+fn bank(customers: usize, arrival_rate: f64, tellers: usize, service_rate: f64) -> Simulation<Bank> {
+    let mut timeline = Timeline::default();
+    timeline.push(Box::new(Arrive { customer: 0 }));
+    let mut sim = Simulation::from(Scenario {
+        initial: Bank {
+            customers,
+            arrival_rate,
+            tellers: Server::new(tellers, Distribution::Exponential { rate: service_rate }),
+        },
+        timeline,
+        seed: 42,
+    });
+    sim.run().unwrap();
+    sim
+}
+
+/// The mean time spent waiting in an M/M/c queue, using the Erlang C formula.
+fn erlang_c_wait(arrival_rate: f64, servers: usize, service_rate: f64) -> f64 {
+    let offered = arrival_rate / service_rate;
+    let utilisation = offered / servers as f64;
+    let mut term = 1.0;
+    let mut sum = 0.0;
+    for k in 0..servers {
+        sum += term;
+        term *= offered / (k + 1) as f64;
+    }
+    let queued = term / (1.0 - utilisation);
+    let probability_of_waiting = queued / (sum + queued);
+    probability_of_waiting / (servers as f64 * service_rate - arrival_rate)
+}
+
+fn assert_within(expected: f64, actual: f64, tolerance: f64) {
+    let error = (actual - expected).abs() / expected;
+    assert!(error < tolerance, "expected {expected}, got {actual} (error {error})");
+}
+
+#[test]
+fn mmc_bank_matches_erlang_c() {
+    const CUSTOMERS: usize = 50_000;
+    let (arrival_rate, tellers, service_rate) = (1.5, 2, 1.0);
+    let sim = bank(CUSTOMERS, arrival_rate, tellers, service_rate);
+    let now = sim.now();
+    let server = &sim.current_state().tellers;
+    assert_eq!(CUSTOMERS as u64, server.served());
+    assert_eq!(0, server.resource().in_use());
+
+    let wait = erlang_c_wait(arrival_rate, tellers, service_rate);
+    assert_within(wait, server.resource().wait().mean().unwrap(), 0.05);
+    // Little's law: the mean length of the line is the arrival rate times the mean wait
+    assert_within(arrival_rate * wait, server.resource().line().length().mean(now).unwrap(), 0.05);
+    assert_within(arrival_rate / (tellers as f64 * service_rate), server.resource().utilisation(now), 0.02);
+}
+
+#[test]
+fn mm1_bank_matches_analytical() {
+    const CUSTOMERS: usize = 50_000;
+    let (arrival_rate, service_rate) = (0.5, 1.0);
+    let sim = bank(CUSTOMERS, arrival_rate, 1, service_rate);
+    let server = &sim.current_state().tellers;
+
+    // for M/M/1, the mean wait is ρ / (μ - λ)
+    let utilisation = arrival_rate / service_rate;
+    let wait = utilisation / (service_rate - arrival_rate);
+    assert_within(wait, erlang_c_wait(arrival_rate, 1, service_rate), 1e-9);
+    assert_within(wait, server.resource().wait().mean().unwrap(), 0.05);
+    assert_within(utilisation, server.resource().utilisation(sim.now()), 0.02);
+}
+
+#[test]
+fn server_serde_round_trip() {
+    let sim = bank(10, 1.5, 2, 1.0);
+    let server = &sim.current_state().tellers;
+    let yaml = serde_yaml::to_string(server).unwrap();
+    assert!(yaml.contains("exponential"));
+    assert_eq!(server, &serde_yaml::from_str::<Server<usize>>(&yaml).unwrap());
+    assert_eq!(&Distribution::Exponential { rate: 1.0 }, server.service());
+}
+
+/// A bakery with a bounded shelf, where bakers put loaves and shoppers get them.
+#[derive(Debug, Clone, Default)]
+struct Bakery {
+    shelf: Store<usize, char>,
+    log: Vec<String>,
+}
+
+/// An attempt by a baker to put a loaf on the shelf.
+#[derive(Debug)]
+struct Bake {
+    baker: char,
+    loaf: usize,
+}
+
+impl ToString for Bake {
+    fn to_string(&self) -> String {
+        format!("{} {}", self.baker, self.loaf)
+    }
+}
+
+impl StaticNamed for Bake {
+    fn name() -> &'static str {
+        "bake"
+    }
+}
+
+impl Event for Bake {
+    type State = Bakery;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        let shelved = state.shelf.put(self.baker, self.loaf, queue, |shopper, loaf| Box::new(Got { shopper, loaf }));
+        state.log.push(format!("{} {} loaf {}", self.baker, if shelved { "shelved" } else { "holds" }, self.loaf));
+        Ok(())
+    }
+}
+
+/// An attempt by a shopper to get a loaf from the shelf.
+#[derive(Debug)]
+struct Shop {
+    shopper: char,
+}
+
+impl ToString for Shop {
+    fn to_string(&self) -> String {
+        self.shopper.to_string()
+    }
+}
+
+impl StaticNamed for Shop {
+    fn name() -> &'static str {
+        "shop"
+    }
+}
+
+impl Event for Shop {
+    type State = Bakery;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        match state.shelf.get(self.shopper, queue, |baker| Box::new(Shelved { baker })) {
+            Some(loaf) => state.log.push(format!("{} got loaf {loaf}", self.shopper)),
+            None => state.log.push(format!("{} waits", self.shopper)),
+        }
+        Ok(())
+    }
+}
+
+/// Resumes a waiting shopper with a loaf.
+#[derive(Debug)]
+struct Got {
+    shopper: char,
+    loaf: usize,
+}
+
+impl ToString for Got {
+    fn to_string(&self) -> String {
+        format!("{} {}", self.shopper, self.loaf)
+    }
+}
+
+impl StaticNamed for Got {
+    fn name() -> &'static str {
+        "got"
+    }
+}
+
+impl Event for Got {
+    type State = Bakery;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.log.push(format!("{} got loaf {} after waiting", self.shopper, self.loaf));
+        Ok(())
+    }
+}
+
+/// Resumes a waiting baker once its loaf is shelved.
+#[derive(Debug)]
+struct Shelved {
+    baker: char,
+}
+
+impl ToString for Shelved {
+    fn to_string(&self) -> String {
+        self.baker.to_string()
+    }
+}
+
+impl StaticNamed for Shelved {
+    fn name() -> &'static str {
+        "shelved"
+    }
+}
+
+impl Event for Shelved {
+    type State = Bakery;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.log.push(format!("{} shelved held loaf", self.baker));
+        Ok(())
+    }
+}
+
+#[test]
+fn store_put_get() {
+    let mut timeline = Timeline::default();
+    timeline.push_at(1.0, Box::new(Shop { shopper: 'x' }) as Box<dyn Event<State = _>>);
+    timeline.push_at(2.0, Box::new(Bake { baker: 'a', loaf: 0 }));
+    timeline.push_at(3.0, Box::new(Bake { baker: 'a', loaf: 1 }));
+    timeline.push_at(3.0, Box::new(Bake { baker: 'b', loaf: 2 }));
+    timeline.push_at(3.0, Box::new(Bake { baker: 'b', loaf: 3 }));
+    timeline.push_at(5.0, Box::new(Shop { shopper: 'y' }));
+    let mut sim = Simulation::from(Scenario {
+        initial: Bakery {
+            shelf: Store::new(2),
+            log: vec![],
+        },
+        timeline,
+        seed: 0,
+    });
+    sim.run().unwrap();
+    assert_eq!(
+        vec![
+            "x waits",
+            "a shelved loaf 0",
+            "x got loaf 0 after waiting",
+            "a shelved loaf 1",
+            "b shelved loaf 2",
+            "b holds loaf 3",
+            "y got loaf 1",
+            "b shelved held loaf",
+        ],
+        sim.current_state().log
+    );
+    let shelf = &sim.current_state().shelf;
+    assert_eq!(vec![2, 3], shelf.items().copied().collect::<Vec<_>>());
+    assert_eq!(2, shelf.len());
+    assert_eq!(2, shelf.capacity());
+    assert!(shelf.getters().is_empty());
+    assert!(shelf.putters().is_empty());
+    assert_eq!(Some(1.0), shelf.getters().wait().mean());
+    assert_eq!(Some(2.0), shelf.putters().wait().mean());
+    // empty until time 3, then 2 loaves
+    assert_eq!(Some(4.0 / 5.0), shelf.level().mean(5.0));
+}
+
+#[test]
+fn store_unbounded() {
+    let store = Store::<(), ()>::default();
+    assert_eq!(usize::MAX, store.capacity());
+    assert!(store.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "capacity must be greater than 0")]
+fn store_without_capacity() {
+    Store::<(), ()>::new(0);
+}
@@ -0,0 +1,174 @@
+//! The context in which an event is evaluated.
+
+use crate::{Provenance, Queue, Rng, Time};
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// The context in which an event is evaluated, passed to [`crate::Event::apply_with()`]. Exposes the
+/// location of the event in the timeline, the simulation clock, the random number generator, the
+/// [`Queue`] of pending events and the run-wide [`Services`] registered with the simulation.
+///
+/// The context is opaque; new capabilities are added as methods, so that extending the context does
+/// not break existing [`crate::Event`] implementations.
+pub struct ApplyContext<'c, 'q, S> {
+    queue: &'c mut Queue<'q, S>,
+    services: &'c Services,
+    delegated: bool,
+}
+
+impl<'c, 'q, S> ApplyContext<'c, 'q, S> {
+    /// Creates a context around the given queue and services.
+    pub fn new(queue: &'c mut Queue<'q, S>, services: &'c Services) -> Self {
+        Self {
+            queue,
+            services,
+            delegated: false,
+        }
+    }
+
+    /// Creates a context on behalf of the default implementation of [`crate::Event::apply()`], which
+    /// has no access to the services.
+    pub(crate) fn delegated(queue: &'c mut Queue<'q, S>) -> Self {
+        const NO_SERVICES: &Services = &Services::new();
+        Self {
+            queue,
+            services: NO_SERVICES,
+            delegated: true,
+        }
+    }
+
+    /// Returns `true` if this context was created by the default implementation of [`crate::Event::apply()`].
+    pub(crate) fn is_delegated(&self) -> bool {
+        self.delegated
+    }
+
+    /// The location of the event in the timeline. As the event is being evaluated, this is also the
+    /// location of the cursor.
+    pub fn index(&self) -> usize {
+        self.queue.offset() - 1
+    }
+
+    /// The current simulation time; i.e., the time of the event. Equivalent to [`Queue::now()`].
+    pub fn now(&self) -> Time {
+        self.queue.now()
+    }
+
+    /// The random number generator for the event. Equivalent to [`Queue::rng()`].
+    pub fn rng(&mut self) -> &mut Rng {
+        self.queue.rng()
+    }
+
+    /// The provenance of the event; i.e., whether it was added by the user, loaded from a persisted
+    /// scenario, or generated by another event.
+    pub fn provenance(&self) -> Provenance {
+        self.queue.timeline().provenance_at(self.index())
+    }
+
+    /// The queue of pending events, through which the event may schedule, cancel or reschedule
+    /// other events.
+    pub fn queue(&mut self) -> &mut Queue<'q, S> {
+        self.queue
+    }
+
+    /// The service of type `T`, if one is registered with the simulation.
+    pub fn service<T: 'static>(&self) -> Option<&T> {
+        self.services.get()
+    }
+
+    /// The run-wide services registered with the simulation.
+    pub fn services(&self) -> &Services {
+        self.services
+    }
+}
+
+impl<S> Debug for ApplyContext<'_, '_, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApplyContext")
+            .field("index", &self.index())
+            .field("now", &self.now())
+            .field("services", self.services)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A registry of run-wide services, such as loggers, made available to events through the
+/// [`ApplyContext`]. Services are keyed by their type, so at most one service of each type may be
+/// registered.
+///
+/// Services are shared rather than owned by the simulation: a cloned (or forked) simulation shares
+/// its services with the original. Services are immutable from the perspective of events; a service
+/// that must be mutated should use interior mutability (e.g., [`std::cell::RefCell`]). Unlike the
+/// simulation state, services are not rewound when the simulation is reset or jumps backward, so they
+/// should not be used to accumulate results that depend on the timeline; use [`crate::stats`] instead.
+#[derive(Clone, Default)]
+pub struct Services {
+    entries: Option<HashMap<TypeId, Entry>>,
+}
+
+/// A registered service, along with the name of its type.
+type Entry = (&'static str, Rc<dyn Any>);
+
+impl Services {
+    /// Creates an empty registry.
+    pub const fn new() -> Self {
+        Self { entries: None }
+    }
+
+    /// Registers a service, replacing and returning any existing service of the same type.
+    pub fn insert<T: 'static>(&mut self, service: T) -> Option<Rc<T>> {
+        self.insert_shared(Rc::new(service))
+    }
+
+    /// Registers a service that may already be shared with other parties, replacing and returning
+    /// any existing service of the same type.
+    pub fn insert_shared<T: 'static>(&mut self, service: Rc<T>) -> Option<Rc<T>> {
+        self.entries
+            .get_or_insert_with(HashMap::default)
+            .insert(TypeId::of::<T>(), (type_name::<T>(), service))
+            .map(|(_, existing)| existing.downcast().unwrap())
+    }
+
+    /// The service of type `T`, if one is registered.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.entries
+            .as_ref()?
+            .get(&TypeId::of::<T>())
+            .map(|(_, service)| service.downcast_ref().unwrap())
+    }
+
+    /// Deregisters the service of type `T`, returning it if one was registered.
+    pub fn remove<T: 'static>(&mut self) -> Option<Rc<T>> {
+        self.entries
+            .as_mut()?
+            .remove(&TypeId::of::<T>())
+            .map(|(_, service)| service.downcast().unwrap())
+    }
+
+    /// The number of registered services.
+    pub fn len(&self) -> usize {
+        self.entries.as_ref().map_or(0, HashMap::len)
+    }
+
+    /// Returns `true` if no services are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Debug for Services {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut names = self
+            .entries
+            .iter()
+            .flat_map(HashMap::values)
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        f.debug_set().entries(names).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
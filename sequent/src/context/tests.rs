@@ -0,0 +1,187 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, Append, TestState};
+use crate::{ApplyContext, Event, Provenance, Queue, Scenario, Services, Simulation, StaticNamed, Timeline, TransitionError};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Collects log lines emitted by events.
+#[derive(Debug, Default)]
+struct Log(RefCell<Vec<String>>);
+
+/// Logs its location, time and provenance, and spawns a child event until the given depth is reached.
+#[derive(Debug)]
+struct Trace {
+    depth: usize,
+}
+
+impl ToString for Trace {
+    fn to_string(&self) -> String {
+        self.depth.to_string()
+    }
+}
+
+impl StaticNamed for Trace {
+    fn name() -> &'static str {
+        "trace"
+    }
+}
+
+impl Event for Trace {
+    type State = TestState;
+
+    fn apply_with(&self, state: &mut Self::State, context: &mut ApplyContext<Self::State>) -> Result<(), TransitionError> {
+        state.transitions.push(context.index());
+        if let Some(log) = context.service::<Log>() {
+            log.0.borrow_mut().push(format!(
+                "{} at {} ({})",
+                context.index(),
+                context.now(),
+                context.provenance()
+            ));
+        }
+        if self.depth > 0 {
+            let delay = context.rng().uniform(1.0, 2.0);
+            context.queue().schedule_in(delay, Box::new(Trace { depth: self.depth - 1 }));
+        }
+        Ok(())
+    }
+}
+
+/// Implements neither [`Event::apply()`] nor [`Event::apply_with()`].
+#[derive(Debug)]
+struct Inert;
+
+impl ToString for Inert {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for Inert {
+    fn name() -> &'static str {
+        "inert"
+    }
+}
+
+impl Event for Inert {
+    type State = TestState;
+}
+
+fn trace_scenario() -> Scenario<TestState> {
+    let mut timeline = Timeline::default();
+    timeline.push_at(1.0, Box::new(Append { id: 100 }) as Box<dyn Event<State = _>>);
+    timeline.push_at(1.0, Box::new(Trace { depth: 2 }));
+    Scenario {
+        initial: TestState::default(),
+        timeline,
+        seed: 0,
+    }
+}
+
+#[test]
+fn apply_with_context() {
+    let mut sim = Simulation::from(trace_scenario());
+    sim.services_mut().insert(Log::default());
+    sim.run().unwrap();
+    assert_eq!(vec![100, 1, 2, 3], sim.current_state().transitions);
+
+    let log = sim.services().get::<Log>().unwrap().0.borrow().clone();
+    let times = sim.scenario().timeline.times();
+    assert_eq!(
+        vec![
+            "1 at 1 (user)".to_string(),
+            format!("2 at {} (generated by 1)", times[2]),
+            format!("3 at {} (generated by 2)", times[3]),
+        ],
+        log
+    );
+}
+
+#[test]
+fn apply_with_without_services() {
+    let mut sim = Simulation::from(trace_scenario());
+    sim.run().unwrap();
+    assert_eq!(vec![100, 1, 2, 3], sim.current_state().transitions);
+}
+
+#[test]
+fn forked_simulation_shares_services() {
+    let mut sim = Simulation::from(trace_scenario());
+    sim.services_mut().insert(Log::default());
+    let mut fork = sim.fork();
+    fork.run().unwrap();
+    assert_eq!(3, sim.services().get::<Log>().unwrap().0.borrow().len());
+}
+
+#[test]
+fn legacy_apply_delegates_to_apply_with() {
+    let timeline = Timeline::from(vec![Box::new(Trace { depth: 1 }) as Box<dyn Event<State = _>>]);
+    let mut state = TestState::default();
+    let mut queue = Queue::new(1, &timeline);
+    Trace { depth: 1 }.apply(&mut state, &mut queue).unwrap();
+    assert_eq!(vec![0], state.transitions);
+    assert_eq!(1, queue.into_inner().2.len());
+}
+
+#[test]
+fn apply_with_delegates_to_legacy_apply() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+}
+
+#[test]
+fn event_implementing_neither_method() {
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: Timeline::from(vec![Box::new(Inert) as Box<dyn Event<State = _>>]),
+        seed: 0,
+    });
+    assert_eq!(
+        TransitionError("event 'inert' implements neither apply() nor apply_with()".into()),
+        sim.step().unwrap_err().transition().unwrap()
+    );
+}
+
+#[test]
+fn context_implements_debug() {
+    let timeline = Timeline::from(vec![Box::new(Inert) as Box<dyn Event<State = TestState>>]);
+    let mut queue = Queue::new(1, &timeline);
+    let mut services = Services::new();
+    services.insert(Log::default());
+    let context = ApplyContext::new(&mut queue, &services);
+    assert_eq!(Provenance::User, context.provenance());
+    assert_eq!(1, context.services().len());
+    assert_eq!(
+        "ApplyContext { index: 0, now: 0.0, services: {\"sequent::context::tests::Log\"}, .. }",
+        format!("{context:?}")
+    );
+}
+
+#[test]
+fn services() {
+    let mut services = Services::new();
+    assert!(services.is_empty());
+    assert_eq!(None, services.get::<u32>());
+    assert_eq!(None, services.remove::<u32>());
+
+    assert_eq!(None, services.insert(7_u32));
+    assert_eq!(None, services.insert("seven"));
+    assert_eq!(2, services.len());
+    assert_eq!(Some(&7), services.get::<u32>());
+    assert_eq!(Some(&"seven"), services.get::<&str>());
+    assert_eq!("{\"&str\", \"u32\"}", format!("{services:?}"));
+
+    let shared = Rc::new(8_u32);
+    assert_eq!(Some(Rc::new(7)), services.insert_shared(shared.clone()));
+    assert_eq!(2, Rc::strong_count(&shared));
+
+    let cloned = services.clone();
+    assert_eq!(Some(&8), cloned.get::<u32>());
+    assert_eq!(3, Rc::strong_count(&shared));
+
+    assert_eq!(Some(shared), services.remove::<u32>());
+    assert_eq!(1, services.len());
+    assert_eq!(Some(&8), cloned.get::<u32>());
+}
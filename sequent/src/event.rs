@@ -1,13 +1,21 @@
 //! Aspects of the simulation relating to (discrete) events.
 
+use crate::{ApplyContext, Payload, Rng};
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::collections::BTreeMap;
-use std::fmt::{Debug, Formatter};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::rc::{Rc, Weak};
 use std::str::FromStr;
 use thiserror::Error;
 
+/// A point in simulated time. Time is measured in arbitrary, model-specific units, starting at
+/// zero.
+pub type Time = f64;
+
 /// A mutable view over the event timeline. The queue
 /// is notionally subdivided into _past_, _current_ and _future_ events. The past events
 /// are those that have already been executed. The current event is the one at which the
@@ -15,8 +23,22 @@ use thiserror::Error;
 /// events comprise the sequence that follows the current event.
 pub struct Queue<'a, S> {
     offset: usize,
-    timeline: &'a Vec<Box<dyn Event<State = S>>>,
-    insertions: Vec<(usize, Box<dyn Event<State = S>>)>
+    timeline: &'a Timeline<S>,
+    insertions: Vec<(Placement, Box<dyn Event<State = S>>)>,
+    cancellations: BTreeSet<usize>,
+    reschedules: BTreeMap<usize, Time>,
+    rng: Rng,
+}
+
+/// Determines where an event added to the [`Queue`] will be placed in the timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement {
+    /// At the given index, relative to the first future event. The event inherits the time of its
+    /// immediate predecessor.
+    Index(usize),
+
+    /// In chronological order, after all future events scheduled at or before the given time.
+    Time(Time),
 }
 
 impl<'a, S> Queue<'a, S> {
@@ -25,62 +47,259 @@ impl<'a, S> Queue<'a, S> {
     ///
     /// # Panics
     /// If the offset is less than 1 or exceeds the length of the timeline.
-    pub fn new(offset: usize, timeline: &'a Vec<Box<dyn Event<State = S>>>) -> Self {
+    pub fn new(offset: usize, timeline: &'a Timeline<S>) -> Self {
         assert!(offset >= 1, "offset ({offset}) cannot be less than 1");
         assert!(offset <= timeline.len(), "offset ({offset}) cannot exceed length of timeline {}", timeline.len());
         Self {
             offset,
             timeline,
-            insertions: Vec::default()
+            insertions: Vec::default(),
+            cancellations: BTreeSet::default(),
+            reschedules: BTreeMap::default(),
+            rng: Rng::new(0),
         }
     }
 
+    /// Replaces the random number generator made available to the event.
+    pub(crate) fn with_rng(mut self, rng: Rng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// The random number generator for the current event. Events should draw all random numbers
+    /// from this generator, so that their evaluation is reproducible.
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
     /// Insert an event into the specified location in the queue. The effect on the queue
     /// (and the underlying event timeline) will not persist until after [`Event::apply()`]
     /// returns. Equivalently, the [`Queue::future()`] view will not change after calling this method.
     ///
+    /// The inserted event is scheduled at the same time as its immediate predecessor.
+    ///
     /// # Panics
     /// If the insertion index exceeds the length of the timeline.
     pub fn insert_later(&mut self, index: usize, event: Box<dyn Event<State = S>>) {
-        let lim = self.timeline.len() + self.insertions.len() - self.offset;
+        let lim = self.len_later();
         assert!(index <= lim, "insertion index ({index}) cannot exceed length of queue ({lim})");
-        self.insertions.push((index, event));
+        self.insertions.push((Placement::Index(index), event));
     }
 
     /// Push an event onto the end of the queue. The effect on the queue
     /// (and the underlying event timeline) will not persist until after [`Event::apply()`]
     /// returns. Equivalently, the [`Queue::future()`] view will not change after calling this method.
+    ///
+    /// The pushed event is scheduled at the same time as its immediate predecessor.
     pub fn push_later(&mut self, event: Box<dyn Event<State = S>>) {
-        self.insert_later(self.timeline.len() + self.insertions.len() - self.offset, event);
+        self.insert_later(self.len_later(), event);
+    }
+
+    /// Schedules an event at the given (absolute) time. The event will be placed after all future
+    /// events scheduled at or before that time, so that events scheduled for the same time are
+    /// evaluated in the order in which they were scheduled. The effect on the queue will not persist
+    /// until after [`Event::apply()`] returns.
+    ///
+    /// # Panics
+    /// If the time precedes [`Queue::now()`] or is not a number.
+    pub fn schedule_at(&mut self, time: Time, event: Box<dyn Event<State = S>>) {
+        let now = self.now();
+        assert!(time >= now, "scheduled time ({time}) cannot precede the current time ({now})");
+        self.insertions.push((Placement::Time(time), event));
+    }
+
+    /// Schedules an event after the given delay, relative to [`Queue::now()`]. The effect on the queue
+    /// will not persist until after [`Event::apply()`] returns.
+    ///
+    /// # Panics
+    /// If the delay is negative or is not a number.
+    pub fn schedule_in(&mut self, delay: Time, event: Box<dyn Event<State = S>>) {
+        assert!(delay >= 0.0, "delay ({delay}) cannot be negative");
+        self.schedule_at(self.now() + delay, event);
+    }
+
+    /// Cancels the future event at the given index, relative to [`Queue::future()`]. The cancelled
+    /// event will be removed from the timeline after [`Event::apply()`] returns. Cancelling an event
+    /// more than once has no further effect.
+    ///
+    /// Changes to the queue are applied atomically after [`Event::apply()`] returns, in the following
+    /// order: insertions, then cancellations, then reschedules. Indices passed to [`Queue::cancel()`]
+    /// and [`Queue::reschedule()`] always refer to the [`Queue::future()`] view, which does not change
+    /// during the evaluation of the event, irrespective of any insertions. Only events that were
+    /// already in the timeline may be cancelled or rescheduled.
+    ///
+    /// # Panics
+    /// If the index is out of bounds.
+    pub fn cancel(&mut self, index: usize) {
+        let len = self.future().len();
+        assert!(index < len, "cancellation index ({index}) must be less than length of future ({len})");
+        self.cancellations.insert(index);
+    }
+
+    /// Cancels all future events that satisfy the given predicate, which is given each event
+    /// in [`Queue::future()`], along with its scheduled time. Returns the number of matching events.
+    /// See [`Queue::cancel()`].
+    pub fn cancel_where(&mut self, mut predicate: impl FnMut(&dyn Event<State = S>, Time) -> bool) -> usize {
+        let times = &self.timeline.times()[self.offset..];
+        let matching = self
+            .future()
+            .iter()
+            .zip(times)
+            .enumerate()
+            .filter(|(_, (event, &time))| predicate(&***event, time))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let count = matching.len();
+        self.cancellations.extend(matching);
+        count
+    }
+
+    /// Reschedules the future event at the given index, relative to [`Queue::future()`], to the given
+    /// (absolute) time. The event is removed from its current location and placed as if by
+    /// [`Queue::schedule_at()`]. If the event is rescheduled more than once, the last time applies;
+    /// if the event is also cancelled, the cancellation takes precedence. See [`Queue::cancel()`] for
+    /// the semantics of combining changes.
+    ///
+    /// # Panics
+    /// If the index is out of bounds, or if the time precedes [`Queue::now()`] or is not a number.
+    pub fn reschedule(&mut self, index: usize, time: Time) {
+        let len = self.future().len();
+        assert!(index < len, "reschedule index ({index}) must be less than length of future ({len})");
+        let now = self.now();
+        assert!(time >= now, "scheduled time ({time}) cannot precede the current time ({now})");
+        self.reschedules.insert(index, time);
+    }
+
+    /// The index of the first future event.
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The underlying timeline.
+    pub(crate) fn timeline(&self) -> &'a Timeline<S> {
+        self.timeline
+    }
+
+    /// The current simulation time; i.e., the time of the current event.
+    pub fn now(&self) -> Time {
+        self.timeline.times()[self.offset - 1]
+    }
+
+    fn len_later(&self) -> usize {
+        self.timeline.len() + self.insertions.len() - self.offset
     }
 
     /// A slice of past (already executed) events. This is an immutable view.
-    pub fn past(&self) -> &[Box<dyn Event<State = S>>] {
+    pub fn past(&self) -> &[Rc<dyn Event<State = S>>] {
         &self.timeline[..self.offset - 1]
     }
 
     /// A slice of future events, excluding the current. This is an immutable view; it does not include
-    /// events added via [`Queue::insert_later()`] or [`Queue::push_later()`].
-    pub fn future(&self) -> &[Box<dyn Event<State = S>>] {
+    /// events added via [`Queue::insert_later()`], [`Queue::push_later()`] or [`Queue::schedule_at()`].
+    pub fn future(&self) -> &[Rc<dyn Event<State = S>>] {
         &self.timeline[self.offset..]
     }
 
     /// Consumes this queue, returning its constituents (`offset`, `timeline`, `insertions`).
+    /// Cancellations and reschedules are discarded.
     #[allow(clippy::type_complexity)]
-    pub fn into_inner(self) -> (usize, &'a Vec<Box<dyn Event<State = S>>>, Vec<(usize, Box<dyn Event<State = S>>)>) {
+    pub fn into_inner(self) -> (usize, &'a Timeline<S>, Vec<(Placement, Box<dyn Event<State = S>>)>) {
         (self.offset, self.timeline, self.insertions)
     }
+
+    pub(crate) fn into_changes(self) -> Changes<S> {
+        Changes {
+            offset: self.offset,
+            insertions: self.insertions.into_iter().map(|(placement, event)| (placement, event.into())).collect(),
+            cancellations: self.cancellations,
+            reschedules: self.reschedules,
+        }
+    }
 }
 
-pub(crate) fn process_insertions<S>(offset: usize, insertions: Vec<(usize, Box<dyn Event<State = S>>)>, timeline: &mut Vec<Box<dyn Event<State = S>>>) {
-    for (index, event) in insertions {
-        timeline.insert(offset + index, event);
+/// The changes to the timeline accumulated by a [`Queue`] during the evaluation of an event.
+pub(crate) struct Changes<S> {
+    pub(crate) offset: usize,
+    pub(crate) insertions: Vec<(Placement, Rc<dyn Event<State = S>>)>,
+    cancellations: BTreeSet<usize>,
+    reschedules: BTreeMap<usize, Time>,
+}
+
+impl<S> Changes<S> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.insertions.is_empty() && self.cancellations.is_empty() && self.reschedules.is_empty()
+    }
+
+    /// Applies the changes to the timeline: first the insertions, then the cancellations, then
+    /// the reschedules.
+    pub(crate) fn apply(self, timeline: &mut Timeline<S>) {
+        let Changes { offset, insertions, cancellations, reschedules } = self;
+        if cancellations.is_empty() && reschedules.is_empty() {
+            process_insertions(offset, insertions, timeline);
+            return;
+        }
+
+        // track the locations of the original future events as insertions shift them
+        let mut locations = (offset..timeline.len()).collect::<Vec<_>>();
+        for inserted in process_insertions(offset, insertions, timeline) {
+            for location in &mut locations {
+                if *location >= inserted {
+                    *location += 1;
+                }
+            }
+        }
+
+        let mut removals = cancellations
+            .iter()
+            .map(|&index| (locations[index], index, None))
+            .chain(
+                reschedules
+                    .iter()
+                    .filter(|(index, _)| !cancellations.contains(index))
+                    .map(|(&index, &time)| (locations[index], index, Some(time))),
+            )
+            .collect::<Vec<_>>();
+        removals.sort_by_key(|&(location, _, _)| Reverse(location));
+        let mut rescheduled = vec![];
+        for (location, index, time) in removals {
+            let event = timeline.events.remove(location);
+            timeline.times.remove(location);
+            let origin = timeline.origins.remove(location);
+            if let Some(time) = time {
+                rescheduled.push((index, event, origin, time));
+            }
+        }
+        rescheduled.sort_by_key(|&(index, _, _, _)| index);
+        for (_, event, origin, time) in rescheduled {
+            place(offset, Placement::Time(time), event, origin, timeline);
+        }
     }
 }
 
+/// Merges insertions into the timeline, returning the locations at which the events were inserted.
+/// The inserted events are attributed to the event immediately preceding the offset.
+pub(crate) fn process_insertions<S, E: Into<Rc<dyn Event<State = S>>>>(offset: usize, insertions: Vec<(Placement, E)>, timeline: &mut Timeline<S>) -> Vec<usize> {
+    let parent = Rc::downgrade(&timeline.events[offset - 1]);
+    insertions
+        .into_iter()
+        .map(|(placement, event)| place(offset, placement, event.into(), Origin::Generated(parent.clone(), offset - 1), timeline))
+        .collect()
+}
+
+fn place<S>(offset: usize, placement: Placement, event: Rc<dyn Event<State = S>>, origin: Origin<S>, timeline: &mut Timeline<S>) -> usize {
+    let (index, time) = match placement {
+        Placement::Index(index) => (offset + index, timeline.times[offset + index - 1]),
+        Placement::Time(time) => (offset + timeline.times[offset..].partition_point(|&t| t <= time), time),
+    };
+    timeline.events.insert(index, event);
+    timeline.times.insert(index, time);
+    timeline.origins.insert(index, origin);
+    index
+}
+
 /// Dereferencing a [`Queue`] is equivalent to [`Queue::future()`].
 impl<S> Deref for Queue<'_, S> {
-    type Target = [Box<dyn Event<State = S>>];
+    type Target = [Rc<dyn Event<State = S>>];
 
     fn deref(&self) -> &Self::Target {
         self.future()
@@ -122,9 +341,62 @@ pub trait Event: Named + Debug + ToString {
     /// also insert or append new events to the pending queue. (Changes to the queue, if any, will
     /// only be persisted after [`Event::apply()`] returns.)
     ///
+    /// This is the original form of [`Event::apply_with()`], which the simulation invokes instead.
+    /// Existing implementations of [`Event::apply()`] continue to work unchanged; new implementations
+    /// should prefer [`Event::apply_with()`], which provides a richer [`ApplyContext`]. The default
+    /// implementation delegates to [`Event::apply_with()`], with a context that has no services.
+    /// An event must implement one of the two methods.
+    ///
     /// # Errors
     /// [`TransitionError`] if the event could not be evaluated.
-    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError>;
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        self.apply_with(state, &mut ApplyContext::delegated(queue))
+    }
+
+    /// Evaluates the event in the course of a simulation, as per [`Event::apply()`], with access to
+    /// the [`ApplyContext`], which exposes the location of the event in the timeline, the simulation
+    /// clock, the random number generator, the queue and the services registered with the simulation.
+    ///
+    /// The default implementation delegates to [`Event::apply()`], so that events written against the
+    /// original signature need not change.
+    ///
+    /// # Errors
+    /// [`TransitionError`] if the event could not be evaluated, or if the event implements neither
+    /// [`Event::apply()`] nor [`Event::apply_with()`].
+    fn apply_with(&self, state: &mut Self::State, context: &mut ApplyContext<Self::State>) -> Result<(), TransitionError> {
+        if context.is_delegated() {
+            return Err(TransitionError(
+                format!("event '{}' implements neither apply() nor apply_with()", self.name()).into(),
+            ));
+        }
+        self.apply(state, context.queue())
+    }
+
+    /// Obtains a [`ReversibleEvent`] view of this event, if the event can be reversed. The default
+    /// implementation returns `None`. Events that implement [`ReversibleEvent`] should override this
+    /// method to return `Some(self)`.
+    fn as_reversible(&self) -> Option<&dyn ReversibleEvent<State = Self::State>> {
+        None
+    }
+
+    /// Obtains a structured [`Payload`] for persisting this event in place of its string form. The
+    /// default implementation returns `None`, in which case the event is persisted using
+    /// [`ToString`]. Events that implement [`serde::Serialize`] may override this method to return
+    /// `Payload::encode(self).ok()`, and should be decoded with a [`crate::SerdeParser`].
+    fn payload(&self) -> Option<Payload> {
+        None
+    }
+}
+
+/// An [`Event`] whose effect on the state can be undone without having to replay the timeline.
+pub trait ReversibleEvent: Event {
+    /// Reverts the effect of a prior [`Event::apply()`], transitioning the state back to what it was
+    /// before the event was applied. Any changes that [`Event::apply()`] made to the queue are not
+    /// reverted.
+    ///
+    /// # Errors
+    /// [`TransitionError`] if the event could not be reversed.
+    fn unapply(&self, state: &mut Self::State) -> Result<(), TransitionError>;
 }
 
 /// Produced by [`Event::apply()`] if an error occurs.
@@ -132,22 +404,336 @@ pub trait Event: Named + Debug + ToString {
 #[error("{0}")]
 pub struct TransitionError(pub Cow<'static, str>);
 
+/// A chronologically ordered sequence of [`Event`] objects, each scheduled at some point in
+/// simulated [`Time`]. Dereferences to a slice of events.
+///
+/// Events are held behind shared handles, so that copies of a timeline (as made when forking a
+/// [`crate::Simulation`]) share the underlying event objects.
+pub struct Timeline<S> {
+    events: Vec<Rc<dyn Event<State = S>>>,
+    times: Vec<Time>,
+    origins: Vec<Origin<S>>,
+}
+
+/// Describes how an event came to be in the [`Timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Provenance {
+    /// Added by the user, rather than by another event.
+    User,
+
+    /// Loaded from a persisted scenario that did not record the provenance of the event.
+    Loaded,
+
+    /// Generated by the event at the given location in the timeline, by way of the [`Queue`].
+    Generated(usize),
+
+    /// Generated by an event that is no longer in the timeline.
+    Orphaned,
+}
+
+impl Provenance {
+    /// Returns `true` if the event was generated by another event, irrespective of whether the generating
+    /// event is still in the timeline.
+    pub fn is_generated(&self) -> bool {
+        matches!(self, Self::Generated(_) | Self::Orphaned)
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Provenance::User => write!(f, "user"),
+            Provenance::Loaded => write!(f, "loaded"),
+            Provenance::Generated(index) => write!(f, "generated by {index}"),
+            Provenance::Orphaned => write!(f, "orphaned"),
+        }
+    }
+}
+
+/// The internal form of a [`Provenance`]. Generating events are identified by reference rather than
+/// by location, so that the provenance remains accurate as the timeline is edited. The location of the
+/// generating event at the time is also retained, as a hint for finding it again.
+enum Origin<S> {
+    User,
+    Loaded,
+    Generated(Weak<dyn Event<State = S>>, usize),
+    Orphaned,
+}
+
+impl<S> Origin<S> {
+    fn resolve(&self, locations: &HashMap<*const (), usize>) -> Provenance {
+        match self {
+            Origin::User => Provenance::User,
+            Origin::Loaded => Provenance::Loaded,
+            Origin::Generated(parent, _) => locations
+                .get(&parent.as_ptr().cast::<()>())
+                .map_or(Provenance::Orphaned, |&index| Provenance::Generated(index)),
+            Origin::Orphaned => Provenance::Orphaned,
+        }
+    }
+}
+
+impl<S> Clone for Origin<S> {
+    fn clone(&self) -> Self {
+        match self {
+            Origin::User => Origin::User,
+            Origin::Loaded => Origin::Loaded,
+            Origin::Generated(parent, hint) => Origin::Generated(parent.clone(), *hint),
+            Origin::Orphaned => Origin::Orphaned,
+        }
+    }
+}
+
+impl<S> Timeline<S> {
+    /// The times at which the events are scheduled. The times are in non-decreasing order, and the
+    /// returned slice is of the same length as the timeline.
+    pub fn times(&self) -> &[Time] {
+        &self.times
+    }
+
+    /// Appends an event, scheduling it at the same time as the last event in the timeline, or at
+    /// time zero if the timeline is empty.
+    pub fn push(&mut self, event: Box<dyn Event<State = S>>) {
+        let time = self.times.last().copied().unwrap_or_default();
+        self.events.push(event.into());
+        self.times.push(time);
+        self.origins.push(Origin::User);
+    }
+
+    /// Appends an event, scheduling it at the given time.
+    ///
+    /// # Panics
+    /// If the time precedes that of the last event in the timeline or is not a number.
+    pub fn push_at(&mut self, time: Time, event: Box<dyn Event<State = S>>) {
+        let last = self.times.last().copied().unwrap_or_default();
+        assert!(time >= last, "scheduled time ({time}) cannot precede that of the last event ({last})");
+        self.events.push(event.into());
+        self.times.push(time);
+        self.origins.push(Origin::User);
+    }
+
+    /// Inserts an event at the given location, shifting all subsequent events. The event is scheduled
+    /// at the same time as its predecessor, or at time zero if it has no predecessor.
+    ///
+    /// # Panics
+    /// If the location exceeds the length of the timeline.
+    pub fn insert(&mut self, index: usize, event: Box<dyn Event<State = S>>) {
+        self.insert_shared(index, event.into());
+    }
+
+    pub(crate) fn insert_shared(&mut self, index: usize, event: Rc<dyn Event<State = S>>) {
+        let len = self.events.len();
+        assert!(index <= len, "insertion index ({index}) cannot exceed length of timeline ({len})");
+        let time = match index {
+            0 => 0.0,
+            index => self.times[index - 1],
+        };
+        self.events.insert(index, event);
+        self.times.insert(index, time);
+        self.origins.insert(index, Origin::User);
+    }
+
+    /// Removes the event at the given location, shifting all subsequent events. The removed event is
+    /// returned.
+    ///
+    /// # Panics
+    /// If the location is not less than the length of the timeline.
+    pub fn remove(&mut self, index: usize) -> Rc<dyn Event<State = S>> {
+        let len = self.events.len();
+        assert!(index < len, "removal index ({index}) must be less than length of timeline ({len})");
+        self.times.remove(index);
+        self.origins.remove(index);
+        self.events.remove(index)
+    }
+
+    /// Replaces the event at the given location, retaining its scheduled time. The replaced event is
+    /// returned.
+    ///
+    /// # Panics
+    /// If the location is not less than the length of the timeline.
+    pub fn replace(&mut self, index: usize, event: Box<dyn Event<State = S>>) -> Rc<dyn Event<State = S>> {
+        self.replace_shared(index, event.into())
+    }
+
+    pub(crate) fn replace_shared(&mut self, index: usize, event: Rc<dyn Event<State = S>>) -> Rc<dyn Event<State = S>> {
+        let len = self.events.len();
+        assert!(index < len, "replacement index ({index}) must be less than length of timeline ({len})");
+        self.origins[index] = Origin::User;
+        std::mem::replace(&mut self.events[index], event)
+    }
+
+    /// Moves the event at location `from` to location `to`, retaining its provenance. The event is
+    /// scheduled at the same time as its new predecessor, or at time zero if it has no predecessor.
+    pub(crate) fn relocate(&mut self, from: usize, to: usize) {
+        let origin = self.origins[from].clone();
+        let event = self.remove(from);
+        self.insert_shared(to, event);
+        self.origins[to] = origin;
+    }
+
+    /// Shortens the timeline, keeping the first `len` events and dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.events.truncate(len);
+        self.times.truncate(len);
+        self.origins.truncate(len);
+    }
+
+    /// The provenance of each event in the timeline.
+    pub fn provenance(&self) -> Vec<Provenance> {
+        let mut locations = HashMap::with_capacity(self.events.len());
+        for (index, event) in self.events.iter().enumerate() {
+            locations.entry(Rc::as_ptr(event).cast::<()>()).or_insert(index);
+        }
+        self.origins.iter().map(|origin| origin.resolve(&locations)).collect()
+    }
+
+    /// The provenance of the event at the given location.
+    pub(crate) fn provenance_at(&self, index: usize) -> Provenance {
+        match &self.origins[index] {
+            Origin::Generated(parent, hint) => self
+                .locate(parent.as_ptr().cast(), *hint)
+                .map_or(Provenance::Orphaned, Provenance::Generated),
+            origin => origin.resolve(&HashMap::new()),
+        }
+    }
+
+    /// The location of the given event, searching outward from the location given by `hint`. As
+    /// events are only ever generated into the future, a generating event stays where it was while the
+    /// timeline is evaluated, and is found immediately; edits shift it by no more than a few locations.
+    fn locate(&self, event: *const (), hint: usize) -> Option<usize> {
+        let len = self.events.len();
+        let hint = hint.min(len.saturating_sub(1));
+        let matches = |index: &usize| *index < len && Rc::as_ptr(&self.events[*index]).cast::<()>() == event;
+        (0..len).find_map(|distance| [hint.checked_sub(distance), hint.checked_add(distance)].into_iter().flatten().find(matches))
+    }
+
+    /// The causal ancestry of the event at the given location: the location of the event that generated it,
+    /// followed by the location of the event that generated that event, and so on. The ancestry is empty
+    /// if the event was not generated by another event in the timeline.
+    ///
+    /// # Panics
+    /// If the location is not less than the length of the timeline.
+    pub fn ancestry(&self, index: usize) -> Vec<usize> {
+        let len = self.events.len();
+        assert!(index < len, "index ({index}) must be less than length of timeline ({len})");
+        let provenance = self.provenance();
+        let mut ancestry = vec![];
+        let mut current = index;
+        // a well-formed timeline is acyclic, but a persisted one might not be
+        while let (Provenance::Generated(parent), true) = (provenance[current], ancestry.len() < len) {
+            ancestry.push(parent);
+            current = parent;
+        }
+        ancestry
+    }
+
+    /// Drops all generated events (see [`Provenance::is_generated()`]) at or beyond the given location.
+    pub(crate) fn truncate_generated(&mut self, from: usize) {
+        let provenance = self.provenance();
+        let retain = (0..self.events.len())
+            .map(|index| index < from || !provenance[index].is_generated())
+            .collect::<Vec<_>>();
+        let mut mask = retain.iter();
+        self.events.retain(|_| *mask.next().unwrap());
+        let mut mask = retain.iter();
+        self.times.retain(|_| *mask.next().unwrap());
+        let mut mask = retain.iter();
+        self.origins.retain(|_| *mask.next().unwrap());
+    }
+
+    /// Assigns the provenance of each event, as recorded in a persisted scenario.
+    ///
+    /// # Panics
+    /// If the number of elements differs from the length of the timeline, or if an event is said to
+    /// have been generated by an event that is not in the timeline.
+    pub(crate) fn restore_provenance(&mut self, provenance: &[Provenance]) {
+        assert_eq!(self.events.len(), provenance.len());
+        self.origins = provenance
+            .iter()
+            .map(|provenance| match *provenance {
+                Provenance::User => Origin::User,
+                Provenance::Loaded => Origin::Loaded,
+                Provenance::Generated(parent) => Origin::Generated(Rc::downgrade(&self.events[parent]), parent),
+                Provenance::Orphaned => Origin::Orphaned,
+            })
+            .collect();
+    }
+}
+
+impl<S> Clone for Timeline<S> {
+    fn clone(&self) -> Self {
+        Self {
+            events: self.events.clone(),
+            times: self.times.clone(),
+            origins: self.origins.clone(),
+        }
+    }
+}
+
+impl<S> Default for Timeline<S> {
+    fn default() -> Self {
+        Self {
+            events: Vec::default(),
+            times: Vec::default(),
+            origins: Vec::default(),
+        }
+    }
+}
+
+/// Dereferencing a [`Timeline`] yields its events.
+impl<S> Deref for Timeline<S> {
+    type Target = [Rc<dyn Event<State = S>>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.events
+    }
+}
+
+impl<S> Debug for Timeline<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.times.iter().zip(self.events.iter())).finish()
+    }
+}
+
+/// Creates a timeline from a vector of events, all scheduled at time zero.
+impl<S> From<Vec<Box<dyn Event<State = S>>>> for Timeline<S> {
+    fn from(events: Vec<Box<dyn Event<State = S>>>) -> Self {
+        let times = vec![0.0; events.len()];
+        let origins = events.iter().map(|_| Origin::User).collect();
+        let events = events.into_iter().map(Rc::from).collect();
+        Self { events, times, origins }
+    }
+}
+
+/// Collects events into a timeline, all scheduled at time zero.
+impl<S> FromIterator<Box<dyn Event<State = S>>> for Timeline<S> {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Event<State = S>>>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
 /// A complete simulation scenario, comprising the initial state and a timeline of discrete
-/// events.
-#[derive(Debug)]
+/// events. Cloning a scenario is cheap relative to the size of its timeline, as the clone shares
+/// the event objects with the original.
+#[derive(Debug, Clone)]
 pub struct Scenario<S> {
     /// The initial simulation state.
     pub initial: S,
 
     /// Timeline of discrete [`Event`] objects.
-    pub timeline: Vec<Box<dyn Event<State = S>>>,
+    pub timeline: Timeline<S>,
+
+    /// The seed from which the random number generators given to events are derived.
+    pub seed: u64,
 }
 
 impl<S: Default> Default for Scenario<S> {
     fn default() -> Self {
         Self {
             initial: S::default(),
-            timeline: Vec::default(),
+            timeline: Timeline::default(),
+            seed: 0,
         }
     }
 }
@@ -167,6 +753,17 @@ pub trait NamedEventParser: Named {
     /// # Errors
     /// [`ParseEventError`] if the given string slice could not be decoded.
     fn parse(&self, s: &str) -> Result<Box<dyn Event<State = Self::State>>, ParseEventError>;
+
+    /// Constructs an [`Event`] object from its structured [`Payload`]. The default implementation
+    /// rejects all payloads, as events are ordinarily parsed from strings.
+    ///
+    /// # Errors
+    /// [`ParseEventError`] if the given payload could not be decoded.
+    fn parse_payload(&self, _payload: Payload) -> Result<Box<dyn Event<State = Self::State>>, ParseEventError> {
+        Err(ParseEventError(
+            format!("event parser for '{}' does not support structured payloads", self.name()).into(),
+        ))
+    }
 }
 
 /// Decodes a name-value tuple into an [`Event`] object using a preconfigured map of
@@ -195,11 +792,22 @@ impl<S> Decoder<S> {
     /// # Errors
     /// [`ParseEventError`] if an event could not be decoded from the given `name` and `encoded` pair.
     pub fn decode(&self, name: &str, encoded: &str) -> Result<Box<dyn Event<State = S>>, ParseEventError> {
-        let parser = self
-            .by_name
+        self.parser(name)?.parse(encoded)
+    }
+
+    /// Decodes a given structured `payload` for an event of a given `name` into an [`Event`] object.
+    ///
+    /// # Errors
+    /// [`ParseEventError`] if an event could not be decoded from the given `name` and `payload` pair.
+    pub fn decode_payload(&self, name: &str, payload: Payload) -> Result<Box<dyn Event<State = S>>, ParseEventError> {
+        self.parser(name)?.parse_payload(payload)
+    }
+
+    fn parser(&self, name: &str) -> Result<&dyn NamedEventParser<State = S>, ParseEventError> {
+        self.by_name
             .get(name)
-            .ok_or_else(|| ParseEventError(format!("no event parser for '{name}'").into()))?;
-        parser.parse(encoded)
+            .map(AsRef::as_ref)
+            .ok_or_else(|| ParseEventError(format!("no event parser for '{name}'").into()))
     }
 }
 
@@ -136,14 +136,14 @@ impl Event for IndexedEvent {
     }
 }
 
-fn indexed_events(range: Range<usize>) -> Vec<Box<dyn Event<State = TestState>>> {
+fn indexed_events(range: Range<usize>) -> Timeline<TestState> {
     range
         .into_iter()
         .map(|idx| Box::new(IndexedEvent(idx)) as Box<dyn Event<State = TestState>>)
         .collect()
 }
 
-fn indexes(events: &[Box<dyn Event<State = TestState>>]) -> Vec<usize> {
+fn indexes(events: &[Rc<dyn Event<State = TestState>>]) -> Vec<usize> {
     events.iter().map(|event| usize::from_str(&event.to_string()).unwrap()).collect()
 }
 
@@ -256,3 +256,341 @@ fn queue_construct_offset_exceeds_length() {
     let timeline = indexed_events(0..3);
     Queue::new(4, &timeline);
 }
+
+fn timed_events(times: &[Time]) -> Timeline<TestState> {
+    let mut timeline = Timeline::default();
+    for (idx, &time) in times.iter().enumerate() {
+        timeline.push_at(time, Box::new(IndexedEvent(idx)));
+    }
+    timeline
+}
+
+#[test]
+fn timeline_push() {
+    let mut timeline = Timeline::<TestState>::default();
+    timeline.push(Box::new(IndexedEvent(0)));
+    timeline.push_at(1.5, Box::new(IndexedEvent(1)));
+    timeline.push(Box::new(IndexedEvent(2)));
+    assert_eq!(vec![0, 1, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 1.5, 1.5], timeline.times());
+
+    timeline.truncate(1);
+    assert_eq!(vec![0], indexes(&timeline));
+    assert_eq!(&[0.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "scheduled time (1) cannot precede that of the last event (2)")]
+fn timeline_push_at_before_last() {
+    timed_events(&[0.0, 2.0, 1.0]);
+}
+
+#[test]
+fn timeline_insert() {
+    let mut timeline = timed_events(&[1.0, 2.0]);
+    timeline.insert(0, Box::new(IndexedEvent(10)));
+    timeline.insert(2, Box::new(IndexedEvent(20)));
+    timeline.insert(4, Box::new(IndexedEvent(30)));
+    assert_eq!(vec![10, 0, 20, 1, 30], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 1.0, 2.0, 2.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "insertion index (3) cannot exceed length of timeline (2)")]
+fn timeline_insert_beyond_end() {
+    timed_events(&[0.0, 1.0]).insert(3, Box::new(IndexedEvent(10)));
+}
+
+#[test]
+fn timeline_remove() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    assert_eq!(1, indexes(&[timeline.remove(1)])[0]);
+    assert_eq!(vec![0, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 2.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "removal index (2) must be less than length of timeline (2)")]
+fn timeline_remove_beyond_end() {
+    timed_events(&[0.0, 1.0]).remove(2);
+}
+
+#[test]
+fn timeline_replace() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    assert_eq!(1, indexes(&[timeline.replace(1, Box::new(IndexedEvent(10)))])[0]);
+    assert_eq!(vec![0, 10, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 2.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "replacement index (2) must be less than length of timeline (2)")]
+fn timeline_replace_beyond_end() {
+    timed_events(&[0.0, 1.0]).replace(2, Box::new(IndexedEvent(10)));
+}
+
+#[test]
+fn timeline_provenance() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.push_later(Box::new(IndexedEvent(10)));
+    queue.schedule_at(1.5, Box::new(IndexedEvent(20)));
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 1, 20, 2, 10], indexes(&timeline));
+    assert_eq!(
+        vec![
+            Provenance::User,
+            Provenance::User,
+            Provenance::Generated(1),
+            Provenance::User,
+            Provenance::Generated(1)
+        ],
+        timeline.provenance()
+    );
+
+    // events generated by a generated event
+    let mut queue = Queue::new(3, &timeline);
+    queue.insert_later(0, Box::new(IndexedEvent(30)));
+    queue.reschedule(0, 2.5);
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 1, 20, 30, 10, 2], indexes(&timeline));
+    assert_eq!(
+        vec![
+            Provenance::User,
+            Provenance::User,
+            Provenance::Generated(1),
+            Provenance::Generated(2),
+            Provenance::Generated(1),
+            Provenance::User
+        ],
+        timeline.provenance()
+    );
+    assert_eq!(vec![2, 1], timeline.ancestry(3));
+    assert!(timeline.ancestry(5).is_empty());
+
+    timeline.relocate(1, 5);
+    assert_eq!(vec![0, 20, 30, 10, 2, 1], indexes(&timeline));
+    assert_eq!(
+        vec![
+            Provenance::User,
+            Provenance::Generated(5),
+            Provenance::Generated(1),
+            Provenance::Generated(5),
+            Provenance::User,
+            Provenance::User
+        ],
+        timeline.provenance()
+    );
+
+    timeline.replace(1, Box::new(IndexedEvent(40)));
+    timeline.remove(5);
+    assert_eq!(
+        vec![Provenance::User, Provenance::User, Provenance::Orphaned, Provenance::Orphaned, Provenance::User],
+        timeline.provenance()
+    );
+
+    timeline.truncate_generated(3);
+    assert_eq!(vec![0, 40, 30, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 1.5, 1.5, 2.5], timeline.times());
+    assert_eq!(vec![Provenance::User, Provenance::User, Provenance::Orphaned, Provenance::User], timeline.provenance());
+}
+
+#[test]
+fn timeline_provenance_at_follows_edits() {
+    let provenance_at = |timeline: &Timeline<TestState>| (0..timeline.len()).map(|index| timeline.provenance_at(index)).collect::<Vec<_>>();
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.push_later(Box::new(IndexedEvent(10)));
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(Provenance::Generated(1), timeline.provenance_at(3));
+
+    // the generating event is shifted forward, then backward, then past the generated event
+    timeline.insert(0, Box::new(IndexedEvent(20)));
+    timeline.insert(0, Box::new(IndexedEvent(30)));
+    assert_eq!(Provenance::Generated(3), timeline.provenance_at(5));
+    timeline.remove(0);
+    assert_eq!(Provenance::Generated(2), timeline.provenance_at(4));
+    timeline.relocate(2, 4);
+    assert_eq!(Provenance::Generated(4), timeline.provenance_at(3));
+    assert_eq!(timeline.provenance(), provenance_at(&timeline));
+
+    // the generating event is gone
+    timeline.remove(4);
+    assert_eq!(Provenance::Orphaned, timeline.provenance_at(3));
+    assert_eq!(timeline.provenance(), provenance_at(&timeline));
+}
+
+#[test]
+#[should_panic(expected = "index (2) must be less than length of timeline (2)")]
+fn timeline_ancestry_beyond_end() {
+    timed_events(&[0.0, 1.0]).ancestry(2);
+}
+
+#[test]
+fn timeline_restore_provenance() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    timeline.restore_provenance(&[Provenance::Generated(2), Provenance::Generated(0), Provenance::Loaded]);
+    assert_eq!(vec![Provenance::Generated(2), Provenance::Generated(0), Provenance::Loaded], timeline.provenance());
+    assert_eq!(vec![0, 2], timeline.ancestry(1));
+
+    // cyclic provenance does not send the ancestry into an infinite loop
+    timeline.restore_provenance(&[Provenance::Generated(1), Provenance::Generated(0), Provenance::Orphaned]);
+    assert_eq!(vec![0, 1, 0], timeline.ancestry(1));
+}
+
+#[test]
+fn provenance_implements_display() {
+    assert_eq!("user", Provenance::User.to_string());
+    assert_eq!("loaded", Provenance::Loaded.to_string());
+    assert_eq!("generated by 3", Provenance::Generated(3).to_string());
+    assert_eq!("orphaned", Provenance::Orphaned.to_string());
+    assert!(Provenance::Generated(3).is_generated());
+    assert!(Provenance::Orphaned.is_generated());
+    assert!(!Provenance::User.is_generated());
+    assert!(!Provenance::Loaded.is_generated());
+}
+
+#[test]
+fn timeline_from_vec() {
+    let timeline = Timeline::from(vec![Box::new(IndexedEvent(0)) as Box<dyn Event<State = TestState>>]);
+    assert_eq!(vec![0], indexes(&timeline));
+    assert_eq!(&[0.0], timeline.times());
+}
+
+#[test]
+fn timeline_implements_debug() {
+    let timeline = timed_events(&[0.0, 0.5]);
+    let s = format!("{timeline:?}");
+    assert_eq!("[(0.0, IndexedEvent(0)), (0.5, IndexedEvent(1))]", s);
+}
+
+#[test]
+fn queue_now() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    assert_eq!(0.0, Queue::new(1, &timeline).now());
+    assert_eq!(1.0, Queue::new(2, &timeline).now());
+}
+
+#[test]
+fn queue_schedule_at() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0, 2.0, 3.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.schedule_at(2.0, Box::new(IndexedEvent(10)));
+    queue.schedule_at(1.0, Box::new(IndexedEvent(20)));
+    queue.schedule_at(2.0, Box::new(IndexedEvent(30)));
+    queue.schedule_at(5.0, Box::new(IndexedEvent(40)));
+    process_insertions(queue.offset, queue.insertions, &mut timeline);
+    assert_eq!(vec![0, 1, 20, 2, 3, 10, 30, 4, 40], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0, 3.0, 5.0], timeline.times());
+}
+
+#[test]
+fn queue_schedule_in() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.schedule_in(0.5, Box::new(IndexedEvent(10)));
+    queue.schedule_in(0.0, Box::new(IndexedEvent(20)));
+    process_insertions(queue.offset, queue.insertions, &mut timeline);
+    assert_eq!(vec![0, 1, 20, 10, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 1.0, 1.5, 2.0], timeline.times());
+}
+
+#[test]
+fn queue_insert_later_inherits_time() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(1, &timeline);
+    queue.insert_later(1, Box::new(IndexedEvent(10)));
+    queue.push_later(Box::new(IndexedEvent(20)));
+    process_insertions(queue.offset, queue.insertions, &mut timeline);
+    assert_eq!(vec![0, 1, 10, 2, 20], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 1.0, 2.0, 2.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "scheduled time (0.5) cannot precede the current time (1)")]
+fn queue_schedule_at_in_the_past() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.schedule_at(0.5, Box::new(IndexedEvent(10)));
+}
+
+#[test]
+#[should_panic(expected = "delay (-1) cannot be negative")]
+fn queue_schedule_in_negative_delay() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.schedule_in(-1.0, Box::new(IndexedEvent(10)));
+}
+
+#[test]
+fn queue_cancel() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+    let mut queue = Queue::new(1, &timeline);
+    queue.cancel(0);
+    queue.cancel(2);
+    queue.cancel(2);
+    assert_eq!(vec![1, 2, 3, 4], indexes(queue.future()));
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 2, 4], indexes(&timeline));
+    assert_eq!(&[0.0, 2.0, 4.0], timeline.times());
+}
+
+#[test]
+fn queue_cancel_where() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+    let mut queue = Queue::new(1, &timeline);
+    assert_eq!(2, queue.cancel_where(|_, time| time >= 3.0));
+    assert_eq!(1, queue.cancel_where(|event, _| event.to_string() == "1"));
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 2], indexes(&timeline));
+}
+
+#[test]
+fn queue_reschedule() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.reschedule(0, 3.5);
+    queue.reschedule(2, 1.0);
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 1, 4, 3, 2], indexes(&timeline));
+    assert_eq!(&[0.0, 1.0, 1.0, 3.0, 3.5], timeline.times());
+}
+
+#[test]
+fn queue_mixed_changes() {
+    let mut timeline = timed_events(&[0.0, 1.0, 2.0, 3.0, 4.0]);
+    let mut queue = Queue::new(1, &timeline);
+    queue.insert_later(0, Box::new(IndexedEvent(10)));
+    queue.schedule_at(2.5, Box::new(IndexedEvent(20)));
+    queue.cancel(1);
+    queue.reschedule(0, 3.0);
+    queue.reschedule(3, 0.5);
+    queue.cancel(3);
+    queue.into_changes().apply(&mut timeline);
+    assert_eq!(vec![0, 10, 20, 3, 1], indexes(&timeline));
+    assert_eq!(&[0.0, 0.0, 2.5, 3.0, 3.0], timeline.times());
+}
+
+#[test]
+#[should_panic(expected = "cancellation index (2) must be less than length of future (2)")]
+fn queue_cancel_out_of_bounds() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(1, &timeline);
+    queue.cancel(2);
+}
+
+#[test]
+#[should_panic(expected = "reschedule index (1) must be less than length of future (1)")]
+fn queue_reschedule_out_of_bounds() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.reschedule(1, 3.0);
+}
+
+#[test]
+#[should_panic(expected = "scheduled time (0.5) cannot precede the current time (1)")]
+fn queue_reschedule_in_the_past() {
+    let timeline = timed_events(&[0.0, 1.0, 2.0]);
+    let mut queue = Queue::new(2, &timeline);
+    queue.reschedule(0, 0.5);
+}
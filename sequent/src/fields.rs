@@ -0,0 +1,77 @@
+//! The whitespace-separated encoding of event fields, as used by `#[derive(Event)]` in the
+//! `sequent-derive` crate.
+//!
+//! A field is written as-is, unless it is empty or contains whitespace or a double quote, in which
+//! case it is enclosed in double quotes, with any `"` or `\` inside escaped by a preceding `\`. So
+//! `42` encodes to `42`, `a b` to `"a b"`, and the empty string to `""`.
+
+use crate::ParseEventError;
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::Display;
+
+/// Writes the string form of `value` as a single field, quoting it if necessary.
+///
+/// # Errors
+/// [`fmt::Error`] if the underlying writer fails.
+pub fn write_field<T: Display + ?Sized>(f: &mut impl fmt::Write, value: &T) -> fmt::Result {
+    let encoded = value.to_string();
+    if !encoded.is_empty() && !encoded.contains(|ch: char| ch.is_whitespace() || ch == '"') {
+        return f.write_str(&encoded);
+    }
+
+    f.write_char('"')?;
+    for ch in encoded.chars() {
+        if ch == '"' || ch == '\\' {
+            f.write_char('\\')?;
+        }
+        f.write_char(ch)?;
+    }
+    f.write_char('"')
+}
+
+/// Splits `s` into its fields, undoing the quoting applied by [`write_field()`]. Fields are separated
+/// by one or more whitespace characters; leading and trailing whitespace is ignored. Unquoted fields
+/// are borrowed from `s`.
+///
+/// # Errors
+/// [`ParseEventError`] if a quoted field is unterminated, contains an invalid escape sequence, or
+/// is not followed by whitespace.
+pub fn split_fields(s: &str) -> Result<Vec<Cow<'_, str>>, ParseEventError> {
+    let mut fields = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    loop {
+        while chars.next_if(|(_, ch)| ch.is_whitespace()).is_some() {}
+        let (start, quoted) = match chars.peek() {
+            None => return Ok(fields),
+            Some(&(start, ch)) => (start, ch == '"'),
+        };
+
+        if quoted {
+            let mut field = String::new();
+            chars.next();
+            loop {
+                match chars.next().map(|(_, ch)| ch) {
+                    Some('"') => break,
+                    Some('\\') => match chars.next().map(|(_, ch)| ch) {
+                        Some(ch @ ('"' | '\\')) => field.push(ch),
+                        _ => return Err(ParseEventError(format!("invalid escape sequence in '{s}'").into())),
+                    },
+                    Some(ch) => field.push(ch),
+                    None => return Err(ParseEventError(format!("unterminated quoted field in '{s}'").into())),
+                }
+            }
+            if matches!(chars.peek(), Some((_, ch)) if !ch.is_whitespace()) {
+                return Err(ParseEventError(format!("expected whitespace after quoted field in '{s}'").into()));
+            }
+            fields.push(Cow::Owned(field));
+        } else {
+            while chars.next_if(|(_, ch)| !ch.is_whitespace()).is_some() {}
+            let end = chars.peek().map_or(s.len(), |&(end, _)| end);
+            fields.push(Cow::Borrowed(&s[start..end]));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
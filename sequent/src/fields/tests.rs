@@ -0,0 +1,51 @@
+// $coverage:ignore-start
+
+use crate::fields::{split_fields, write_field};
+use crate::ParseEventError;
+
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    write_field(&mut encoded, value).unwrap();
+    encoded
+}
+
+#[test]
+fn write_field_quotes_when_necessary() {
+    assert_eq!("42", encode("42"));
+    assert_eq!(r"C:\temp", encode(r"C:\temp"));
+    assert_eq!(r#""""#, encode(""));
+    assert_eq!(r#""a b""#, encode("a b"));
+    assert_eq!("\"tab\there\"", encode("tab\there"));
+    assert_eq!(r#""say \"hi\"""#, encode(r#"say "hi""#));
+    assert_eq!(r#""a\\ b""#, encode(r"a\ b"));
+}
+
+#[test]
+fn split_fields_round_trip() {
+    let values = ["", "a b", "42", r#"say "hi""#, r"a\ b", r"C:\temp", " ", "\"", "\\"];
+    let encoded = values.iter().map(|value| encode(value)).collect::<Vec<_>>().join(" ");
+    assert_eq!(values.to_vec(), split_fields(&encoded).unwrap());
+}
+
+#[test]
+fn split_fields_ignores_surrounding_whitespace() {
+    assert_eq!(vec!["a", "b c", ""], split_fields(" \ta  \"b c\"\t\"\" ").unwrap());
+    assert!(split_fields("").unwrap().is_empty());
+    assert!(split_fields("  ").unwrap().is_empty());
+}
+
+#[test]
+fn split_fields_rejects_malformed_quotes() {
+    assert_eq!(
+        ParseEventError(r#"unterminated quoted field in 'a "b'"#.into()),
+        split_fields(r#"a "b"#).unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError(r#"invalid escape sequence in '"a\b"'"#.into()),
+        split_fields(r#""a\b""#).unwrap_err()
+    );
+    assert_eq!(
+        ParseEventError(r#"expected whitespace after quoted field in '"a"b'"#.into()),
+        split_fields(r#""a"b"#).unwrap_err()
+    );
+}
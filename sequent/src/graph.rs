@@ -0,0 +1,154 @@
+//! Export of the causal structure of a timeline as a directed acyclic graph, in which each event is a
+//! node, and an edge connects each generated event to the event that generated it (see [`Provenance`]).
+//! The graph may be rendered in the Graphviz DOT language or as a Mermaid flowchart.
+
+use crate::persistence::UnsupportedFileFormatError;
+use crate::{Provenance, Timeline};
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use thiserror::Error;
+
+/// Supported graph formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The Graphviz DOT language. Files use the `dot` or `gv` extension.
+    Dot,
+
+    /// A Mermaid flowchart. Files use the `mmd` or `mermaid` extension.
+    Mermaid,
+}
+
+impl Format {
+    /// Infers the format from the extension of the given path.
+    ///
+    /// # Errors
+    /// [`UnsupportedFileFormatError`] if the extension does not correspond to a supported format.
+    pub fn from_path(path: &Path) -> Result<Self, UnsupportedFileFormatError> {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_str().unwrap_or_default())
+            .unwrap_or_default();
+        match ext {
+            "dot" | "gv" => Ok(Format::Dot),
+            "mmd" | "mermaid" => Ok(Format::Mermaid),
+            _ => Err(UnsupportedFileFormatError(format!(
+                "expected file extension 'dot', 'gv', 'mmd' or 'mermaid', got '{ext}'"
+            ))),
+        }
+    }
+}
+
+/// Renders the causality graph of a timeline in the given format. Nodes are labelled with the location,
+/// name and encoded arguments of each event.
+pub fn render<S>(timeline: &Timeline<S>, format: Format) -> String {
+    match format {
+        Format::Dot => dot(timeline),
+        Format::Mermaid => mermaid(timeline),
+    }
+}
+
+/// Renders the causality graph of a timeline in the Graphviz DOT language.
+pub fn dot<S>(timeline: &Timeline<S>) -> String {
+    let mut buf = String::from("digraph timeline {\n");
+    for (index, label) in labels(timeline).into_iter().enumerate() {
+        let label = label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+        buf.push_str(&format!("    e{index} [label=\"{label}\"];\n"));
+    }
+    for (parent, child) in edges(timeline) {
+        buf.push_str(&format!("    e{parent} -> e{child};\n"));
+    }
+    buf.push_str("}\n");
+    buf
+}
+
+/// Renders the causality graph of a timeline as a Mermaid flowchart.
+pub fn mermaid<S>(timeline: &Timeline<S>) -> String {
+    let mut buf = String::from("flowchart TD\n");
+    for (index, label) in labels(timeline).into_iter().enumerate() {
+        let label = label
+            .replace('#', "#35;")
+            .replace('"', "#quot;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;")
+            .replace('\n', "<br>");
+        buf.push_str(&format!("    e{index}[\"{label}\"]\n"));
+    }
+    for (parent, child) in edges(timeline) {
+        buf.push_str(&format!("    e{parent} --> e{child}\n"));
+    }
+    buf
+}
+
+fn labels<S>(timeline: &Timeline<S>) -> Vec<String> {
+    timeline
+        .iter()
+        .enumerate()
+        .map(|(index, event)| {
+            let encoded = event.to_string();
+            if encoded.is_empty() {
+                format!("{index}: {}", event.name())
+            } else {
+                format!("{index}: {} {encoded}", event.name())
+            }
+        })
+        .collect()
+}
+
+fn edges<S>(timeline: &Timeline<S>) -> Vec<(usize, usize)> {
+    timeline
+        .provenance()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(child, provenance)| match provenance {
+            Provenance::Generated(parent) => Some((parent, child)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Writes the causality graph of a timeline to a file, inferring the format from the file extension.
+/// (See [`Format::from_path()`].)
+///
+/// # Errors
+/// [`WriteGraphError`] if the graph could not be written.
+pub fn write_to_file<S>(timeline: &Timeline<S>, path: impl AsRef<Path>) -> Result<(), WriteGraphError> {
+    let format = Format::from_path(path.as_ref())?;
+    let mut w = BufWriter::new(File::create(&path)?);
+    w.write_all(render(timeline, format).as_bytes())?;
+    w.flush()?;
+    Ok(())
+}
+
+/// Produced when a graph could not be written to a file.
+#[derive(Debug, Error)]
+pub enum WriteGraphError {
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("unsupported file format: {0}")]
+    UnsupportedFileFormat(#[from] UnsupportedFileFormatError),
+}
+
+/// Error variant conversions.
+impl WriteGraphError {
+    /// Converts the error into an [`Option<io::Error>`].
+    pub fn io(self) -> Option<io::Error> {
+        match self {
+            WriteGraphError::Io(err) => Some(err),
+            _ => None
+        }
+    }
+
+    /// Converts the error into an [`Option<UnsupportedFileFormatError>`].
+    pub fn unsupported_file_format(self) -> Option<UnsupportedFileFormatError> {
+        match self {
+            WriteGraphError::UnsupportedFileFormat(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
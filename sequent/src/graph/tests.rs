@@ -0,0 +1,161 @@
+// $coverage:ignore-start
+
+use crate::graph::{dot, mermaid, render, write_to_file, Format};
+use crate::test_fixtures::{Append, TestState};
+use crate::{Event, Provenance, Queue, StaticNamed, Timeline, TransitionError};
+use flanker_temp::TempPath;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+struct Note(String);
+
+impl ToString for Note {
+    fn to_string(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl StaticNamed for Note {
+    fn name() -> &'static str {
+        "note"
+    }
+}
+
+impl Event for Note {
+    type State = TestState;
+
+    fn apply(&self, _: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        Ok(())
+    }
+}
+
+fn timeline_fixture() -> Timeline<TestState> {
+    let mut timeline = Timeline::from(vec![
+        Box::new(Append { id: 0 }) as Box<dyn Event<State = _>>,
+        Box::new(Append { id: 1 }),
+        Box::new(Append { id: 2 }),
+        Box::new(Append { id: 3 }),
+    ]);
+    timeline.restore_provenance(&[
+        Provenance::User,
+        Provenance::Generated(0),
+        Provenance::Generated(0),
+        Provenance::Generated(2),
+    ]);
+    timeline
+}
+
+#[test]
+fn format_from_path() {
+    assert_eq!(Format::Dot, Format::from_path(Path::new("graph.dot")).unwrap());
+    assert_eq!(Format::Dot, Format::from_path(Path::new("graph.gv")).unwrap());
+    assert_eq!(Format::Mermaid, Format::from_path(Path::new("graph.mmd")).unwrap());
+    assert_eq!(Format::Mermaid, Format::from_path(Path::new("graph.mermaid")).unwrap());
+    assert_eq!(
+        "expected file extension 'dot', 'gv', 'mmd' or 'mermaid', got 'yaml'",
+        Format::from_path(Path::new("graph.yaml")).unwrap_err().to_string()
+    );
+    assert_eq!(
+        "expected file extension 'dot', 'gv', 'mmd' or 'mermaid', got ''",
+        Format::from_path(Path::new("graph")).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn dot_output() {
+    assert_eq!(
+        "\
+digraph timeline {
+    e0 [label=\"0: append 0\"];
+    e1 [label=\"1: append 1\"];
+    e2 [label=\"2: append 2\"];
+    e3 [label=\"3: append 3\"];
+    e0 -> e1;
+    e0 -> e2;
+    e2 -> e3;
+}
+",
+        dot(&timeline_fixture())
+    );
+}
+
+#[test]
+fn mermaid_output() {
+    assert_eq!(
+        "\
+flowchart TD
+    e0[\"0: append 0\"]
+    e1[\"1: append 1\"]
+    e2[\"2: append 2\"]
+    e3[\"3: append 3\"]
+    e0 --> e1
+    e0 --> e2
+    e2 --> e3
+",
+        mermaid(&timeline_fixture())
+    );
+}
+
+#[test]
+fn render_empty() {
+    let timeline = Timeline::<TestState>::default();
+    assert_eq!("digraph timeline {\n}\n", render(&timeline, Format::Dot));
+    assert_eq!("flowchart TD\n", render(&timeline, Format::Mermaid));
+}
+
+#[test]
+fn render_escapes_labels() {
+    let timeline = Timeline::from(vec![
+        Box::new(Note(r#"say "hi" \ <#1>"#.into())) as Box<dyn Event<State = _>>,
+        Box::new(Note("".into())),
+    ]);
+    assert_eq!(
+        "\
+digraph timeline {
+    e0 [label=\"0: note say \\\"hi\\\" \\\\ <#1>\"];
+    e1 [label=\"1: note\"];
+}
+",
+        render(&timeline, Format::Dot)
+    );
+    assert_eq!(
+        "\
+flowchart TD
+    e0[\"0: note say #quot;hi#quot; \\ #lt;#35;1#gt;\"]
+    e1[\"1: note\"]
+",
+        render(&timeline, Format::Mermaid)
+    );
+}
+
+#[test]
+fn write_then_read() {
+    let temp = TempPath::with_extension("dot");
+    write_to_file(&timeline_fixture(), &temp).unwrap();
+    assert_eq!(dot(&timeline_fixture()), fs::read_to_string(&temp).unwrap());
+
+    let temp = TempPath::with_extension("mmd");
+    write_to_file(&timeline_fixture(), &temp).unwrap();
+    assert_eq!(mermaid(&timeline_fixture()), fs::read_to_string(&temp).unwrap());
+}
+
+#[test]
+fn write_to_file_invalid_format() {
+    let err = write_to_file(&timeline_fixture(), "graph.yaml").unwrap_err();
+    assert_eq!(
+        "unsupported file format: expected file extension 'dot', 'gv', 'mmd' or 'mermaid', got 'yaml'",
+        err.to_string()
+    );
+    assert!(err.unsupported_file_format().is_some());
+}
+
+#[test]
+fn write_to_file_nonexistent_directory() {
+    let err = write_to_file(&timeline_fixture(), "nonexistent_dir/graph.dot").unwrap_err();
+    assert_eq!(
+        "io: No such file or directory (os error 2)",
+        err.to_string()
+    );
+    assert!(err.io().is_some());
+}
@@ -1,8 +1,26 @@
 //! A Discrete-Event Simulation.
 
+mod context;
 mod event;
+mod parallel;
+mod payload;
+mod replicate;
+mod rng;
 mod sim;
+mod sweep;
+pub mod blocks;
+pub mod fields;
+pub mod graph;
 pub mod persistence;
+pub mod stats;
 
+#[cfg(test)]
+mod test_fixtures;
+
+pub use context::*;
 pub use event::*;
-pub use sim::*;
\ No newline at end of file
+pub use payload::*;
+pub use replicate::*;
+pub use rng::*;
+pub use sim::*;
+pub use sweep::*;
\ No newline at end of file
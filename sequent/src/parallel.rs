@@ -0,0 +1,43 @@
+//! A minimal worker pool for running independent jobs in parallel.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Applies `job` to each of the `inputs` on a pool of scoped worker threads, bounded by the available
+/// parallelism, returning the outputs in the order of the inputs. The job is given the index of its input.
+///
+/// Once a job fails, no jobs for later inputs are started. Jobs for earlier inputs still run, so that the
+/// error of the earliest failed job is returned.
+pub(crate) fn map<I: Send, O: Send, E: Send>(
+    inputs: Vec<I>,
+    job: impl Fn(usize, I) -> Result<O, E> + Sync,
+) -> Result<Vec<O>, E> {
+    let n = inputs.len();
+    let workers = thread::available_parallelism().map_or(1, usize::from).min(n);
+    let inputs = inputs.into_iter().map(|input| Mutex::new(Some(input))).collect::<Vec<_>>();
+    let next = AtomicUsize::new(0);
+    let earliest_failure = AtomicUsize::new(usize::MAX);
+    let results = Mutex::new((0..n).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= n || index > earliest_failure.load(Ordering::Relaxed) {
+                    break;
+                }
+                let input = inputs[index].lock().unwrap().take().unwrap();
+                let result = job(index, input);
+                if result.is_err() {
+                    earliest_failure.fetch_min(index, Ordering::Relaxed);
+                }
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results.into_inner().unwrap().into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests;
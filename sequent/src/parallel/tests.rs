@@ -0,0 +1,57 @@
+// $coverage:ignore-start
+
+use crate::parallel::map;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn map_preserves_order() {
+    let outputs = map((0..100).collect(), |index, input: usize| {
+        assert_eq!(index, input);
+        Ok::<_, ()>(input * 2)
+    })
+    .unwrap();
+    assert_eq!((0..100).map(|input| input * 2).collect::<Vec<_>>(), outputs);
+}
+
+#[test]
+fn map_empty() {
+    assert!(map(Vec::<usize>::new(), |_, input| Ok::<_, ()>(input)).unwrap().is_empty());
+}
+
+#[test]
+fn map_inputs_need_not_be_sync() {
+    let outputs = map(vec![Cell::new(1), Cell::new(2)], |_, input| Ok::<_, ()>(input.get())).unwrap();
+    assert_eq!(vec![1, 2], outputs);
+}
+
+#[test]
+fn map_returns_earliest_error() {
+    let err = map((0..100).collect(), |_, input: usize| {
+        if input % 10 == 3 {
+            Err(input)
+        } else {
+            Ok(input)
+        }
+    })
+    .unwrap_err();
+    assert_eq!(3, err);
+}
+
+#[test]
+fn map_runs_every_job_preceding_a_failure() {
+    let preceding = AtomicUsize::new(0);
+    let err = map((0..100).collect(), |_, input: usize| {
+        if input < 50 {
+            preceding.fetch_add(1, Ordering::Relaxed);
+        }
+        if input >= 50 {
+            Err(input)
+        } else {
+            Ok(input)
+        }
+    })
+    .unwrap_err();
+    assert_eq!(50, err);
+    assert_eq!(50, preceding.load(Ordering::Relaxed));
+}
@@ -0,0 +1,85 @@
+//! Structured event payloads.
+
+use crate::{Event, NamedEventParser, ParseEventError, StaticNamed};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+/// A structured, format-neutral representation of the arguments of an [`Event`], produced by
+/// [`Event::payload()`]. Unlike the string form given by [`ToString`], the payload is persisted as a
+/// nested value (e.g., a YAML mapping) rather than an opaque string.
+///
+/// The payload follows the JSON data model; so, for example, the keys of a map must be strings, and
+/// non-finite floating-point numbers are not preserved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Payload(serde_json::Value);
+
+impl Payload {
+    /// Encodes the given value as a payload.
+    ///
+    /// # Errors
+    /// [`serde_json::Error`] if the value cannot be represented in the JSON data model.
+    pub fn encode<T: Serialize + ?Sized>(value: &T) -> Result<Self, serde_json::Error> {
+        Ok(Self(serde_json::to_value(value)?))
+    }
+
+    /// Decodes the payload into a value of type `T`.
+    ///
+    /// # Errors
+    /// [`ParseEventError`] if the payload does not match the structure of `T`.
+    pub fn decode<T: DeserializeOwned>(self) -> Result<T, ParseEventError> {
+        serde_json::from_value(self.0).map_err(|err| ParseEventError(format!("invalid payload: {err}").into()))
+    }
+}
+
+/// Formats the payload as compact JSON.
+impl Display for Payload {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A generic parser for event types that are decoded with serde; the counterpart of
+/// [`Parser`](crate::Parser) for events that implement [`Event::payload()`]. In addition to decoding
+/// payloads, the parser decodes events from their JSON string representation; e.g., when entered
+/// in a REPL.
+pub struct SerdeParser<E>(PhantomData<E>);
+
+impl<E> Default for SerdeParser<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Acquired implementation of [`StaticNamed`] for any [`SerdeParser`] that is parametrised with an
+/// [`Event`] type that is also [`StaticNamed`].
+impl<E> StaticNamed for SerdeParser<E>
+where
+    E: StaticNamed,
+{
+    fn name() -> &'static str {
+        <E as StaticNamed>::name()
+    }
+}
+
+/// Blanket [`NamedEventParser`] implementation for any compliant [`SerdeParser`].
+impl<E> NamedEventParser for SerdeParser<E>
+where
+    E: StaticNamed + DeserializeOwned + Event + 'static,
+{
+    type State = E::State;
+
+    fn parse(&self, s: &str) -> Result<Box<dyn Event<State = Self::State>>, ParseEventError> {
+        let event = serde_json::from_str::<E>(s).map_err(|err| ParseEventError(format!("invalid payload: {err}").into()))?;
+        Ok(Box::new(event))
+    }
+
+    fn parse_payload(&self, payload: Payload) -> Result<Box<dyn Event<State = Self::State>>, ParseEventError> {
+        Ok(Box::new(payload.decode::<E>()?))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,112 @@
+// $coverage:ignore-start
+
+use crate::payload::{Payload, SerdeParser};
+use crate::{Decoder, Event, Named, NamedEventParser, ParseEventError, Parser, Queue, StaticNamed, TransitionError};
+use crate::test_fixtures::{Append, TestState};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Circle { radius: f64 },
+    Square(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Draw {
+    shapes: Vec<Shape>,
+    caption: Option<String>,
+}
+
+impl StaticNamed for Draw {
+    fn name() -> &'static str {
+        "draw"
+    }
+}
+
+impl ToString for Draw {
+    fn to_string(&self) -> String {
+        Payload::encode(self).unwrap().to_string()
+    }
+}
+
+impl Event for Draw {
+    type State = TestState;
+
+    fn apply(&self, _: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        Ok(())
+    }
+
+    fn payload(&self) -> Option<Payload> {
+        Payload::encode(self).ok()
+    }
+}
+
+fn draw() -> Draw {
+    Draw {
+        shapes: vec![Shape::Circle { radius: 1.5 }, Shape::Square(2.0)],
+        caption: None,
+    }
+}
+
+#[test]
+fn encode_decode() {
+    let payload = Payload::encode(&draw()).unwrap();
+    assert_eq!(
+        r#"{"caption":null,"shapes":[{"Circle":{"radius":1.5}},{"Square":2.0}]}"#,
+        payload.to_string()
+    );
+    assert_eq!(draw(), payload.decode::<Draw>().unwrap());
+}
+
+#[test]
+fn encode_unrepresentable() {
+    let map = [((1, 2), "pair")].into_iter().collect::<std::collections::BTreeMap<_, _>>();
+    assert!(Payload::encode(&map).is_err());
+}
+
+#[test]
+fn decode_mismatched() {
+    let payload = Payload::encode(&[1, 2, 3]).unwrap();
+    assert_eq!(
+        ParseEventError("invalid payload: invalid type: integer `1`, expected a sequence".into()),
+        payload.decode::<Draw>().unwrap_err()
+    );
+}
+
+#[test]
+fn serde_parser_parse_string() {
+    let parser = SerdeParser::<Draw>::default();
+    assert_eq!("draw", parser.name());
+    let event = parser.parse(&draw().to_string()).unwrap();
+    assert_eq!(draw().to_string(), event.to_string());
+
+    let err = parser.parse("circle 1.5").err().unwrap();
+    assert!(err.0.starts_with("invalid payload: expected value"), "{err}");
+}
+
+#[test]
+fn serde_parser_parse_payload() {
+    let parser = SerdeParser::<Draw>::default();
+    let event = parser.parse_payload(draw().payload().unwrap()).unwrap();
+    assert_eq!(draw().payload(), event.payload());
+}
+
+#[test]
+fn decoder_dispatches_payload_by_name() {
+    let decoder = Decoder::new(vec![Box::new(SerdeParser::<Draw>::default()), Box::new(Parser::<Append>::default())]);
+    let event = decoder.decode_payload("draw", draw().payload().unwrap()).unwrap();
+    assert_eq!("draw", event.name());
+    assert_eq!(draw().payload(), event.payload());
+
+    // the string path remains available to parsers that do not support payloads
+    assert_eq!("7", decoder.decode("append", "7").unwrap().to_string());
+    assert_eq!(None, Append { id: 7 }.payload());
+    assert_eq!(
+        ParseEventError("event parser for 'append' does not support structured payloads".into()),
+        decoder.decode_payload("append", Payload::encode(&7).unwrap()).err().unwrap()
+    );
+    assert_eq!(
+        ParseEventError("no event parser for 'erase'".into()),
+        decoder.decode_payload("erase", Payload::encode(&7).unwrap()).err().unwrap()
+    );
+}
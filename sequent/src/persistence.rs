@@ -1,8 +1,10 @@
 //! Persistence of a scenario.
 
+pub mod binary;
+pub mod json;
 pub mod yaml;
 
-use crate::{Decoder, ParseEventError, Scenario};
+use crate::{Decoder, Event, ParseEventError, Payload, Provenance, Scenario, Time, Timeline};
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt::{Debug};
@@ -14,23 +16,62 @@ use thiserror::Error;
 
 /// A DTO for shuttling a scenario in a persistence-friendly form. Here, the timeline is replaced
 /// with a vector of [`PersistentEvent`]s, which are encoded versions of the [`Event`](crate::Event) objects.
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct PersistentScenario<S> {
     /// Initial simulation state.
     pub initial: S,
 
     /// Timeline of encoded [`PersistentEvent`]s.
     pub timeline: Vec<PersistentEvent>,
+
+    /// The random number generator seed. Omitted from the persisted form if zero.
+    #[serde(default, skip_serializing_if = "is_zero_seed")]
+    pub seed: u64,
 }
 
 /// A persistence-friendly representation of an [`Event`](crate::Event).
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct PersistentEvent {
     /// The name of the event. Taken from [`crate::Named::name`].
     pub name: String,
 
-    /// An encoded form. Taken from [`ToString::to_string`].
+    /// An encoded form. Taken from [`ToString::to_string`], unless the event has a structured
+    /// [`payload`](Self::payload), in which case it is empty. Omitted from the persisted form if empty.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub encoded: String,
+
+    /// A structured form, which takes precedence over the [`encoded`](Self::encoded) form. Taken from
+    /// [`Event::payload`](crate::Event::payload). Omitted from the persisted form if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Payload>,
+
+    /// The time at which the event is scheduled. Omitted from the persisted form if zero.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub time: Time,
+
+    /// How the event came to be in the timeline. Omitted from the persisted form if
+    /// [`Provenance::Loaded`], which is also assumed when the provenance is absent.
+    #[serde(default = "loaded", skip_serializing_if = "is_loaded")]
+    pub provenance: Provenance,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(time: &Time) -> bool {
+    *time == 0.0
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero_seed(seed: &u64) -> bool {
+    *seed == 0
+}
+
+fn loaded() -> Provenance {
+    Provenance::Loaded
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_loaded(provenance: &Provenance) -> bool {
+    *provenance == Provenance::Loaded
 }
 
 /// Creates a [`PersistentScenario`] from a [`Scenario`] reference.
@@ -41,11 +82,11 @@ impl<S: Clone> From<&Scenario<S>> for PersistentScenario<S> {
             timeline: scenario
                 .timeline
                 .iter()
-                .map(|event| PersistentEvent {
-                    name: event.name().into(),
-                    encoded: event.to_string(),
-                })
+                .zip(scenario.timeline.times())
+                .zip(scenario.timeline.provenance())
+                .map(|((event, &time), provenance)| PersistentEvent::encode(&**event, time, provenance))
                 .collect(),
+            seed: scenario.seed,
         }
     }
 }
@@ -55,21 +96,107 @@ impl<S> PersistentScenario<S> {
     /// This will iterate over all [`PersistentEvent`]s, converting them to their [`Event`](crate::Event) equivalents.
     ///
     /// # Errors
-    /// [`ParseEventError`] if the event could not be decoded.
+    /// [`ParseEventError`] if the event could not be decoded, if the events are not in chronological order,
+    /// or if an event is said to have been generated by an event that is not in the timeline.
     pub fn decode(self, decoder: &Decoder<S>) -> Result<Scenario<S>, ParseEventError> {
-        let mut timeline = Vec::with_capacity(self.timeline.len());
+        let mut builder = TimelineBuilder::new(decoder, self.timeline.len());
         for event in self.timeline {
-            let event = decoder.decode(&event.name, &event.encoded)?;
-            timeline.push(event);
+            builder.push(event)?;
         }
 
         Ok(Scenario {
             initial: self.initial,
-            timeline,
+            timeline: builder.build(),
+            seed: self.seed,
         })
     }
 }
 
+impl PersistentEvent {
+    /// Encodes an [`Event`](crate::Event) that is scheduled at the given time and has the given provenance.
+    fn encode<S>(event: &dyn Event<State = S>, time: Time, provenance: Provenance) -> Self {
+        let payload = event.payload();
+        Self {
+            name: event.name().into(),
+            encoded: if payload.is_some() { String::default() } else { event.to_string() },
+            payload,
+            time,
+            provenance,
+        }
+    }
+
+    /// Decodes a [`PersistentEvent`] into its [`Event`](crate::Event) equivalent, using the supplied
+    /// `decoder`. The [`payload`](Self::payload), if present, takes precedence over the
+    /// [`encoded`](Self::encoded) form.
+    ///
+    /// # Errors
+    /// [`ParseEventError`] if the event could not be decoded.
+    pub fn decode<S>(self, decoder: &Decoder<S>) -> Result<Box<dyn Event<State = S>>, ParseEventError> {
+        match self.payload {
+            Some(payload) => decoder.decode_payload(&self.name, payload),
+            None => decoder.decode(&self.name, &self.encoded),
+        }
+    }
+}
+
+/// The most events for which space is reserved upfront. The length of a timeline is read from the
+/// file, so a larger (e.g., corrupt) length must not exhaust memory before any event is read.
+const MAX_PREALLOCATED: usize = 1 << 16;
+
+/// Assembles a [`Timeline`] from a sequence of [`PersistentEvent`]s, one event at a time, so that the
+/// events need not be held in memory all at once.
+struct TimelineBuilder<'d, S> {
+    decoder: &'d Decoder<S>,
+    len: usize,
+    timeline: Timeline<S>,
+    provenance: Vec<Provenance>,
+}
+
+impl<'d, S> TimelineBuilder<'d, S> {
+    /// Creates a builder for a timeline of the given length.
+    fn new(decoder: &'d Decoder<S>, len: usize) -> Self {
+        Self {
+            decoder,
+            len,
+            timeline: Timeline::default(),
+            provenance: Vec::with_capacity(len.min(MAX_PREALLOCATED)),
+        }
+    }
+
+    /// Decodes the next event and appends it to the timeline.
+    ///
+    /// # Errors
+    /// [`ParseEventError`] if the event could not be decoded, if it is not in chronological order,
+    /// or if it is said to have been generated by an event that is not in the timeline.
+    fn push(&mut self, event: PersistentEvent) -> Result<(), ParseEventError> {
+        let index = self.provenance.len();
+        let last = self.timeline.times().last().copied().unwrap_or_default();
+        if event.time < last || event.time.is_nan() {
+            return Err(ParseEventError(
+                format!("event at index {index} is scheduled at {}, before its predecessor at {last}", event.time).into(),
+            ));
+        }
+        if let Provenance::Generated(parent) = event.provenance {
+            if parent >= self.len || parent == index {
+                return Err(ParseEventError(
+                    format!("event at index {index} cannot have been generated by an event at index {parent}").into(),
+                ));
+            }
+        }
+        let time = event.time;
+        self.provenance.push(event.provenance);
+        let event = event.decode(self.decoder)?;
+        self.timeline.push_at(time, event);
+        Ok(())
+    }
+
+    /// Completes the timeline, restoring the provenance of its events.
+    fn build(mut self) -> Timeline<S> {
+        self.timeline.restore_provenance(&self.provenance);
+        self.timeline
+    }
+}
+
 /// Unwraps a container type into its inner value, consuming the container in the process.
 trait IntoInner<T> {
     /// Obtains the inner value.
@@ -80,7 +207,7 @@ trait IntoInner<T> {
 /// of persistence.
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 #[error("{0}")]
-pub struct UnsupportedFileFormatError(String);
+pub struct UnsupportedFileFormatError(pub(crate) String);
 
 /// Produced when the scenario could not be saved to an output stream or a file. Encompasses all
 /// possible error variants, some of which may not apply in all persistence scenarios.
@@ -91,6 +218,9 @@ pub enum WriteScenarioError {
 
     #[error("unsupported file format: {0}")]
     UnsupportedFileFormat(#[from] UnsupportedFileFormatError),
+
+    #[error("serializer: {0}")]
+    Serializer(#[from] Box<dyn Error>),
 }
 
 /// Error variant conversions.
@@ -99,15 +229,23 @@ impl WriteScenarioError {
     pub fn io(self) -> Option<io::Error> {
         match self {
             WriteScenarioError::Io(err) => Some(err),
-            WriteScenarioError::UnsupportedFileFormat(_) => None
+            _ => None
         }
     }
 
     /// Converts the error into an [`Option<UnsupportedFileFormatError>`].
     pub fn unsupported_file_format(self) -> Option<UnsupportedFileFormatError> {
         match self {
-            WriteScenarioError::Io(_) => None,
-            WriteScenarioError::UnsupportedFileFormat(err) => Some(err)
+            WriteScenarioError::UnsupportedFileFormat(err) => Some(err),
+            _ => None
+        }
+    }
+
+    /// Converts the error into an [`Option<Box<dyn Error>>`].
+    pub fn serializer(self) -> Option<Box<dyn Error>> {
+        match self {
+            WriteScenarioError::Serializer(err) => Some(err),
+            _ => None
         }
     }
 }
@@ -164,6 +302,73 @@ impl ReadScenarioError {
     }
 }
 
+/// Supported scenario file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// YAML, handled by the [`yaml`] module. Files use the `yaml` extension.
+    Yaml,
+
+    /// JSON, handled by the [`json`] module. Files use the `json` extension.
+    Json,
+
+    /// A compact binary format, handled by the [`binary`] module. Files use the `seqb` extension.
+    Binary,
+}
+
+impl Format {
+    /// Infers the format from the extension of the given path.
+    ///
+    /// # Errors
+    /// [`UnsupportedFileFormatError`] if the extension does not correspond to a supported format.
+    pub fn from_path(path: &Path) -> Result<Self, UnsupportedFileFormatError> {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_str().unwrap_or_default())
+            .unwrap_or_default();
+        match ext {
+            "yaml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            "seqb" => Ok(Format::Binary),
+            _ => Err(UnsupportedFileFormatError(format!(
+                "expected file extension 'yaml', 'json' or 'seqb', got '{ext}'"
+            ))),
+        }
+    }
+}
+
+/// Reads and decodes a scenario from a given file, in the format implied by its extension.
+///
+/// # Errors
+/// [`ReadScenarioError`] if the scenario could not be read.
+pub fn read_from_file<S>(
+    decoder: &Decoder<S>,
+    path: impl AsRef<Path>,
+) -> Result<Scenario<S>, ReadScenarioError>
+where
+    for<'de> S: Deserialize<'de>,
+{
+    match Format::from_path(path.as_ref())? {
+        Format::Yaml => yaml::read_from_file(decoder, path),
+        Format::Json => json::read_from_file(decoder, path),
+        Format::Binary => binary::read_from_file(decoder, path),
+    }
+}
+
+/// Writes a scenario to a given file, in the format implied by its extension.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write_to_file<S: Clone + Serialize>(
+    scenario: &Scenario<S>,
+    path: impl AsRef<Path>,
+) -> Result<(), WriteScenarioError> {
+    match Format::from_path(path.as_ref())? {
+        Format::Yaml => yaml::write_to_file(scenario, path),
+        Format::Json => json::write_to_file(scenario, path),
+        Format::Binary => binary::write_to_file(scenario, path),
+    }
+}
+
 fn check_ext(path: &Path, expected: &str) -> Result<(), UnsupportedFileFormatError> {
     let ext = path
         .extension()
@@ -0,0 +1,305 @@
+//! Persistence extensions for working with a compact binary format, suited to large timelines.
+//!
+//! A binary file starts with a six-byte preamble: the magic bytes `SEQB`, the version of the format
+//! and a set of flags, which indicate whether the remainder of the file is compressed. The remainder
+//! comprises the initial state, the seed and the number of events, followed by each event in turn,
+//! all encoded in [MessagePack](https://msgpack.org). As the events are laid out one after the other,
+//! a [`Reader`] decodes them lazily, without holding the entire timeline in its persistent form.
+
+use crate::persistence::{
+    check_ext, PersistentEvent, ReadScenarioError, TimelineBuilder, UnsupportedFileFormatError,
+    WriteScenarioError,
+};
+use crate::{Decoder, ParseEventError, Payload, Provenance, Scenario, Time};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use rmp_serde::decode::ReadReader;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const EXT: &str = "seqb";
+
+const MAGIC: [u8; 4] = *b"SEQB";
+
+/// The version of the format that is written. Files of a later version are rejected.
+const VERSION: u8 = 1;
+
+/// Set if the body of the file is compressed with DEFLATE.
+const FLAG_DEFLATE: u8 = 0b0000_0001;
+
+/// The compression applied to the body of a binary file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// No compression; the fastest to read and write.
+    #[default]
+    None,
+
+    /// DEFLATE compression, which trades speed for a considerably smaller file. Writing is
+    /// several times slower than without compression, whereas reading is only slightly slower.
+    Deflate,
+}
+
+/// Everything in a binary file besides the events. Written as a map, so that the initial state may
+/// safely omit fields.
+#[derive(Serialize, Deserialize)]
+struct Header<S> {
+    initial: S,
+    seed: u64,
+    len: u64,
+}
+
+/// The positional form of a [`PersistentEvent`]. Unlike the latter, which is written as a map of
+/// named fields, an event is written as an array; so no field may be omitted.
+type Record = (String, String, Option<Payload>, Time, Provenance);
+
+/// Writes a scenario in the binary format to an output stream.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write<S: Serialize>(
+    scenario: &Scenario<S>,
+    w: &mut impl Write,
+    compression: Compression,
+) -> Result<(), WriteScenarioError> {
+    let flags = match compression {
+        Compression::None => 0,
+        Compression::Deflate => FLAG_DEFLATE,
+    };
+    w.write_all(&MAGIC)?;
+    w.write_all(&[VERSION, flags])?;
+    match compression {
+        Compression::None => write_body(scenario, w),
+        Compression::Deflate => {
+            // the events are written in small pieces, each of which would otherwise be compressed separately
+            let mut w = BufWriter::new(DeflateEncoder::new(w, flate2::Compression::default()));
+            write_body(scenario, &mut w)?;
+            w.into_inner().map_err(|err| err.into_error())?.finish()?;
+            Ok(())
+        }
+    }
+}
+
+fn write_body<S: Serialize>(scenario: &Scenario<S>, w: &mut impl Write) -> Result<(), WriteScenarioError> {
+    let header = Header {
+        initial: &scenario.initial,
+        seed: scenario.seed,
+        len: scenario.timeline.len() as u64,
+    };
+    rmp_serde::encode::write_named(w, &header).map_err(serializer)?;
+
+    let timeline = &scenario.timeline;
+    for ((event, &time), provenance) in timeline.iter().zip(timeline.times()).zip(timeline.provenance()) {
+        let event = PersistentEvent::encode(&**event, time, provenance);
+        let record: Record = (event.name, event.encoded, event.payload, event.time, event.provenance);
+        rmp_serde::encode::write(w, &record).map_err(serializer)?;
+    }
+    Ok(())
+}
+
+fn serializer(err: rmp_serde::encode::Error) -> WriteScenarioError {
+    WriteScenarioError::Serializer(Box::new(err))
+}
+
+fn deserializer(err: impl Error + 'static) -> ReadScenarioError {
+    ReadScenarioError::Deserializer(Box::new(err))
+}
+
+/// Writes a scenario to a binary file, without compression.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write_to_file<S: Serialize>(
+    scenario: &Scenario<S>,
+    path: impl AsRef<Path>,
+) -> Result<(), WriteScenarioError> {
+    write_to_file_with_compression(scenario, path, Compression::None)
+}
+
+/// Writes a scenario to a binary file, using the given compression.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write_to_file_with_compression<S: Serialize>(
+    scenario: &Scenario<S>,
+    path: impl AsRef<Path>,
+    compression: Compression,
+) -> Result<(), WriteScenarioError> {
+    check_ext(path.as_ref(), EXT)?;
+    let mut w = BufWriter::new(File::create(&path)?);
+    write(scenario, &mut w, compression)?;
+    w.flush()?;
+    Ok(())
+}
+
+/// Reads and decodes a scenario from a given binary file. The compression, if any, is detected
+/// automatically.
+///
+/// # Errors
+/// [`ReadScenarioError`] if the scenario could not be read.
+pub fn read_from_file<S: DeserializeOwned>(
+    decoder: &Decoder<S>,
+    path: impl AsRef<Path>,
+) -> Result<Scenario<S>, ReadScenarioError> {
+    Reader::open(path)?.decode(decoder)
+}
+
+/// Reads a scenario in the binary format from an input stream. The initial state and the seed are
+/// read upfront, whereas the events are read lazily, one at a time, by iterating over the reader.
+/// Each [`PersistentEvent`] may then be decoded with [`PersistentEvent::decode()`]. Alternatively,
+/// [`Reader::decode()`] decodes all events into a [`Scenario`], provided that none have been read.
+pub struct Reader<'r, S> {
+    initial: S,
+    seed: u64,
+    len: usize,
+    remaining: usize,
+    de: rmp_serde::Deserializer<ReadReader<Box<dyn Read + 'r>>>,
+}
+
+impl<S> Debug for Reader<'_, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reader")
+            .field("seed", &self.seed)
+            .field("len", &self.len)
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: DeserializeOwned> Reader<'static, S> {
+    /// Opens a binary file for reading.
+    ///
+    /// # Errors
+    /// [`ReadScenarioError`] if the file could not be opened, or its preamble or header could not be read.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ReadScenarioError> {
+        check_ext(path.as_ref(), EXT)?;
+        Self::new(BufReader::new(File::open(&path)?))
+    }
+}
+
+impl<'r, S: DeserializeOwned> Reader<'r, S> {
+    /// Creates a reader over the given input stream, reading the preamble and the header. The stream
+    /// should be buffered.
+    ///
+    /// # Errors
+    /// [`ReadScenarioError`] if the preamble or header could not be read, or if the stream is not
+    /// in a supported version of the format.
+    pub fn new(mut r: impl Read + 'r) -> Result<Self, ReadScenarioError> {
+        let mut preamble = [0; 6];
+        r.read_exact(&mut preamble)?;
+        let (magic, version, flags) = (&preamble[..4], preamble[4], preamble[5]);
+        if magic != MAGIC {
+            return Err(UnsupportedFileFormatError(format!("expected magic bytes {MAGIC:02x?}, got {magic:02x?}")).into());
+        }
+        if version == 0 || version > VERSION {
+            return Err(UnsupportedFileFormatError(format!("expected format version {VERSION} or earlier, got {version}")).into());
+        }
+        if flags & !FLAG_DEFLATE != 0 {
+            return Err(UnsupportedFileFormatError(format!("unsupported flags {flags:#010b}")).into());
+        }
+
+        let r: Box<dyn Read + 'r> = if flags & FLAG_DEFLATE == 0 {
+            Box::new(r)
+        } else {
+            Box::new(BufReader::new(DeflateDecoder::new(r)))
+        };
+        let mut de = rmp_serde::Deserializer::new(r);
+        let header = Header::<S>::deserialize(&mut de).map_err(deserializer)?;
+        let len = usize::try_from(header.len).map_err(deserializer)?;
+        Ok(Self {
+            initial: header.initial,
+            seed: header.seed,
+            len,
+            remaining: len,
+            de,
+        })
+    }
+
+    /// The initial simulation state.
+    pub fn initial(&self) -> &S {
+        &self.initial
+    }
+
+    /// The random number generator seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The total number of events in the timeline, including those that have already been read.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the timeline has no events.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Decodes the events, along with the initial state and the seed, into a [`Scenario`], using the
+    /// supplied `decoder`. The events are read from the stream one at a time, by iterating over the reader.
+    ///
+    /// # Errors
+    /// [`ReadScenarioError`] if an event could not be read or decoded, or if some events have already
+    /// been read by iterating over the reader.
+    pub fn decode(mut self, decoder: &Decoder<S>) -> Result<Scenario<S>, ReadScenarioError> {
+        if self.remaining != self.len {
+            return Err(ParseEventError(
+                format!("cannot decode a timeline after {} of its {} events have been read", self.len - self.remaining, self.len).into(),
+            )
+            .into());
+        }
+
+        let mut builder = TimelineBuilder::new(decoder, self.len);
+        for event in self.by_ref() {
+            builder.push(event?)?;
+        }
+
+        Ok(Scenario {
+            initial: self.initial,
+            timeline: builder.build(),
+            seed: self.seed,
+        })
+    }
+}
+
+/// Lazily reads the events, one at a time. Iteration stops after the first error.
+impl<S> Iterator for Reader<'_, S> {
+    type Item = Result<PersistentEvent, ReadScenarioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match Record::deserialize(&mut self.de) {
+            Ok(record) => Some(Ok(PersistentEvent::from_record(record))),
+            Err(err) => {
+                self.remaining = 0;
+                Some(Err(deserializer(err)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl PersistentEvent {
+    fn from_record((name, encoded, payload, time, provenance): Record) -> Self {
+        Self {
+            name,
+            encoded,
+            payload,
+            time,
+            provenance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
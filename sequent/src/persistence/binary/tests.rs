@@ -0,0 +1,234 @@
+// $coverage:ignore-start
+
+use std::fs;
+use std::path::PathBuf;
+use flanker_temp::TempPath;
+use crate::{Decoder, Event, ParseEventError, Parser, Payload, Provenance, Scenario, Timeline};
+use crate::test_fixtures::persistence::{decoder, replay, StructuredEvent, TestEvent, TestState};
+use crate::persistence::{PersistentEvent, PersistentScenario, ReadScenarioError};
+use crate::persistence::binary::{read_from_file, write, write_to_file, write_to_file_with_compression, Compression, Header, Reader};
+
+fn persistent_scenario_fixture() -> PersistentScenario<TestState> {
+    PersistentScenario {
+        initial: TestState::new("hello", 2.5),
+        timeline: vec![
+            PersistentEvent {
+                name: "test".into(),
+                encoded: "a b".into(),
+                payload: None,
+                time: 0.0,
+                provenance: Provenance::User,
+            },
+            PersistentEvent {
+                name: "structured".into(),
+                encoded: String::default(),
+                payload: Some(
+                    Payload::encode(&StructuredEvent {
+                        label: "first".into(),
+                        weights: vec![0.5],
+                    })
+                    .unwrap(),
+                ),
+                time: 1.5,
+                provenance: Provenance::Generated(0),
+            },
+            PersistentEvent {
+                name: "test".into(),
+                encoded: String::default(),
+                payload: None,
+                time: 1.5,
+                provenance: Provenance::Loaded,
+            },
+        ],
+        seed: 7,
+    }
+}
+
+fn scenario_fixture() -> Scenario<TestState> {
+    persistent_scenario_fixture().decode(&decoder()).unwrap()
+}
+
+fn to_bytes(scenario: &Scenario<TestState>, compression: Compression) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write(scenario, &mut buf, compression).unwrap();
+    buf
+}
+
+#[test]
+fn preamble() {
+    assert_eq!(b"SEQB\x01\x00", &to_bytes(&scenario_fixture(), Compression::None)[..6]);
+    assert_eq!(b"SEQB\x01\x01", &to_bytes(&scenario_fixture(), Compression::Deflate)[..6]);
+}
+
+#[test]
+fn round_trip() {
+    for compression in [Compression::None, Compression::Deflate] {
+        let bytes = to_bytes(&scenario_fixture(), compression);
+        let decoded = Reader::new(&bytes[..]).unwrap().decode(&decoder()).unwrap();
+        assert_eq!(persistent_scenario_fixture(), PersistentScenario::from(&decoded), "for {compression:?}");
+
+        // the decoded events replay as the originals
+        assert_eq!(TestState::new("hello a b first", 3.0), replay(decoded), "for {compression:?}");
+    }
+}
+
+#[test]
+fn compression_shrinks_repetitive_timeline() {
+    let scenario = Scenario {
+        initial: scenario_fixture().initial,
+        timeline: Timeline::from(
+            (0..1_000)
+                .map(|_| Box::new(TestEvent(vec!["repetitive".into()])) as Box<dyn Event<State = _>>)
+                .collect::<Vec<_>>(),
+        ),
+        seed: 0,
+    };
+    let uncompressed = to_bytes(&scenario, Compression::None);
+    let compressed = to_bytes(&scenario, Compression::Deflate);
+    assert!(compressed.len() * 10 < uncompressed.len(), "{} vs {}", compressed.len(), uncompressed.len());
+}
+
+#[test]
+fn read_lazily() {
+    let bytes = to_bytes(&scenario_fixture(), Compression::Deflate);
+    let mut reader = Reader::<TestState>::new(&bytes[..]).unwrap();
+    assert_eq!(persistent_scenario_fixture().initial, *reader.initial());
+    assert_eq!(7, reader.seed());
+    assert_eq!(3, reader.len());
+    assert!(!reader.is_empty());
+    assert_eq!((3, Some(3)), reader.size_hint());
+
+    let first = reader.next().unwrap().unwrap();
+    assert_eq!(persistent_scenario_fixture().timeline[0], first);
+    assert_eq!("a b", first.decode(&decoder()).unwrap().to_string());
+    assert_eq!((2, Some(2)), reader.size_hint());
+
+    let rest = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(persistent_scenario_fixture().timeline[1..], rest);
+}
+
+#[test]
+fn read_empty_timeline() {
+    let scenario = Scenario {
+        initial: scenario_fixture().initial,
+        timeline: Timeline::default(),
+        seed: 0,
+    };
+    let bytes = to_bytes(&scenario, Compression::None);
+    let mut reader = Reader::<TestState>::new(&bytes[..]).unwrap();
+    assert!(reader.is_empty());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn read_bad_magic() {
+    let mut bytes = to_bytes(&scenario_fixture(), Compression::None);
+    bytes[0] = b'X';
+    assert_eq!(
+        "unsupported file format: expected magic bytes [53, 45, 51, 42], got [58, 45, 51, 42]",
+        Reader::<TestState>::new(&bytes[..]).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn read_later_version() {
+    let mut bytes = to_bytes(&scenario_fixture(), Compression::None);
+    bytes[4] = 2;
+    assert_eq!(
+        "unsupported file format: expected format version 1 or earlier, got 2",
+        Reader::<TestState>::new(&bytes[..]).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn read_unsupported_flags() {
+    let mut bytes = to_bytes(&scenario_fixture(), Compression::None);
+    bytes[5] = 0b10;
+    assert_eq!(
+        "unsupported file format: unsupported flags 0b00000010",
+        Reader::<TestState>::new(&bytes[..]).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn read_truncated() {
+    let bytes = to_bytes(&scenario_fixture(), Compression::None);
+    assert!(Reader::<TestState>::new(&bytes[..3]).unwrap_err().io().is_some());
+
+    let mut reader = Reader::<TestState>::new(&bytes[..bytes.len() - 2]).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().unwrap_err().deserializer().is_some());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn read_corrupt_length() {
+    let mut bytes = b"SEQB\x01\x00".to_vec();
+    let header = Header {
+        initial: scenario_fixture().initial,
+        seed: 0,
+        len: u64::MAX,
+    };
+    rmp_serde::encode::write_named(&mut bytes, &header).unwrap();
+
+    // the length is not trusted for reserving space, so decoding fails only once the events run out
+    let reader = Reader::<TestState>::new(&bytes[..]).unwrap();
+    assert!(reader.decode(&decoder()).unwrap_err().deserializer().is_some());
+}
+
+#[test]
+fn decode_after_partial_read() {
+    let bytes = to_bytes(&scenario_fixture(), Compression::None);
+    let mut reader = Reader::new(&bytes[..]).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!(
+        Some(ParseEventError("cannot decode a timeline after 1 of its 3 events have been read".into())),
+        reader.decode(&decoder()).unwrap_err().parse_event()
+    );
+}
+
+#[test]
+fn decode_unknown_event() {
+    let bytes = to_bytes(&scenario_fixture(), Compression::None);
+    let decoder = Decoder::new(vec![Box::new(Parser::<TestEvent>::default())]);
+    assert_eq!(
+        Some(ParseEventError("no event parser for 'structured'".into())),
+        Reader::new(&bytes[..]).unwrap().decode(&decoder).unwrap_err().parse_event()
+    );
+}
+
+#[test]
+fn write_then_read() {
+    let scenario = scenario_fixture();
+    for compression in [Compression::None, Compression::Deflate] {
+        let temp = TempPath::with_extension("seqb");
+        write_to_file_with_compression(&scenario, &temp, compression).unwrap();
+        assert_eq!(to_bytes(&scenario, compression), fs::read(&temp).unwrap());
+
+        let decoded = read_from_file(&decoder(), &temp).unwrap();
+        assert_eq!(persistent_scenario_fixture(), PersistentScenario::from(&decoded));
+        assert_eq!(TestState::new("hello a b first", 3.0), replay(decoded));
+    }
+}
+
+#[test]
+fn write_to_file_invalid_format() {
+    assert!(matches!(
+        write_to_file(&scenario_fixture(), PathBuf::from("data.yaml")).unwrap_err().unsupported_file_format(),
+        Some(err) if err.to_string() == "expected file extension 'seqb', got 'yaml'"
+    ));
+}
+
+#[test]
+fn read_from_file_invalid_format() {
+    assert!(matches!(
+        read_from_file(&decoder(), PathBuf::from("data.json")),
+        Err(ReadScenarioError::UnsupportedFileFormat(err)) if err.to_string() == "expected file extension 'seqb', got 'json'"
+    ));
+}
+
+#[test]
+fn read_from_file_nonexistent_file() {
+    assert!(read_from_file(&decoder(), PathBuf::from("nonexistent.seqb")).unwrap_err().io().is_some());
+}
@@ -0,0 +1,132 @@
+//! Persistence extensions for working with JSON files.
+
+use crate::persistence::{
+    check_ext, read, IntoInner, PersistentScenario, ReadScenarioError, WriteScenarioError,
+};
+use crate::{Decoder, Scenario};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+const EXT: &str = "json";
+
+/// The layout of the JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// Indented over multiple lines, for readability.
+    #[default]
+    Pretty,
+
+    /// On a single line, without insignificant whitespace.
+    Compact,
+}
+
+/// A container for de/serializing arbitrary types from/into JSON.
+pub struct Carrier<T>(T, Style);
+
+impl<T> Carrier<T> {
+    /// Sets the layout of the serialized form. The default is [`Style::Pretty`].
+    #[must_use]
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.1 = style;
+        self
+    }
+}
+
+impl<T: PartialEq> PartialEq for Carrier<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Debug> Debug for Carrier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Carrier({:?})", self.0)
+    }
+}
+
+impl<T> From<T> for Carrier<T> {
+    fn from(value: T) -> Self {
+        Self(value, Style::default())
+    }
+}
+
+/// Serializes the content of a [`Carrier`] to its JSON representation.
+impl<T: Serialize> Display for Carrier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let json = match self.1 {
+            Style::Pretty => serde_json::to_string_pretty(&self.0),
+            Style::Compact => serde_json::to_string(&self.0),
+        };
+        f.write_str(&json.unwrap())
+    }
+}
+
+/// Populates the contents of a new [`Carrier`] from a JSON string.
+impl<T> FromStr for Carrier<T>
+where
+    for<'a> T: Deserialize<'a>,
+{
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: T = serde_json::from_str(s)?;
+        Ok(Self::from(value))
+    }
+}
+
+impl<T> IntoInner<T> for Carrier<T> {
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Reads and decodes a scenario from a given JSON file.
+///
+/// # Errors
+/// [`ReadScenarioError`] if the scenario could not be read.
+pub fn read_from_file<S>(
+    decoder: &Decoder<S>,
+    path: impl AsRef<Path>,
+) -> Result<Scenario<S>, ReadScenarioError>
+where
+    for<'de> S: Deserialize<'de>,
+{
+    check_ext(path.as_ref(), EXT)?;
+    let mut r = BufReader::new(File::open(&path)?);
+    read::<Carrier<PersistentScenario<S>>, _, _>(decoder, &mut r)
+}
+
+/// Writes a scenario to a JSON file, using the [`Style::Pretty`] layout.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write_to_file<S: Clone + Serialize>(
+    scenario: &Scenario<S>,
+    path: impl AsRef<Path>,
+) -> Result<(), WriteScenarioError> {
+    write_to_file_with_style(scenario, path, Style::Pretty)
+}
+
+/// Writes a scenario to a JSON file, using the given layout.
+///
+/// # Errors
+/// [`WriteScenarioError`] if the scenario could not be written.
+pub fn write_to_file_with_style<S: Clone + Serialize>(
+    scenario: &Scenario<S>,
+    path: impl AsRef<Path>,
+    style: Style,
+) -> Result<(), WriteScenarioError> {
+    check_ext(path.as_ref(), EXT)?;
+    let mut w = BufWriter::new(File::create(&path)?);
+    let carrier = Carrier::from(PersistentScenario::from(scenario)).with_style(style);
+    w.write_all(carrier.to_string().as_bytes())?;
+    w.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,177 @@
+// $coverage:ignore-start
+
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use flanker_assert_str::assert_loopback;
+use flanker_temp::TempPath;
+use crate::{Event, Payload, Provenance, Scenario, Timeline};
+use crate::test_fixtures::persistence::{decoder, replay, StructuredEvent, TestEvent, TestState};
+use crate::persistence::{IntoInner, PersistentEvent, PersistentScenario};
+use crate::persistence::json::{Carrier, read_from_file, write_to_file, write_to_file_with_style, Style};
+
+fn persistent_scenario_fixture() -> PersistentScenario<TestState> {
+    PersistentScenario {
+        initial: TestState::new("hello", 2.5),
+        timeline: vec![
+            PersistentEvent {
+                name: "test".into(),
+                encoded: "a b".into(),
+                payload: None,
+                time: 0.0,
+                provenance: Provenance::User,
+            },
+            PersistentEvent {
+                name: "structured".into(),
+                encoded: String::default(),
+                payload: Some(
+                    Payload::encode(&StructuredEvent {
+                        label: "first".into(),
+                        weights: vec![0.5],
+                    })
+                    .unwrap(),
+                ),
+                time: 1.5,
+                provenance: Provenance::Generated(0),
+            },
+        ],
+        seed: 7,
+    }
+}
+
+#[test]
+fn scenario_round_trip() {
+    let decoded = persistent_scenario_fixture().decode(&decoder()).unwrap();
+    assert_eq!(&[0.0, 1.5], decoded.timeline.times());
+    assert_eq!("a b", decoded.timeline[0].to_string());
+    assert_eq!(r#"{"label":"first","weights":[0.5]}"#, decoded.timeline[1].to_string());
+
+    let ps = PersistentScenario::from(&decoded);
+    assert_eq!(ps, persistent_scenario_fixture());
+
+    // the decoded events replay as the originals
+    assert_eq!(TestState::new("hello a b first", 3.0), replay(decoded));
+}
+
+#[test]
+fn to_string_pretty() {
+    assert_eq!(
+        r#"{
+  "initial": {
+    "some_string": "hello",
+    "some_f64": 2.5
+  },
+  "timeline": [
+    {
+      "name": "test",
+      "encoded": "a b",
+      "provenance": "user"
+    },
+    {
+      "name": "structured",
+      "payload": {
+        "label": "first",
+        "weights": [
+          0.5
+        ]
+      },
+      "time": 1.5,
+      "provenance": {
+        "generated": 0
+      }
+    }
+  ],
+  "seed": 7
+}"#,
+        Carrier::from(persistent_scenario_fixture()).to_string()
+    );
+}
+
+#[test]
+fn to_string_compact() {
+    assert_eq!(
+        r#"{"initial":{"some_string":"hello","some_f64":2.5},"timeline":[{"name":"test","encoded":"a b","provenance":"user"},{"name":"structured","payload":{"label":"first","weights":[0.5]},"time":1.5,"provenance":{"generated":0}}],"seed":7}"#,
+        Carrier::from(persistent_scenario_fixture()).with_style(Style::Compact).to_string()
+    );
+}
+
+#[test]
+fn str_loopback() {
+    assert_loopback(&Carrier::from(persistent_scenario_fixture()));
+}
+
+#[test]
+fn from_str_compact() {
+    let compact = Carrier::from(persistent_scenario_fixture()).with_style(Style::Compact).to_string();
+    assert_eq!(persistent_scenario_fixture(), Carrier::from_str(&compact).unwrap().into_inner());
+}
+
+#[test]
+fn carrier_implements_debug() {
+    let s = format!("{:?}", Carrier::from(persistent_scenario_fixture()));
+    assert!(s.contains("Carrier"))
+}
+
+#[test]
+#[should_panic(expected = "UnsupportedFileFormat(UnsupportedFileFormatError(\"expected file extension 'json', got 'yaml'\"))")]
+fn read_from_file_invalid_format() {
+    read_from_file(&decoder(), PathBuf::from("data.yaml")).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Io(Os { code: 2, kind: NotFound, message: \"No such file or directory\" })")]
+fn read_from_file_nonexistent_file() {
+    read_from_file(&decoder(), PathBuf::from("nonexistent.json")).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Deserializer(Error(\"expected ident\", line: 1, column: 2))")]
+fn read_from_file_invalid_content() {
+    let temp = TempPath::with_extension("json");
+    fs::write(&temp, "not a valid scenario").unwrap();
+    read_from_file(&decoder(), &temp).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "UnsupportedFileFormat(UnsupportedFileFormatError(\"expected file extension 'json', got 'yaml'\"))")]
+fn write_to_file_invalid_format() {
+    let decoded = persistent_scenario_fixture().decode(&decoder()).unwrap();
+    write_to_file(&decoded, PathBuf::from("data.yaml")).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Io(Os { code: 2, kind: NotFound, message: \"No such file or directory\" })")]
+fn write_to_file_nonexistent_directory() {
+    let decoded = persistent_scenario_fixture().decode(&decoder()).unwrap();
+    write_to_file(&decoded, PathBuf::from("nonexistent_dir/data.json")).unwrap();
+}
+
+#[test]
+fn write_then_read() {
+    let decoded = persistent_scenario_fixture().decode(&decoder()).unwrap();
+    for style in [Style::Pretty, Style::Compact] {
+        let temp = TempPath::with_extension("json");
+        write_to_file_with_style(&decoded, &temp, style).unwrap();
+        assert_eq!(
+            Carrier::from(persistent_scenario_fixture()).with_style(style).to_string(),
+            fs::read_to_string(&temp).unwrap()
+        );
+
+        let original = read_from_file(&decoder(), &temp).unwrap();
+        assert_eq!(persistent_scenario_fixture(), PersistentScenario::from(&original));
+        assert_eq!(TestState::new("hello a b first", 3.0), replay(original));
+    }
+}
+
+#[test]
+fn scenario_from_timeline() {
+    let scenario = Scenario {
+        initial: TestState::new("hello", 2.5),
+        timeline: Timeline::from(vec![Box::new(TestEvent(vec![])) as Box<dyn Event<State = _>>]),
+        seed: 0,
+    };
+    assert_eq!(
+        r#"{"initial":{"some_string":"hello","some_f64":2.5},"timeline":[{"name":"test","provenance":"user"}]}"#,
+        Carrier::from(PersistentScenario::from(&scenario)).with_style(Style::Compact).to_string()
+    );
+}
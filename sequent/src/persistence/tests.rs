@@ -4,8 +4,10 @@ use std::error::Error;
 use std::io;
 use std::io::ErrorKind;
 use std::path::PathBuf;
-use crate::ParseEventError;
-use crate::persistence::{check_ext, ReadScenarioError, UnsupportedFileFormatError, WriteScenarioError};
+use flanker_temp::TempPath;
+use crate::{Decoder, ParseEventError, Parser};
+use crate::persistence::{check_ext, read_from_file, write_to_file, Format, PersistentScenario, ReadScenarioError, UnsupportedFileFormatError, WriteScenarioError};
+use crate::test_fixtures::{scenario, Append};
 
 #[test]
 fn check_ext_passes() {
@@ -31,10 +33,16 @@ fn write_scenario_error_unsupported_file_format() -> WriteScenarioError {
     UnsupportedFileFormatError("data".into()).into()
 }
 
+fn write_scenario_error_serializer() -> WriteScenarioError {
+    let err: Box<dyn Error> = "data".into();
+    err.into()
+}
+
 #[test]
 fn write_scenario_error_implements_display() {
     assert_eq!("io: broken pipe", write_scenario_error_io().to_string());
     assert_eq!("unsupported file format: data", write_scenario_error_unsupported_file_format().to_string());
+    assert_eq!("serializer: data", write_scenario_error_serializer().to_string());
 }
 
 #[test]
@@ -44,6 +52,10 @@ fn write_scenario_error_variants() {
 
     assert!(write_scenario_error_unsupported_file_format().unsupported_file_format().is_some());
     assert!(write_scenario_error_unsupported_file_format().io().is_none());
+    assert!(write_scenario_error_unsupported_file_format().serializer().is_none());
+
+    assert!(write_scenario_error_serializer().serializer().is_some());
+    assert!(write_scenario_error_serializer().io().is_none());
 }
 
 fn read_scenario_error_io() -> ReadScenarioError {
@@ -84,4 +96,40 @@ fn read_scenario_error_variants() {
 
     assert!(read_scenario_error_deserializer().deserializer().is_some());
     assert!(read_scenario_error_deserializer().io().is_none());
-}
\ No newline at end of file
+}
+
+#[test]
+fn format_from_path() {
+    assert_eq!(Ok(Format::Yaml), Format::from_path(&PathBuf::from("data.yaml")));
+    assert_eq!(Ok(Format::Json), Format::from_path(&PathBuf::from("data.json")));
+    assert_eq!(Ok(Format::Binary), Format::from_path(&PathBuf::from("data.seqb")));
+    assert_eq!(
+        Err(UnsupportedFileFormatError("expected file extension 'yaml', 'json' or 'seqb', got 'txt'".into())),
+        Format::from_path(&PathBuf::from("data.txt"))
+    );
+    assert_eq!(
+        Err(UnsupportedFileFormatError("expected file extension 'yaml', 'json' or 'seqb', got ''".into())),
+        Format::from_path(&PathBuf::from("data"))
+    );
+}
+
+#[test]
+fn write_then_read_by_extension() {
+    let original = scenario(&[0, 1, 2]);
+    let decoder = Decoder::new(vec![Box::new(Parser::<Append>::default())]);
+    for ext in ["yaml", "json", "seqb"] {
+        let temp = TempPath::with_extension(ext);
+        write_to_file(&original, &temp).unwrap();
+        let decoded = read_from_file(&decoder, &temp).unwrap();
+        assert_eq!(PersistentScenario::from(&original), PersistentScenario::from(&decoded), "for {ext}");
+    }
+}
+
+#[test]
+fn write_then_read_unsupported_extension() {
+    let temp = TempPath::with_extension("txt");
+    assert!(write_to_file(&scenario(&[0]), &temp).unwrap_err().unsupported_file_format().is_some());
+
+    let decoder = Decoder::new(vec![Box::new(Parser::<Append>::default())]);
+    assert!(read_from_file(&decoder, &temp).unwrap_err().unsupported_file_format().is_some());
+}
@@ -5,70 +5,32 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use flanker_assert_str::assert_loopback;
 use flanker_temp::TempPath;
-use crate::{Decoder, Event, ParseEventError, Parser, Queue, Scenario, StaticNamed, TransitionError};
-use serde::{Deserialize, Serialize};
-use crate::persistence::{PersistentEvent, PersistentScenario};
+use crate::{Decoder, Event, ParseEventError, Parser, Provenance, Scenario, Timeline};
+use crate::test_fixtures::persistence::{decoder, replay, StructuredEvent, TestEvent, TestState};
+use crate::persistence::{IntoInner, PersistentEvent, PersistentScenario};
 use crate::persistence::yaml::{Carrier, read_from_file, write_to_file};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-struct TestState {
-    some_string: String,
-    some_f64: f64,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct TestEvent(Vec<String>);
-
-impl StaticNamed for TestEvent {
-    fn name() -> &'static str {
-        "test"
-    }
-}
-
-impl ToString for TestEvent {
-    fn to_string(&self) -> String {
-        self.0.join(" ")
-    }
-}
-
-impl Event for TestEvent {
-    type State = TestState;
-
-    fn apply(&self, _: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
-        unimplemented!()
-    }
-}
-
-impl FromStr for TestEvent {
-    type Err = ParseEventError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.split_whitespace().map(ToString::to_string).collect()))
-    }
-}
-
 fn persistent_scenario_fixture() -> PersistentScenario<TestState> {
     PersistentScenario {
-        initial: TestState {
-            some_string: "hello".to_string(),
-            some_f64: 3.14,
-        },
+        initial: TestState::new("hello", 3.14),
         timeline: vec![PersistentEvent {
             name: "test".into(),
             encoded: "a b c".into(),
+            payload: None,
+            time: 0.0,
+            provenance: Provenance::Loaded,
         }],
+        seed: 0,
     }
 }
 
 fn scenario_fixture() -> Scenario<TestState> {
     Scenario {
-        initial: TestState {
-            some_string: "hello".to_string(),
-            some_f64: 3.14,
-        },
-        timeline: vec![
-            Box::new(TestEvent(vec!["a".into(), "b".into(), "c".into()]))
-        ]
+        initial: TestState::new("hello", 3.14),
+        timeline: Timeline::from(vec![
+            Box::new(TestEvent(vec!["a".into(), "b".into(), "c".into()])) as Box<dyn Event<State = _>>
+        ]),
+        seed: 0,
     }
 }
 
@@ -82,6 +44,12 @@ fn scenario_round_trip() {
 
     let ps = PersistentScenario::from(&decoded);
     assert_eq!(ps, persistent_scenario_fixture());
+
+    // a cloned scenario encodes identically to the original
+    assert_eq!(ps, PersistentScenario::from(&decoded.clone()));
+
+    // the decoded events replay as the originals
+    assert_eq!(TestState::new("hello a b c", 3.14), replay(decoded));
 }
 
 #[test]
@@ -180,4 +148,184 @@ fn write_then_read() {
     let original = read_from_file(&decoder, &temp).unwrap();
     let ps = persistent_scenario_fixture();
     assert_eq!(ps, PersistentScenario::from(&original));
-}
\ No newline at end of file
+}
+
+fn timed_persistent_scenario_fixture() -> PersistentScenario<TestState> {
+    PersistentScenario {
+        initial: TestState::new("hello", 2.5),
+        timeline: vec![
+            PersistentEvent {
+                name: "test".into(),
+                encoded: "a".into(),
+                payload: None,
+                time: 0.0,
+                provenance: Provenance::User,
+            },
+            PersistentEvent {
+                name: "test".into(),
+                encoded: "b".into(),
+                payload: None,
+                time: 1.5,
+                provenance: Provenance::Generated(0),
+            },
+        ],
+        seed: 7,
+    }
+}
+
+#[test]
+fn timed_to_string() {
+    assert_eq!(
+        "\
+initial:
+  some_string: hello
+  some_f64: 2.5
+timeline:
+- name: test
+  encoded: a
+  provenance: user
+- name: test
+  encoded: b
+  time: 1.5
+  provenance: !generated 0
+seed: 7
+",
+        Carrier::from(timed_persistent_scenario_fixture()).to_string()
+    );
+}
+
+#[test]
+fn timed_scenario_round_trip() {
+    let decoder = Decoder::new(vec![Box::new(Parser::<TestEvent>::default())]);
+    let decoded = timed_persistent_scenario_fixture().decode(&decoder).unwrap();
+    assert_eq!(&[0.0, 1.5], decoded.timeline.times());
+    assert_eq!(vec![Provenance::User, Provenance::Generated(0)], decoded.timeline.provenance());
+    assert_eq!(7, decoded.seed);
+
+    let ps = PersistentScenario::from(&decoded);
+    assert_eq!(ps, timed_persistent_scenario_fixture());
+}
+
+#[test]
+fn decode_out_of_order() {
+    let mut ps = timed_persistent_scenario_fixture();
+    ps.timeline[0].time = 2.0;
+    let decoder = Decoder::new(vec![Box::new(Parser::<TestEvent>::default())]);
+    assert_eq!(
+        ParseEventError("event at index 1 is scheduled at 1.5, before its predecessor at 2".into()),
+        ps.decode(&decoder).unwrap_err()
+    );
+}
+
+#[test]
+fn decode_generated_by_nonexistent_event() {
+    let mut ps = timed_persistent_scenario_fixture();
+    ps.timeline[1].provenance = Provenance::Generated(2);
+    let decoder = Decoder::new(vec![Box::new(Parser::<TestEvent>::default())]);
+    assert_eq!(
+        ParseEventError("event at index 1 cannot have been generated by an event at index 2".into()),
+        ps.decode(&decoder).unwrap_err()
+    );
+}
+
+fn mixed_scenario_fixture() -> Scenario<TestState> {
+    Scenario {
+        initial: TestState::new("hello", 2.5),
+        timeline: Timeline::from(vec![
+            Box::new(StructuredEvent {
+                label: "first: with a colon".into(),
+                weights: vec![0.5, 1.0],
+            }) as Box<dyn Event<State = _>>,
+            Box::new(TestEvent(vec!["a".into(), "b".into()])),
+        ]),
+        seed: 0,
+    }
+}
+
+#[test]
+fn payload_to_string() {
+    assert_eq!(
+        "\
+initial:
+  some_string: hello
+  some_f64: 2.5
+timeline:
+- name: structured
+  payload:
+    label: 'first: with a colon'
+    weights:
+    - 0.5
+    - 1.0
+  provenance: user
+- name: test
+  encoded: a b
+  provenance: user
+",
+        Carrier::from(PersistentScenario::from(&mixed_scenario_fixture())).to_string()
+    );
+}
+
+#[test]
+fn payload_write_then_read() {
+    let temp = TempPath::with_extension("yaml");
+    write_to_file(&mixed_scenario_fixture(), &temp).unwrap();
+
+    let decoded = read_from_file(&decoder(), &temp).unwrap();
+    assert_eq!(
+        vec![
+            ("structured".to_string(), r#"{"label":"first: with a colon","weights":[0.5,1.0]}"#.to_string()),
+            ("test".into(), "a b".into()),
+        ],
+        decoded
+            .timeline
+            .iter()
+            .map(|event| (event.name().to_string(), event.to_string()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(PersistentScenario::from(&mixed_scenario_fixture()), PersistentScenario::from(&decoded));
+    assert_eq!(TestState::new("hello first: with a colon a b", 4.0), replay(decoded));
+}
+
+#[test]
+fn payload_with_string_parser() {
+    let mut ps = PersistentScenario::from(&mixed_scenario_fixture());
+    ps.timeline[0].name = "test".into();
+    assert_eq!(
+        ParseEventError("event parser for 'test' does not support structured payloads".into()),
+        ps.decode(&decoder()).unwrap_err()
+    );
+}
+
+#[test]
+fn payload_mismatched_structure() {
+    let yaml = "\
+initial:
+  some_string: hello
+  some_f64: 2.5
+timeline:
+- name: structured
+  payload:
+    label: first
+    weights: heavy
+";
+    let ps = Carrier::<PersistentScenario<TestState>>::from_str(yaml).unwrap().into_inner();
+    assert_eq!(
+        ParseEventError("invalid payload: invalid type: string \"heavy\", expected a sequence".into()),
+        ps.decode(&decoder()).unwrap_err()
+    );
+}
+
+#[test]
+fn encoded_may_be_omitted() {
+    let yaml = "\
+initial:
+  some_string: hello
+  some_f64: 2.5
+timeline:
+- name: test
+";
+    let ps = Carrier::<PersistentScenario<TestState>>::from_str(yaml).unwrap().into_inner();
+    assert_eq!("", ps.timeline[0].encoded);
+    let decoded = ps.decode(&decoder()).unwrap();
+    assert_eq!("", decoded.timeline[0].to_string());
+}
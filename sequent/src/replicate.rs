@@ -0,0 +1,295 @@
+//! Monte Carlo replication of stochastic scenarios.
+
+use crate::{parallel, Scenario, Simulation};
+use std::fmt::{Debug, Formatter};
+use thiserror::Error;
+
+/// A named outcome measure, evaluated over the final state of each replication.
+pub struct Metric<S> {
+    name: String,
+    measure: Box<dyn Fn(&S) -> f64 + Sync>,
+}
+
+impl<S> Metric<S> {
+    /// Creates a metric with the given name and measurement function.
+    pub fn new(name: impl Into<String>, measure: impl Fn(&S) -> f64 + Sync + 'static) -> Self {
+        Self {
+            name: name.into(),
+            measure: Box::new(measure),
+        }
+    }
+
+    /// The name of the metric.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Evaluates the metric over the given state.
+    pub fn measure(&self, state: &S) -> f64 {
+        (self.measure)(state)
+    }
+}
+
+impl<S> Debug for Metric<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metric").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+/// The seeds assigned to the replications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Seeds {
+    /// Seeds are derived from a single base seed; the same base seed always yields the same
+    /// sequence of replication seeds.
+    Base(u64),
+
+    /// Seeds are given explicitly, one per replication.
+    List(Vec<u64>),
+}
+
+impl Seeds {
+    fn resolve(&self, n: usize) -> Vec<u64> {
+        match self {
+            Seeds::Base(base) => {
+                let mut rng = crate::Rng::new(*base);
+                (0..n).map(|_| rng.next_u64()).collect()
+            }
+            Seeds::List(seeds) => seeds.clone(),
+        }
+    }
+}
+
+/// Runs `n` independent replications of a stochastic scenario in parallel, measuring the
+/// final state of each with the given metrics.
+///
+/// Each replication runs on a worker thread, using a scenario obtained from `scenario_factory`, with the
+/// seed replaced by that of the replication. The state type need not be [`Send`], as the scenario is
+/// created, run and measured on the same thread. The number of workers is bounded by the available
+/// parallelism.
+///
+/// # Errors
+/// [`ReplicationError`] if a replication could not be run to completion, in which case the remaining
+/// replications are abandoned. If more than one replication failed, the error for the earliest is returned.
+///
+/// # Panics
+/// If the seeds are given as a list that does not contain exactly `n` seeds.
+pub fn replicate<S: Clone>(
+    scenario_factory: impl Fn() -> Scenario<S> + Sync,
+    n: usize,
+    seeds: &Seeds,
+    metrics: &[Metric<S>],
+) -> Result<Replication, ReplicationError> {
+    let seeds = seeds.resolve(n);
+    assert_eq!(n, seeds.len(), "expected {n} seeds, got {}", seeds.len());
+
+    let outcomes = parallel::map(seeds.clone(), |replication, seed| {
+        let mut scenario = scenario_factory();
+        scenario.seed = seed;
+        let mut sim = Simulation::from(scenario);
+        sim.run().map_err(|err| ReplicationError {
+            replication,
+            seed,
+            message: err.to_string(),
+        })?;
+        Ok(metrics
+            .iter()
+            .map(|metric| metric.measure(sim.current_state()))
+            .collect::<Vec<_>>())
+    })?;
+    Ok(Replication {
+        metrics: metrics.iter().map(|metric| metric.name.clone()).collect(),
+        seeds,
+        outcomes,
+    })
+}
+
+/// Produced by [`replicate()`] if a replication could not be run to completion.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("replication {replication} (seed {seed}) failed: {message}")]
+pub struct ReplicationError {
+    /// The index of the failed replication.
+    pub replication: usize,
+
+    /// The seed of the failed replication.
+    pub seed: u64,
+
+    /// The description of the underlying [`crate::SimulationError`].
+    pub message: String,
+}
+
+/// The outcomes of [`replicate()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replication {
+    metrics: Vec<String>,
+    seeds: Vec<u64>,
+    outcomes: Vec<Vec<f64>>,
+}
+
+impl Replication {
+    /// The names of the metrics, in the order in which they were given.
+    pub fn metrics(&self) -> &[String] {
+        &self.metrics
+    }
+
+    /// The seeds of the replications.
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// The measured outcomes, indexed by replication, then by metric.
+    pub fn outcomes(&self) -> &[Vec<f64>] {
+        &self.outcomes
+    }
+
+    /// The outcomes of the named metric across all replications, or `None` if there is no such metric.
+    pub fn samples(&self, metric: &str) -> Option<Vec<f64>> {
+        let index = self.metrics.iter().position(|name| name == metric)?;
+        Some(self.outcomes.iter().map(|outcome| outcome[index]).collect())
+    }
+
+    /// Summary statistics for the named metric, or `None` if there is no such metric.
+    pub fn summary(&self, metric: &str) -> Option<Summary> {
+        self.samples(metric).map(Summary::from)
+    }
+}
+
+/// Summary statistics over a sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    sorted: Vec<f64>,
+    mean: f64,
+    variance: f64,
+}
+
+impl From<Vec<f64>> for Summary {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            sorted: samples,
+            mean,
+            variance,
+        }
+    }
+}
+
+impl Summary {
+    /// The number of samples.
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// The sample mean. Not a number if there are no samples.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// The (unbiased) sample variance. Zero if there are fewer than two samples.
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// The sample standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// The smallest sample, or `None` if there are no samples.
+    pub fn min(&self) -> Option<f64> {
+        self.sorted.first().copied()
+    }
+
+    /// The largest sample, or `None` if there are no samples.
+    pub fn max(&self) -> Option<f64> {
+        self.sorted.last().copied()
+    }
+
+    /// The `p`-th percentile, linearly interpolating between the closest ranks, or `None` if there
+    /// are no samples.
+    ///
+    /// # Panics
+    /// If `p` is outside the interval [0, 100].
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        assert!((0.0..=100.0).contains(&p), "percentile ({p}) must be in [0, 100]");
+        if self.sorted.is_empty() {
+            return None;
+        }
+        let rank = p / 100.0 * (self.sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let fraction = rank - lower as f64;
+        Some(self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * fraction)
+    }
+
+    /// The confidence interval for the mean at the given confidence level (e.g., 0.95), based on
+    /// the normal approximation to the sampling distribution of the mean.
+    ///
+    /// # Panics
+    /// If the level is outside the open interval (0, 1).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        assert!(level > 0.0 && level < 1.0, "confidence level ({level}) must be in (0, 1)");
+        let z = inverse_normal_cdf(0.5 + level / 2.0);
+        let half_width = z * self.std_dev() / (self.count() as f64).sqrt();
+        (self.mean - half_width, self.mean + half_width)
+    }
+}
+
+/// The quantile function of the standard normal distribution, using Acklam's rational approximation
+/// (relative error below 1.2e-9).
+fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.024_25;
+
+    if p < LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -inverse_normal_cdf(1.0 - p)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,121 @@
+// $coverage:ignore-start
+
+use crate::replicate::inverse_normal_cdf;
+use crate::test_fixtures::{random_scenario, scenario, TestState};
+use crate::{replicate, Metric, ReplicationError, Scenario, Seeds, Summary};
+
+fn scenario_factory() -> Scenario<TestState> {
+    random_scenario(10, 100, 0)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn metrics() -> Vec<Metric<TestState>> {
+    vec![
+        Metric::new("sum", |state: &TestState| state.transitions.iter().sum::<usize>() as f64),
+        Metric::new("len", |state: &TestState| state.transitions.len() as f64),
+    ]
+}
+
+#[test]
+fn replicate_and_summarise() {
+    let replication = replicate(scenario_factory, 500, &Seeds::Base(7), &metrics()).unwrap();
+    assert_eq!(&["sum", "len"], replication.metrics());
+    assert_eq!("Metric { name: \"sum\", .. }", format!("{:?}", metrics()[0]));
+    assert_eq!(500, replication.seeds().len());
+    assert_eq!(500, replication.outcomes().len());
+
+    let len = replication.summary("len").unwrap();
+    assert_eq!(10.0, len.mean());
+    assert_eq!(0.0, len.variance());
+
+    // the sum of 10 discrete uniforms over [0, 100) has a mean of 495 and a variance of 8332.5
+    let sum = replication.summary("sum").unwrap();
+    assert_eq!(500, sum.count());
+    assert!((sum.mean() - 495.0).abs() < 15.0, "mean: {}", sum.mean());
+    assert!((sum.variance() - 8332.5).abs() < 1500.0, "variance: {}", sum.variance());
+    let (low, high) = sum.confidence_interval(0.99);
+    assert!(low < 495.0 && 495.0 < high, "interval: ({low}, {high})");
+
+    assert!(replication.summary("missing").is_none());
+}
+
+#[test]
+fn replicate_is_reproducible() {
+    let first = replicate(scenario_factory, 20, &Seeds::Base(7), &metrics()).unwrap();
+    let second = replicate(scenario_factory, 20, &Seeds::Base(7), &metrics()).unwrap();
+    assert_eq!(first, second);
+
+    let third = replicate(scenario_factory, 20, &Seeds::Base(8), &metrics()).unwrap();
+    assert_ne!(first.samples("sum"), third.samples("sum"));
+}
+
+#[test]
+fn replicate_with_seed_list() {
+    let replication = replicate(scenario_factory, 3, &Seeds::List(vec![1, 2, 1]), &metrics()).unwrap();
+    assert_eq!(&[1, 2, 1], replication.seeds());
+    let sums = replication.samples("sum").unwrap();
+    assert_eq!(sums[0], sums[2]);
+}
+
+#[test]
+#[should_panic(expected = "expected 3 seeds, got 2")]
+fn replicate_with_too_few_seeds() {
+    replicate(scenario_factory, 3, &Seeds::List(vec![1, 2]), &metrics()).unwrap();
+}
+
+#[test]
+fn replicate_failure() {
+    let err = replicate(|| scenario(&[0, 0]), 4, &Seeds::List(vec![10, 11, 12, 13]), &metrics()).unwrap_err();
+    assert_eq!(
+        ReplicationError {
+            replication: 0,
+            seed: 10,
+            message: "transition: duplicate ID 0".into()
+        },
+        err
+    );
+    assert_eq!("replication 0 (seed 10) failed: transition: duplicate ID 0", err.to_string());
+}
+
+#[test]
+fn summary() {
+    let summary = Summary::from(vec![4.0, 1.0, 3.0, 2.0, 5.0]);
+    assert_eq!(5, summary.count());
+    assert_eq!(3.0, summary.mean());
+    assert_eq!(2.5, summary.variance());
+    assert_eq!(2.5_f64.sqrt(), summary.std_dev());
+    assert_eq!(Some(1.0), summary.min());
+    assert_eq!(Some(5.0), summary.max());
+    assert_eq!(Some(1.0), summary.percentile(0.0));
+    assert_eq!(Some(3.0), summary.percentile(50.0));
+    assert_eq!(Some(4.5), summary.percentile(87.5));
+    assert_eq!(Some(5.0), summary.percentile(100.0));
+
+    let (low, high) = summary.confidence_interval(0.95);
+    let half_width = 1.959_964 * summary.std_dev() / 5.0_f64.sqrt();
+    assert!((low - (3.0 - half_width)).abs() < 1e-6);
+    assert!((high - (3.0 + half_width)).abs() < 1e-6);
+}
+
+#[test]
+fn summary_of_empty_sample() {
+    let summary = Summary::from(vec![]);
+    assert_eq!(0, summary.count());
+    assert!(summary.mean().is_nan());
+    assert!(summary.min().is_none());
+    assert!(summary.percentile(50.0).is_none());
+}
+
+#[test]
+#[should_panic(expected = "percentile (101) must be in [0, 100]")]
+fn percentile_out_of_range() {
+    Summary::from(vec![1.0]).percentile(101.0);
+}
+
+#[test]
+fn inverse_normal() {
+    assert!((inverse_normal_cdf(0.5)).abs() < 1e-9);
+    assert!((inverse_normal_cdf(0.975) - 1.959_963_985).abs() < 1e-6);
+    assert!((inverse_normal_cdf(0.01) + 2.326_347_874).abs() < 1e-6);
+    assert!((inverse_normal_cdf(0.995) - 2.575_829_304).abs() < 1e-6);
+}
@@ -0,0 +1,104 @@
+//! Deterministic pseudo-random number generation for stochastic models.
+
+use crate::Time;
+
+/// A seedable pseudo-random number generator, implementing the xoshiro256** algorithm. It is small,
+/// fast and of good statistical quality, but is not cryptographically secure.
+///
+/// During a simulation, each event is given its own [`Rng`] (see [`crate::Queue::rng()`]), derived
+/// from the scenario seed and the location of the event in the timeline. An event's random draws
+/// are therefore reproduced exactly whenever the event is replayed; e.g., after a reset, a jump or
+/// a resumption from a snapshot. As the stream is tied to the location, an event that is relocated
+/// by an edit to the timeline draws different numbers from that point on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a generator from the given seed. Generators created from the same seed produce
+    /// identical sequences.
+    pub fn new(seed: u64) -> Self {
+        let mut seed = seed;
+        let state = [
+            splitmix64(&mut seed),
+            splitmix64(&mut seed),
+            splitmix64(&mut seed),
+            splitmix64(&mut seed),
+        ];
+        Self { state }
+    }
+
+    /// Creates a generator for the event at the given timeline location, such that every location
+    /// has an independent stream for a given seed.
+    pub(crate) fn for_event(seed: u64, index: usize) -> Self {
+        let mut index = index as u64;
+        Self::new(seed ^ splitmix64(&mut index))
+    }
+
+    /// Produces the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+        result
+    }
+
+    /// Produces a number uniformly distributed in the interval [0, 1).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+    }
+
+    /// Produces an integer uniformly distributed in the interval [0, `bound`).
+    ///
+    /// # Panics
+    /// If `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound cannot be zero");
+        // rejection sampling, eliminating the bias of the modulo operation
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+
+    /// Produces a number uniformly distributed in the interval [`low`, `high`).
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Produces `true` with probability `p`.
+    pub fn bernoulli(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Produces an exponentially distributed duration with the given rate; i.e., the time between
+    /// successive arrivals of a Poisson process.
+    ///
+    /// # Panics
+    /// If the rate is not positive.
+    pub fn exponential(&mut self, rate: f64) -> Time {
+        assert!(rate > 0.0, "rate ({rate}) must be positive");
+        -(1.0 - self.next_f64()).ln() / rate
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests;
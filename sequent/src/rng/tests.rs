@@ -0,0 +1,78 @@
+// $coverage:ignore-start
+
+use crate::Rng;
+
+#[test]
+fn same_seed_same_sequence() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+
+    let mut c = Rng::new(43);
+    assert_ne!(Rng::new(42).next_u64(), c.next_u64());
+}
+
+#[test]
+fn event_streams_are_independent() {
+    assert_eq!(Rng::for_event(7, 3), Rng::for_event(7, 3));
+    assert_ne!(Rng::for_event(7, 3), Rng::for_event(7, 4));
+    assert_ne!(Rng::for_event(7, 3), Rng::for_event(8, 3));
+}
+
+#[test]
+fn next_f64_in_unit_interval() {
+    let mut rng = Rng::new(0);
+    let mut sum = 0.0;
+    for _ in 0..10_000 {
+        let value = rng.next_f64();
+        assert!((0.0..1.0).contains(&value));
+        sum += value;
+    }
+    let mean = sum / 10_000.0;
+    assert!((mean - 0.5).abs() < 0.01, "mean: {mean}");
+}
+
+#[test]
+fn below() {
+    let mut rng = Rng::new(1);
+    let mut counts = [0; 6];
+    for _ in 0..6_000 {
+        counts[usize::try_from(rng.below(6)).unwrap()] += 1;
+    }
+    for count in counts {
+        assert!((900..1100).contains(&count), "counts: {counts:?}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "bound cannot be zero")]
+fn below_zero_panics() {
+    Rng::new(0).below(0);
+}
+
+#[test]
+fn uniform_and_bernoulli() {
+    let mut rng = Rng::new(2);
+    for _ in 0..1_000 {
+        let value = rng.uniform(-2.0, 3.0);
+        assert!((-2.0..3.0).contains(&value));
+    }
+    assert!(!rng.bernoulli(0.0));
+    assert!(rng.bernoulli(1.0));
+}
+
+#[test]
+fn exponential() {
+    let mut rng = Rng::new(3);
+    let samples = 10_000;
+    let mean = (0..samples).map(|_| rng.exponential(4.0)).sum::<f64>() / f64::from(samples);
+    assert!((mean - 0.25).abs() < 0.01, "mean: {mean}");
+}
+
+#[test]
+#[should_panic(expected = "rate (0) must be positive")]
+fn exponential_zero_rate_panics() {
+    Rng::new(0).exponential(0.0);
+}
@@ -1,18 +1,103 @@
 //! Contains the bulk of the simulation logic.
 
+use crate::graph::WriteGraphError;
 use crate::persistence::{ReadScenarioError, WriteScenarioError};
-use crate::{Event, Queue, Scenario, TransitionError};
+use crate::{ApplyContext, Event, Queue, Rng, Scenario, Services, Time, TransitionError};
 use thiserror::Error;
-use crate::event::process_insertions;
+use limits::Guard;
+use std::collections::BTreeMap;
+use std::mem;
+use std::time::Duration;
+
+mod branch;
+mod breakpoint;
+mod edit;
+mod insertion;
+mod limits;
+mod observer;
+mod run;
+
+pub use branch::*;
+pub use breakpoint::*;
+pub use edit::*;
+pub use insertion::*;
+pub use limits::*;
+pub use observer::*;
+pub use run::*;
 
 /// The overarching simulation state. Contains the scenario being modelled, the current state
 /// of the simulation, as well as a cursor pointing to the next event in the timeline that is scheduled
 /// to be applied.
+///
+/// The simulation may also retain snapshots of prior states, as governed by its [`SnapshotPolicy`].
+/// Snapshots are used to accelerate backward jumps, which would otherwise require replaying
+/// the timeline from the initial state.
+///
+/// Events draw random numbers from a generator that is derived from the scenario seed and the
+/// location of the event (see [`Rng`]). Replaying the timeline, whether from the initial state or
+/// from a snapshot, therefore reproduces the original run exactly. Editing the timeline, however,
+/// shifts the locations of the events after the edit (e.g., by inserting, removing or moving an event),
+/// which gives each of those events a different random stream.
 #[derive(Debug)]
 pub struct Simulation<S> {
     scenario: Scenario<S>,
     current_state: S,
     cursor: usize,
+    snapshot_policy: SnapshotPolicy,
+    snapshot_interval: usize,
+    snapshots: BTreeMap<usize, S>,
+    transaction_mode: TransactionMode,
+    breakpoints: Vec<(usize, Breakpoint<S>)>,
+    next_breakpoint_id: usize,
+    observers: Vec<Box<dyn SimulationObserver<S>>>,
+    limits: Limits,
+    services: Services,
+    clone_state: fn(&S) -> S,
+}
+
+/// Determines how the [`Simulation`] protects its current state from an event that fails midway
+/// through its evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TransactionMode {
+    /// Events are applied directly to the current state. This is the fastest mode, but an event that
+    /// fails may leave the current state partially mutated.
+    #[default]
+    InPlace,
+
+    /// Events are applied to a clone of the current state, which replaces the current state only
+    /// if the event succeeds. A failed step leaves the simulation exactly as it was before the step.
+    CloneAndCommit,
+}
+
+/// Determines when the [`Simulation`] captures snapshots of its state. A snapshot taken at some
+/// cursor location is a copy of the state that reflects the application of all events preceding
+/// that location.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SnapshotPolicy {
+    /// Snapshots are only captured by explicitly calling [`Simulation::snapshot()`].
+    #[default]
+    Explicit,
+
+    /// A snapshot is captured automatically every `n` events. An interval of 0 is treated
+    /// as [`SnapshotPolicy::Explicit`].
+    Every(usize),
+
+    /// A snapshot is captured automatically after every event, subject to a memory budget, expressed
+    /// as the maximum number of retained snapshots. When the budget is exceeded, the snapshot interval
+    /// is doubled and every other snapshot is evicted, spreading the retained snapshots evenly over the
+    /// timeline. A snapshot at the start of the timeline is never evicted, and does not count towards
+    /// the budget.
+    Budget(usize),
+}
+
+impl SnapshotPolicy {
+    fn initial_interval(&self) -> usize {
+        match self {
+            SnapshotPolicy::Explicit => 0,
+            SnapshotPolicy::Every(interval) => *interval,
+            SnapshotPolicy::Budget(_) => 1,
+        }
+    }
 }
 
 impl<S: Default + Clone> Default for Simulation<S> {
@@ -32,6 +117,9 @@ impl<S> Simulation<S> {
     /// Evaluates the next event in the timeline, applying it to the current state
     /// to transition to the next state.
     ///
+    /// If the event fails, the cursor remains in place and any changes that the event made to the queue
+    /// are discarded. Whether the current state is also preserved depends on the [`TransactionMode`].
+    ///
     /// # Errors
     /// [`SimulationError`] if an error occurs. Expected variants:
     ///
@@ -41,42 +129,180 @@ impl<S> Simulation<S> {
         if self.cursor == self.scenario.timeline.len() {
             return Err(SimulationError::TimelineExhausted);
         }
-        let event = &self.scenario.timeline[self.cursor];
-        let mut queue = Queue::new(self.cursor + 1, &self.scenario.timeline);
-        event.apply(&mut self.current_state, &mut queue)?;
-        let (offset, _, insertions) = queue.into_inner();
-        process_insertions(offset, insertions, &mut self.scenario.timeline);
+        let index = self.cursor;
+        let event = &self.scenario.timeline[index];
+        for observer in &mut self.observers {
+            observer.before_event(index, event.as_ref(), &self.current_state);
+        }
+        let mut queue = Queue::new(index + 1, &self.scenario.timeline).with_rng(Rng::for_event(self.scenario.seed, index));
+        let services = &self.services;
+        let result = transact(&self.transaction_mode, self.clone_state, &mut self.current_state, |state| {
+            event.apply_with(state, &mut ApplyContext::new(&mut queue, services))
+        });
+        if let Err(error) = &result {
+            for observer in &mut self.observers {
+                observer.on_error(index, event.as_ref(), &self.current_state, error);
+            }
+        }
+        result?;
+        let changes = queue.into_changes();
+        if !changes.insertions.is_empty() {
+            for observer in &mut self.observers {
+                observer.on_insertions(index, event.as_ref(), &changes.insertions);
+            }
+        }
+        if !changes.is_empty() {
+            // snapshots beyond the current event no longer reflect the timeline
+            self.discard_snapshots_after(changes.offset);
+        }
+        changes.apply(&mut self.scenario.timeline);
         self.cursor += 1;
+        let event = &self.scenario.timeline[index];
+        for observer in &mut self.observers {
+            observer.after_event(index, event.as_ref(), &self.current_state);
+        }
+        self.auto_snapshot();
         Ok(())
     }
 
+    fn auto_snapshot(&mut self) {
+        if self.snapshot_interval == 0 || !self.cursor.is_multiple_of(self.snapshot_interval) {
+            return;
+        }
+        self.snapshot();
+
+        if let SnapshotPolicy::Budget(max_snapshots) = self.snapshot_policy {
+            // location 0 is a multiple of every interval, so it is left out of the count
+            while self.snapshots.range(1..).count() > max_snapshots {
+                self.snapshot_interval *= 2;
+                let interval = self.snapshot_interval;
+                let evicted = self.snapshots.keys().copied().filter(|location| location % interval != 0).collect::<Vec<_>>();
+                for location in evicted {
+                    self.snapshots.remove(&location);
+                    self.notify_discarded(location);
+                }
+            }
+        }
+    }
+
+    /// Steps back over the most recently evaluated event, transitioning to the prior state. If the
+    /// event is reversible (see [`Event::as_reversible()`]), the event is undone in place. Otherwise,
+    /// this is equivalent to jumping to the preceding cursor location, which resumes from a snapshot
+    /// or replays the timeline from the initial state. The latter also applies if any of the registered
+    /// observers cannot follow the reversal (see [`SimulationObserver::follows_reversal()`]).
+    ///
+    /// Events that were added to the timeline by the reversed event are retained.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::TimelineExhausted`], if the cursor is already parked at the start of the timeline.
+    /// * [`SimulationError::Transition`], if the event could not be reversed or replayed.
+    pub fn step_back(&mut self) -> Result<(), SimulationError<S>> {
+        if self.cursor == 0 {
+            return Err(SimulationError::TimelineExhausted);
+        }
+        let event = &self.scenario.timeline[self.cursor - 1];
+        let reversible = event.as_reversible().filter(|_| self.observers.iter().all(|observer| observer.follows_reversal()));
+        match reversible {
+            Some(reversible) => {
+                transact(&self.transaction_mode, self.clone_state, &mut self.current_state, |state| reversible.unapply(state))?;
+                self.cursor -= 1;
+                self.notify_relocation();
+                Ok(())
+            }
+            None => self.jump(self.cursor - 1),
+        }
+    }
+
     /// Resets the simulation, reinitialising the current state from the initial state
-    /// specified in the simulation scenario, and resetting the cursor to location 0.
-    pub fn reset(&mut self)
-    where
-        S: Clone,
-    {
-        self.current_state = self.scenario.initial.clone();
+    /// specified in the simulation scenario, and resetting the cursor to location 0. Retained
+    /// snapshots are unaffected.
+    pub fn reset(&mut self) {
+        self.current_state = (self.clone_state)(&self.scenario.initial);
         self.cursor = 0;
+        self.notify_relocation();
+    }
+
+    fn notify_relocation(&mut self) {
+        for observer in &mut self.observers {
+            observer.on_relocate(self.cursor);
+        }
+    }
+
+    fn notify_discarded(&mut self, location: usize) {
+        for observer in &mut self.observers {
+            observer.on_snapshot_discarded(location);
+        }
+    }
+
+    /// Discards the snapshots beyond the given location.
+    fn discard_snapshots_after(&mut self, location: usize) {
+        let discarded = self.snapshots.split_off(&(location + 1));
+        for &location in discarded.keys() {
+            self.notify_discarded(location);
+        }
+    }
+
+    /// Captures a snapshot of the current state at the current cursor location, replacing any
+    /// existing snapshot at that location.
+    pub fn snapshot(&mut self) {
+        self.snapshots.insert(self.cursor, (self.clone_state)(&self.current_state));
+        for observer in &mut self.observers {
+            observer.on_snapshot(self.cursor);
+        }
+    }
+
+    /// An iterator over the cursor locations of the retained snapshots, in ascending order.
+    pub fn snapshots(&self) -> impl Iterator<Item = usize> + '_ {
+        self.snapshots.keys().copied()
+    }
+
+    /// Discards all retained snapshots.
+    pub fn clear_snapshots(&mut self) {
+        for location in mem::take(&mut self.snapshots).into_keys() {
+            self.notify_discarded(location);
+        }
+    }
+
+    /// The current snapshot policy.
+    pub fn snapshot_policy(&self) -> &SnapshotPolicy {
+        &self.snapshot_policy
+    }
+
+    /// Assigns a new snapshot policy. Existing snapshots are retained, but will not be evicted
+    /// under the new policy until the next automatic snapshot is taken.
+    pub fn set_snapshot_policy(&mut self, snapshot_policy: SnapshotPolicy) {
+        self.snapshot_interval = snapshot_policy.initial_interval();
+        self.snapshot_policy = snapshot_policy;
+    }
+
+    /// The current transaction mode.
+    pub fn transaction_mode(&self) -> &TransactionMode {
+        &self.transaction_mode
+    }
+
+    /// Assigns a new transaction mode.
+    pub fn set_transaction_mode(&mut self, transaction_mode: TransactionMode) {
+        self.transaction_mode = transaction_mode;
     }
 
     /// Jumps to a specified location in the timeline and evaluates the event at that location.
+    /// When jumping backward, the simulation resumes from the nearest snapshot at or before the
+    /// given location, falling back to a reset if there is no such snapshot.
     ///
     /// # Errors
     /// [`SimulationError`] if an error occurs. Expected variants:
     ///
     /// * [`SimulationError::TimelineExhausted`], if the cursor is already parked at the end of the timeline.
     /// * [`SimulationError::Transition`], if the event could not be evaluated.
-    pub fn jump(&mut self, location: usize) -> Result<(), SimulationError<S>>
-    where
-        S: Clone,
-    {
+    pub fn jump(&mut self, location: usize) -> Result<(), SimulationError<S>> {
         if location > self.scenario.timeline.len() {
             return Err(SimulationError::TimelineExhausted);
         }
 
         if location < self.cursor {
-            self.reset();
+            self.rewind(location);
         }
 
         while self.cursor < location {
@@ -86,15 +312,32 @@ impl<S> Simulation<S> {
         Ok(())
     }
 
-    /// Evaluates the remaining events in the timeline.
+    /// Resumes from the nearest snapshot at or before the given location, falling back to a reset if there
+    /// is no such snapshot.
+    fn rewind(&mut self, location: usize) {
+        match self.snapshots.range(..=location).next_back() {
+            Some((&snapshot_location, snapshot)) => {
+                self.current_state = (self.clone_state)(snapshot);
+                self.cursor = snapshot_location;
+                self.notify_relocation();
+            }
+            None => self.reset(),
+        }
+    }
+
+    /// Evaluates the remaining events in the timeline, subject to the configured [`Limits`].
     ///
     /// # Errors
     /// [`SimulationError`] if an error occurs. Expected variants:
     ///
     /// * [`SimulationError::TimelineExhausted`], if the cursor is already parked at the end of the timeline.
     /// * [`SimulationError::Transition`], if the event could not be evaluated.
+    /// * [`SimulationError::StepLimitExceeded`], [`SimulationError::TimelineLimitExceeded`] or
+    ///   [`SimulationError::WallClockLimitExceeded`], if a limit was exceeded.
     pub fn run(&mut self) -> Result<(), SimulationError<S>> {
+        let mut guard = Guard::new(&self.limits);
         while self.cursor < self.scenario.timeline.len() {
+            guard.check(self.scenario.timeline.len())?;
             self.step()?;
         }
 
@@ -102,7 +345,7 @@ impl<S> Simulation<S> {
     }
 
     /// Appends an event to the timeline at the current cursor location, assuming that there
-    /// are no events at and beyond that location.
+    /// are no events at and beyond that location. The event is scheduled at the current simulation time.
     ///
     /// # Errors
     /// [`SimulationError`] if an error occurs. Expected variants:
@@ -119,9 +362,18 @@ impl<S> Simulation<S> {
     }
 
     /// Truncates the timeline at the current cursor location, dropping all events at and beyond
-    /// this point.
+    /// this point. Snapshots beyond the cursor location are also dropped.
     pub fn truncate(&mut self) {
         self.scenario.timeline.truncate(self.cursor);
+        self.discard_snapshots_after(self.cursor);
+    }
+
+    /// Drops the events at and beyond the current cursor location that were generated by other events,
+    /// retaining those that were added by the user or loaded from a persisted scenario. Snapshots beyond
+    /// the cursor location are also dropped. See [`crate::Provenance`].
+    pub fn truncate_generated(&mut self) {
+        self.scenario.timeline.truncate_generated(self.cursor);
+        self.discard_snapshots_after(self.cursor);
     }
 
     /// A reference to the underlying scenario.
@@ -129,15 +381,23 @@ impl<S> Simulation<S> {
         &self.scenario
     }
 
-    /// Assigns a new scenario, resetting the simulation in the process.
-    pub fn set_scenario(&mut self, scenario: Scenario<S>)
-    where
-        S: Clone,
-    {
+    /// Assigns a new scenario, resetting the simulation and discarding all snapshots in the process.
+    pub fn set_scenario(&mut self, scenario: Scenario<S>) {
         self.scenario = scenario;
+        self.clear_snapshots();
         self.reset();
     }
 
+    /// The run-wide services made available to events through the [`ApplyContext`].
+    pub fn services(&self) -> &Services {
+        &self.services
+    }
+
+    /// A mutable reference to the run-wide services, for registering and deregistering services.
+    pub fn services_mut(&mut self) -> &mut Services {
+        &mut self.services
+    }
+
     /// A reference to the current simulation state.
     pub fn current_state(&self) -> &S {
         &self.current_state
@@ -147,15 +407,77 @@ impl<S> Simulation<S> {
     pub fn cursor(&self) -> usize {
         self.cursor
     }
+
+    /// The current simulation time; i.e., the time of the most recently evaluated event, or zero if
+    /// the cursor is at location 0.
+    pub fn now(&self) -> Time {
+        match self.cursor {
+            0 => 0.0,
+            cursor => self.scenario.timeline.times()[cursor - 1],
+        }
+    }
 }
 
 impl<S: Clone> From<Scenario<S>> for Simulation<S> {
     fn from(scenario: Scenario<S>) -> Self {
         let current_state = scenario.initial.clone();
+        let snapshot_policy = SnapshotPolicy::default();
         Self {
             scenario,
             current_state,
             cursor: 0,
+            snapshot_interval: snapshot_policy.initial_interval(),
+            snapshot_policy,
+            snapshots: BTreeMap::default(),
+            transaction_mode: TransactionMode::default(),
+            breakpoints: Vec::default(),
+            next_breakpoint_id: 0,
+            observers: Vec::default(),
+            limits: Limits::default(),
+            services: Services::default(),
+            clone_state: S::clone,
+        }
+    }
+}
+
+/// Clones the simulation, including its scenario, current state, snapshots and breakpoints. Events and
+/// services are shared between the original and the clone. Observers are not cloned, as they typically accumulate
+/// results on behalf of the simulation they were registered with; the clone starts without observers.
+impl<S: Clone> Clone for Simulation<S> {
+    fn clone(&self) -> Self {
+        Self {
+            scenario: self.scenario.clone(),
+            current_state: self.current_state.clone(),
+            cursor: self.cursor,
+            snapshot_policy: self.snapshot_policy.clone(),
+            snapshot_interval: self.snapshot_interval,
+            snapshots: self.snapshots.clone(),
+            transaction_mode: self.transaction_mode.clone(),
+            breakpoints: self.breakpoints.clone(),
+            next_breakpoint_id: self.next_breakpoint_id,
+            observers: Vec::default(),
+            limits: self.limits.clone(),
+            services: self.services.clone(),
+            clone_state: self.clone_state,
+        }
+    }
+}
+
+/// Applies a state transition under the given [`TransactionMode`], using `clone_state` to copy the state
+/// under [`TransactionMode::CloneAndCommit`].
+fn transact<S>(
+    transaction_mode: &TransactionMode,
+    clone_state: fn(&S) -> S,
+    state: &mut S,
+    transition: impl FnOnce(&mut S) -> Result<(), TransitionError>,
+) -> Result<(), TransitionError> {
+    match transaction_mode {
+        TransactionMode::InPlace => transition(state),
+        TransactionMode::CloneAndCommit => {
+            let mut next = clone_state(state);
+            transition(&mut next)?;
+            *state = next;
+            Ok(())
         }
     }
 }
@@ -178,6 +500,24 @@ pub enum SimulationError<S> {
 
     #[error("write scenario: {0}")]
     WriteScenario(#[from] WriteScenarioError),
+
+    #[error("write graph: {0}")]
+    WriteGraph(#[from] WriteGraphError),
+
+    #[error("invalid location {0}")]
+    InvalidLocation(usize),
+
+    #[error("edit rejected: {0}")]
+    Diverged(Divergence),
+
+    #[error("step limit of {0} exceeded")]
+    StepLimitExceeded(usize),
+
+    #[error("timeline length limit of {0} exceeded")]
+    TimelineLimitExceeded(usize),
+
+    #[error("wall-clock limit of {0:?} exceeded")]
+    WallClockLimitExceeded(Duration),
 }
 
 /// Conversions from the blanket [`SimulationError`] type to the underlying variant arguments.
@@ -218,6 +558,62 @@ impl<S> SimulationError<S> {
             _ => None,
         }
     }
+
+    /// Converts the error into a [`Option<WriteGraphError>`].
+    pub fn write_graph(self) -> Option<WriteGraphError> {
+        match self {
+            SimulationError::WriteGraph(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Converts the error into a [`Option<usize>`], containing the invalid location.
+    pub fn invalid_location(self) -> Option<usize> {
+        match self {
+            SimulationError::InvalidLocation(location) => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Converts the error into a [`Option<Divergence>`], describing why an edit was rejected.
+    pub fn diverged(self) -> Option<Divergence> {
+        match self {
+            SimulationError::Diverged(divergence) => Some(divergence),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if and only if this error signals that one of the [`Limits`] was exceeded.
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(
+            self,
+            Self::StepLimitExceeded(_) | Self::TimelineLimitExceeded(_) | Self::WallClockLimitExceeded(_)
+        )
+    }
+
+    /// Converts the error into a [`Option<usize>`], containing the exceeded step limit.
+    pub fn step_limit_exceeded(self) -> Option<usize> {
+        match self {
+            SimulationError::StepLimitExceeded(max_steps) => Some(max_steps),
+            _ => None,
+        }
+    }
+
+    /// Converts the error into a [`Option<usize>`], containing the exceeded timeline length limit.
+    pub fn timeline_limit_exceeded(self) -> Option<usize> {
+        match self {
+            SimulationError::TimelineLimitExceeded(max_timeline_len) => Some(max_timeline_len),
+            _ => None,
+        }
+    }
+
+    /// Converts the error into a [`Option<Duration>`], containing the exceeded wall-clock limit.
+    pub fn wall_clock_limit_exceeded(self) -> Option<Duration> {
+        match self {
+            SimulationError::WallClockLimitExceeded(wall_clock) => Some(wall_clock),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
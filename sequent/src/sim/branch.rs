@@ -0,0 +1,201 @@
+//! Forking of simulations into alternate timelines, and a tree model for tracking the resulting branches.
+
+use crate::Simulation;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+impl<S: Clone> Simulation<S> {
+    /// Forks the simulation, returning an independent simulation that shares the timeline up to
+    /// (and beyond) the current cursor location. The fork starts at the same cursor location, with
+    /// a copy of the current state and of the retained snapshots, and is subject to the same
+    /// policies and limits. Events are shared between the two timelines, but the timelines
+    /// themselves are independent; e.g., truncating the fork's timeline or pushing new events
+    /// onto it does not affect the original.
+    ///
+    /// Breakpoints and observers are not carried over to the fork.
+    pub fn fork(&self) -> Self {
+        let mut fork = self.clone();
+        fork.clear_breakpoints();
+        fork.next_breakpoint_id = 0;
+        fork
+    }
+}
+
+/// A named simulation within a [`BranchTree`].
+#[derive(Debug)]
+pub struct Branch<S> {
+    name: String,
+    parent: Option<String>,
+    divergence: usize,
+    sim: Simulation<S>,
+}
+
+impl<S> Branch<S> {
+    /// The name of the branch.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the branch that this branch was forked from, or `None` if this is the root branch.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// The cursor location of the parent simulation at the time this branch was forked. The two
+    /// timelines are identical up to this location. For the root branch, this is always 0.
+    pub fn divergence(&self) -> usize {
+        self.divergence
+    }
+
+    /// A reference to the branch's simulation.
+    pub fn sim(&self) -> &Simulation<S> {
+        &self.sim
+    }
+
+    /// A mutable reference to the branch's simulation.
+    pub fn sim_mut(&mut self) -> &mut Simulation<S> {
+        &mut self.sim
+    }
+}
+
+/// A tree of named simulation branches, rooted at a single simulation. New branches are created
+/// by forking an existing branch at its current cursor location (see [`Simulation::fork()`]), allowing
+/// alternate futures to be explored and compared without affecting the original.
+#[derive(Debug)]
+pub struct BranchTree<S> {
+    root: String,
+    branches: BTreeMap<String, Branch<S>>,
+}
+
+impl<S> BranchTree<S> {
+    /// Creates a tree comprising a single root branch with the given name.
+    pub fn new(root: impl Into<String>, sim: Simulation<S>) -> Self {
+        let root = root.into();
+        let branch = Branch {
+            name: root.clone(),
+            parent: None,
+            divergence: 0,
+            sim,
+        };
+        Self {
+            branches: BTreeMap::from([(root.clone(), branch)]),
+            root,
+        }
+    }
+
+    /// The root branch.
+    pub fn root(&self) -> &Branch<S> {
+        &self.branches[&self.root]
+    }
+
+    /// Looks up a branch by its name.
+    pub fn get(&self, name: &str) -> Option<&Branch<S>> {
+        self.branches.get(name)
+    }
+
+    /// Looks up a branch by its name, returning a mutable reference.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Branch<S>> {
+        self.branches.get_mut(name)
+    }
+
+    /// An iterator over all branches, in ascending order of their names.
+    pub fn branches(&self) -> impl Iterator<Item = &Branch<S>> {
+        self.branches.values()
+    }
+
+    /// An iterator over the branches that were forked directly from the named branch.
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Branch<S>> {
+        self.branches
+            .values()
+            .filter(move |branch| branch.parent.as_deref() == Some(name))
+    }
+
+    /// The chain of branches from the named branch up to the root, starting with the named branch.
+    /// Empty if there is no such branch.
+    pub fn ancestry(&self, name: &str) -> Vec<&Branch<S>> {
+        let mut ancestry = vec![];
+        let mut current = self.branches.get(name);
+        while let Some(branch) = current {
+            ancestry.push(branch);
+            current = branch.parent.as_deref().and_then(|parent| self.branches.get(parent));
+        }
+        ancestry
+    }
+
+    /// Forks the `parent` branch at its current cursor location, adding the fork to the tree under
+    /// the given name. Returns a mutable reference to the new branch.
+    ///
+    /// # Errors
+    /// [`BranchError`] if an error occurs. Expected variants:
+    ///
+    /// * [`BranchError::NoSuchBranch`], if there is no branch named `parent`.
+    /// * [`BranchError::DuplicateBranch`], if a branch named `name` already exists.
+    pub fn fork(&mut self, parent: &str, name: impl Into<String>) -> Result<&mut Branch<S>, BranchError>
+    where
+        S: Clone,
+    {
+        let name = name.into();
+        if self.branches.contains_key(&name) {
+            return Err(BranchError::DuplicateBranch(name));
+        }
+        let parent_branch = self
+            .branches
+            .get(parent)
+            .ok_or_else(|| BranchError::NoSuchBranch(parent.into()))?;
+        let branch = Branch {
+            name: name.clone(),
+            parent: Some(parent.into()),
+            divergence: parent_branch.sim.cursor(),
+            sim: parent_branch.sim.fork(),
+        };
+        Ok(self.branches.entry(name).or_insert(branch))
+    }
+
+    /// Removes the named branch, along with all of its descendants, returning the removed branches.
+    ///
+    /// # Errors
+    /// [`BranchError`] if an error occurs. Expected variants:
+    ///
+    /// * [`BranchError::NoSuchBranch`], if there is no branch with the given name.
+    /// * [`BranchError::RootRemoval`], if the named branch is the root.
+    pub fn remove(&mut self, name: &str) -> Result<Vec<Branch<S>>, BranchError> {
+        if !self.branches.contains_key(name) {
+            return Err(BranchError::NoSuchBranch(name.into()));
+        }
+        if name == self.root {
+            return Err(BranchError::RootRemoval);
+        }
+        let mut removed = vec![self.branches.remove(name).unwrap()];
+        let mut index = 0;
+        while index < removed.len() {
+            let parent = removed[index].name.clone();
+            let children = self
+                .branches
+                .values()
+                .filter(|branch| branch.parent.as_deref() == Some(parent.as_str()))
+                .map(|branch| branch.name.clone())
+                .collect::<Vec<_>>();
+            for child in children {
+                removed.push(self.branches.remove(&child).unwrap());
+            }
+            index += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Produced by [`BranchTree`] operations.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BranchError {
+    #[error("no such branch '{0}'")]
+    NoSuchBranch(String),
+
+    #[error("duplicate branch '{0}'")]
+    DuplicateBranch(String),
+
+    #[error("cannot remove the root branch")]
+    RootRemoval,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, Append, TestState};
+use crate::{Breakpoint, BranchError, Condition, BranchTree, Limits, Simulation, SnapshotPolicy};
+use std::rc::Rc;
+
+fn transitions(sim: &Simulation<TestState>) -> Vec<usize> {
+    sim.current_state().transitions.clone()
+}
+
+#[test]
+fn fork_shares_past_and_diverges() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.set_limits(Limits {
+        max_steps: Some(10),
+        ..Limits::default()
+    });
+    sim.add_breakpoint(Breakpoint::before(Condition::Index(3)));
+    sim.jump(2).unwrap();
+
+    let mut fork = sim.fork();
+    assert_eq!(0, fork.breakpoints().count());
+    assert_eq!(2, fork.cursor());
+    assert_eq!(vec![0, 1], transitions(&fork));
+    assert_eq!(sim.snapshots().collect::<Vec<_>>(), fork.snapshots().collect::<Vec<_>>());
+    assert_eq!(sim.snapshot_policy(), fork.snapshot_policy());
+    assert_eq!(sim.limits(), fork.limits());
+    for (original, forked) in sim.scenario().timeline.iter().zip(fork.scenario().timeline.iter()) {
+        assert!(Rc::ptr_eq(original, forked));
+    }
+
+    // alter the fork's future
+    fork.truncate();
+    fork.push_event(Box::new(Append { id: 5 })).unwrap();
+    fork.run().unwrap();
+    assert_eq!(vec![0, 1, 5], transitions(&fork));
+
+    // the original is unaffected
+    assert_eq!(4, sim.scenario().timeline.len());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 2, 3], transitions(&sim));
+}
+
+#[test]
+fn branch_tree() {
+    let mut tree = BranchTree::new("main", Simulation::from(scenario(&[0, 1, 2])));
+    assert_eq!("main", tree.root().name());
+    assert!(tree.root().parent().is_none());
+    assert_eq!(0, tree.root().divergence());
+
+    tree.get_mut("main").unwrap().sim_mut().jump(1).unwrap();
+    let what_if = tree.fork("main", "what-if").unwrap();
+    assert_eq!("what-if", what_if.name());
+    assert_eq!(Some("main"), what_if.parent());
+    assert_eq!(1, what_if.divergence());
+    what_if.sim_mut().truncate();
+    what_if.sim_mut().push_event(Box::new(Append { id: 7 })).unwrap();
+    what_if.sim_mut().run().unwrap();
+
+    let nested = tree.fork("what-if", "nested").unwrap();
+    assert_eq!(2, nested.divergence());
+
+    assert_eq!(
+        vec!["main", "nested", "what-if"],
+        tree.branches().map(|branch| branch.name()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["what-if"],
+        tree.children("main").map(|branch| branch.name()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["nested", "what-if", "main"],
+        tree.ancestry("nested").iter().map(|branch| branch.name()).collect::<Vec<_>>()
+    );
+    assert!(tree.ancestry("missing").is_empty());
+
+    tree.get_mut("main").unwrap().sim_mut().run().unwrap();
+    assert_eq!(vec![0, 1, 2], transitions(tree.get("main").unwrap().sim()));
+    assert_eq!(vec![0, 7], transitions(tree.get("what-if").unwrap().sim()));
+}
+
+#[test]
+fn branch_tree_errors() {
+    let mut tree = BranchTree::new("main", Simulation::from(scenario(&[0])));
+    assert_eq!(
+        BranchError::NoSuchBranch("missing".into()),
+        tree.fork("missing", "other").unwrap_err()
+    );
+    assert_eq!(
+        BranchError::DuplicateBranch("main".into()),
+        tree.fork("main", "main").unwrap_err()
+    );
+    assert_eq!(BranchError::RootRemoval, tree.remove("main").unwrap_err());
+    assert_eq!(BranchError::NoSuchBranch("missing".into()), tree.remove("missing").unwrap_err());
+    assert_eq!("no such branch 'missing'", BranchError::NoSuchBranch("missing".into()).to_string());
+    assert_eq!("duplicate branch 'main'", BranchError::DuplicateBranch("main".into()).to_string());
+    assert_eq!("cannot remove the root branch", BranchError::RootRemoval.to_string());
+}
+
+#[test]
+fn remove_branch_with_descendants() {
+    let mut tree = BranchTree::new("main", Simulation::from(scenario(&[0])));
+    tree.fork("main", "a").unwrap();
+    tree.fork("a", "b").unwrap();
+    tree.fork("main", "c").unwrap();
+    let removed = tree.remove("a").unwrap();
+    assert_eq!(vec!["a", "b"], removed.iter().map(|branch| branch.name()).collect::<Vec<_>>());
+    assert_eq!(
+        vec!["c", "main"],
+        tree.branches().map(|branch| branch.name()).collect::<Vec<_>>()
+    );
+}
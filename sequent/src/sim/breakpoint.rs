@@ -0,0 +1,175 @@
+//! Breakpoints for suspending a run at points of interest.
+
+use crate::Simulation;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+/// A condition for suspending a run, together with the timing of the suspension relative to the
+/// matching event.
+#[derive(Debug)]
+pub struct Breakpoint<S> {
+    /// The condition that must be satisfied for the run to be suspended.
+    pub condition: Condition<S>,
+
+    /// Whether the run is suspended before or after the matching event.
+    pub timing: Timing,
+}
+
+impl<S> Breakpoint<S> {
+    /// Creates a breakpoint that suspends the run before the matching event is evaluated.
+    pub fn before(condition: Condition<S>) -> Self {
+        Self {
+            condition,
+            timing: Timing::Before,
+        }
+    }
+
+    /// Creates a breakpoint that suspends the run after the matching event is evaluated.
+    pub fn after(condition: Condition<S>) -> Self {
+        Self {
+            condition,
+            timing: Timing::After,
+        }
+    }
+}
+
+impl<S> Clone for Breakpoint<S> {
+    fn clone(&self) -> Self {
+        Self {
+            condition: self.condition.clone(),
+            timing: self.timing,
+        }
+    }
+}
+
+impl<S> Display for Breakpoint<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.timing, self.condition)
+    }
+}
+
+/// The condition of a [`Breakpoint`].
+pub enum Condition<S> {
+    /// Matches events with the given name.
+    Event(String),
+
+    /// Matches the event at the given timeline location.
+    Index(usize),
+
+    /// Matches when the predicate is satisfied by the current state. The state is tested before
+    /// the event is evaluated for [`Timing::Before`] breakpoints, and after it is evaluated for
+    /// [`Timing::After`] breakpoints.
+    State(Rc<dyn Fn(&S) -> bool>),
+}
+
+impl<S> Clone for Condition<S> {
+    fn clone(&self) -> Self {
+        match self {
+            Condition::Event(name) => Condition::Event(name.clone()),
+            Condition::Index(index) => Condition::Index(*index),
+            Condition::State(predicate) => Condition::State(predicate.clone()),
+        }
+    }
+}
+
+impl<S> Debug for Condition<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Event(name) => f.debug_tuple("Event").field(name).finish(),
+            Condition::Index(index) => f.debug_tuple("Index").field(index).finish(),
+            Condition::State(_) => f.write_str("State(..)"),
+        }
+    }
+}
+
+impl<S> Display for Condition<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Event(name) => write!(f, "event '{name}'"),
+            Condition::Index(index) => write!(f, "index {index}"),
+            Condition::State(_) => write!(f, "state predicate"),
+        }
+    }
+}
+
+/// When a run is suspended relative to the event that matched a [`Breakpoint`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timing {
+    /// The run is suspended before the matching event is evaluated.
+    #[default]
+    Before,
+
+    /// The run is suspended after the matching event is evaluated.
+    After,
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timing::Before => write!(f, "before"),
+            Timing::After => write!(f, "after"),
+        }
+    }
+}
+
+/// Identifies the [`Breakpoint`] that suspended a run, and the location of the matching event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakpointHit {
+    /// The breakpoint ID, as assigned by [`Simulation::add_breakpoint()`].
+    pub id: usize,
+
+    /// The location of the matching event in the timeline.
+    pub index: usize,
+
+    /// Whether the run was suspended before or after the matching event.
+    pub timing: Timing,
+}
+
+/// Management of breakpoints.
+impl<S> Simulation<S> {
+    /// Registers a breakpoint, returning its ID. Breakpoints are honoured by [`Simulation::run_with()`].
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint<S>) -> usize {
+        let id = self.next_breakpoint_id;
+        self.next_breakpoint_id += 1;
+        self.breakpoints.push((id, breakpoint));
+        id
+    }
+
+    /// Deregisters the breakpoint with the given ID, returning it if it was registered.
+    pub fn remove_breakpoint(&mut self, id: usize) -> Option<Breakpoint<S>> {
+        let position = self.breakpoints.iter().position(|(existing, _)| *existing == id)?;
+        Some(self.breakpoints.remove(position).1)
+    }
+
+    /// Deregisters all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// An iterator over the registered breakpoints and their IDs, in the order of registration.
+    pub fn breakpoints(&self) -> impl Iterator<Item = (usize, &Breakpoint<S>)> {
+        self.breakpoints.iter().map(|(id, breakpoint)| (*id, breakpoint))
+    }
+
+    /// Tests the registered breakpoints of the given timing against the event at the given location,
+    /// returning the first that matches.
+    pub(crate) fn test_breakpoints(&self, index: usize, timing: Timing) -> Option<BreakpointHit> {
+        let event = &self.scenario.timeline[index];
+        self.breakpoints
+            .iter()
+            .filter(|(_, breakpoint)| breakpoint.timing == timing)
+            .find(|(_, breakpoint)| match &breakpoint.condition {
+                Condition::Event(name) => event.name() == name.as_str(),
+                Condition::Index(location) => *location == index,
+                Condition::State(predicate) => predicate(&self.current_state),
+            })
+            .map(|(id, _)| BreakpointHit {
+                id: *id,
+                index,
+                timing,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests;
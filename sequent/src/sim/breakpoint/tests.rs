@@ -0,0 +1,129 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, TestState};
+use crate::{Breakpoint, BreakpointHit, Condition, RunOptions, Simulation, Timing};
+use std::rc::Rc;
+
+#[test]
+fn add_and_remove() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    assert_eq!(0, sim.add_breakpoint(Breakpoint::before(Condition::Index(1))));
+    assert_eq!(1, sim.add_breakpoint(Breakpoint::after(Condition::Event("append".into()))));
+    assert_eq!(
+        vec!["0: before index 1", "1: after event 'append'"],
+        sim.breakpoints()
+            .map(|(id, breakpoint)| format!("{id}: {breakpoint}"))
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(Timing::Before, sim.remove_breakpoint(0).unwrap().timing);
+    assert!(sim.remove_breakpoint(0).is_none());
+    assert_eq!(vec![1], sim.breakpoints().map(|(id, _)| id).collect::<Vec<_>>());
+
+    // IDs are not reused
+    assert_eq!(2, sim.add_breakpoint(Breakpoint::before(Condition::Index(1))));
+    sim.clear_breakpoints();
+    assert_eq!(0, sim.breakpoints().count());
+}
+
+#[test]
+fn breakpoint_implements_display_and_debug() {
+    let breakpoint = Breakpoint::<TestState>::before(Condition::State(Rc::new(|_| true)));
+    assert_eq!("before state predicate", breakpoint.to_string());
+    assert_eq!(
+        "Breakpoint { condition: State(..), timing: Before }",
+        format!("{breakpoint:?}")
+    );
+    assert_eq!(
+        "Index(3)",
+        format!("{:?}", Condition::<TestState>::Index(3))
+    );
+    assert_eq!(
+        "Event(\"append\")",
+        format!("{:?}", Condition::<TestState>::Event("append".into()))
+    );
+}
+
+#[test]
+fn run_with_breakpoint_on_index() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    let before = sim.add_breakpoint(Breakpoint::before(Condition::Index(1)));
+    let after = sim.add_breakpoint(Breakpoint::after(Condition::Index(2)));
+
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(
+        Some(BreakpointHit {
+            id: before,
+            index: 1,
+            timing: Timing::Before
+        }),
+        report.breakpoint
+    );
+    assert_eq!(1, report.steps);
+    assert_eq!(1, sim.cursor());
+
+    // resumes past the breakpoint at the cursor
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(
+        Some(BreakpointHit {
+            id: after,
+            index: 2,
+            timing: Timing::After
+        }),
+        report.breakpoint
+    );
+    assert_eq!(2, report.steps);
+    assert_eq!(3, sim.cursor());
+
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(None, report.breakpoint);
+    assert_eq!(4, sim.cursor());
+}
+
+#[test]
+fn run_with_breakpoint_on_event() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    let id = sim.add_breakpoint(Breakpoint::after(Condition::Event("append".into())));
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(
+        Some(BreakpointHit {
+            id,
+            index: 0,
+            timing: Timing::After
+        }),
+        report.breakpoint
+    );
+    assert_eq!(1, sim.cursor());
+}
+
+#[test]
+fn run_with_breakpoint_on_state() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    let id = sim.add_breakpoint(Breakpoint::before(Condition::State(Rc::new(|state: &TestState| {
+        state.transitions.contains(&1)
+    }))));
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(
+        Some(BreakpointHit {
+            id,
+            index: 2,
+            timing: Timing::Before
+        }),
+        report.breakpoint
+    );
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+}
+
+#[test]
+fn run_with_ignore_breakpoints() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.add_breakpoint(Breakpoint::before(Condition::Index(1)));
+    let report = sim
+        .run_with(&RunOptions {
+            ignore_breakpoints: true,
+            ..RunOptions::default()
+        })
+        .unwrap();
+    assert_eq!(None, report.breakpoint);
+    assert_eq!(4, sim.cursor());
+}
@@ -0,0 +1,225 @@
+//! Editing of events already in the timeline: removal, replacement and relocation.
+
+use crate::{Event, Placement, Simulation, SimulationError, SimulationObserver, Timeline};
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
+use std::mem;
+use std::rc::Rc;
+
+/// Determines whether a timeline edit is validated before it is applied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// The edit is applied without validation.
+    #[default]
+    Unchecked,
+
+    /// The events from the edited location onward are evaluated both with and without the edit (on copies
+    /// of the simulation), as per [`Simulation::insert_event()`]. The edit is rejected if any event fails
+    /// where it previously succeeded, or makes different insertions into the timeline.
+    Checked,
+}
+
+impl<S: Clone + 'static> Simulation<S> {
+    /// Removes the event at the given location from the timeline. If the event precedes the cursor,
+    /// the current state is re-derived without it, and the cursor is moved back by one location, so that
+    /// it continues to point at the same future event.
+    ///
+    /// Snapshots beyond the given location are dropped. The simulation is unchanged if an error occurs.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::InvalidLocation`], if the location is not within the timeline.
+    /// * [`SimulationError::Diverged`], if the edit was rejected by [`Validation::Checked`].
+    /// * [`SimulationError::Transition`], if an event could not be evaluated while re-deriving the
+    ///   current state or validating the edit.
+    pub fn remove_event(&mut self, index: usize, validation: Validation) -> Result<(), SimulationError<S>> {
+        self.check_location(index)?;
+        let cursor = if index < self.cursor { self.cursor - 1 } else { self.cursor };
+        self.edit(index, cursor, validation, |timeline| {
+            timeline.remove(index);
+        })
+    }
+
+    /// Replaces the event at the given location in the timeline. The replacement is scheduled at the same
+    /// time as the replaced event. If the event precedes the cursor, the current state is re-derived with
+    /// the replacement.
+    ///
+    /// Snapshots beyond the given location are dropped. The simulation is unchanged if an error occurs.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::InvalidLocation`], if the location is not within the timeline.
+    /// * [`SimulationError::Diverged`], if the edit was rejected by [`Validation::Checked`].
+    /// * [`SimulationError::Transition`], if an event could not be evaluated while re-deriving the
+    ///   current state or validating the edit.
+    pub fn replace_event(&mut self, index: usize, event: Box<dyn Event<State = S>>, validation: Validation) -> Result<(), SimulationError<S>> {
+        self.replace_shared_event(index, event.into(), validation)
+    }
+
+    /// As per [`Simulation::replace_event()`], for an event that may be shared with the caller. A caller that
+    /// retains the event may resubmit it (e.g., without validation) if the replacement is rejected.
+    ///
+    /// # Errors
+    /// As per [`Simulation::replace_event()`].
+    pub fn replace_shared_event(&mut self, index: usize, event: Rc<dyn Event<State = S>>, validation: Validation) -> Result<(), SimulationError<S>> {
+        self.check_location(index)?;
+        self.edit(index, self.cursor, validation, |timeline| {
+            timeline.replace_shared(index, event.clone());
+        })
+    }
+
+    /// Moves the event at location `from` so that it ends up at location `to`, shifting the events in
+    /// between. The moved event retains its [`crate::Provenance`], and is scheduled at the same time as its
+    /// new predecessor. If either location
+    /// precedes the cursor, the current state is re-derived. An event moved from the past into the future
+    /// moves the cursor back by one location; an event moved from the future into the past moves the cursor
+    /// forward by one location.
+    ///
+    /// Snapshots beyond the lesser of the two locations are dropped. The simulation is unchanged if an
+    /// error occurs.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::InvalidLocation`], if either location is not within the timeline.
+    /// * [`SimulationError::Diverged`], if the edit was rejected by [`Validation::Checked`].
+    /// * [`SimulationError::Transition`], if an event could not be evaluated while re-deriving the
+    ///   current state or validating the edit.
+    pub fn move_event(&mut self, from: usize, to: usize, validation: Validation) -> Result<(), SimulationError<S>> {
+        self.check_location(from)?;
+        self.check_location(to)?;
+        let cursor = match (from < self.cursor, to < self.cursor) {
+            (true, false) => self.cursor - 1,
+            (false, true) => self.cursor + 1,
+            _ => self.cursor,
+        };
+        self.edit(from.min(to), cursor, validation, |timeline| timeline.relocate(from, to))
+    }
+
+    fn check_location(&self, index: usize) -> Result<(), SimulationError<S>> {
+        if index < self.scenario.timeline.len() {
+            Ok(())
+        } else {
+            Err(SimulationError::InvalidLocation(index))
+        }
+    }
+
+    /// Applies an edit to the timeline, where `index` is the first location affected by the edit and
+    /// `cursor` is the cursor location once the edit is applied.
+    fn edit(&mut self, index: usize, cursor: usize, validation: Validation, edit: impl Fn(&mut Timeline<S>)) -> Result<(), SimulationError<S>> {
+        if validation == Validation::Checked {
+            let mut candidate = self.fork();
+            edit(&mut candidate.scenario.timeline);
+            if let (_, Some(divergence)) = self.diverge(index.min(self.cursor), candidate)? {
+                return Err(SimulationError::Diverged(divergence));
+            }
+        }
+
+        let timeline = self.scenario.timeline.clone();
+        edit(&mut self.scenario.timeline);
+        let mut dropped = self.snapshots.split_off(&(index + 1));
+        let mut notifications = vec![];
+        if index < self.cursor {
+            // re-derive the current state, rewinding to the edit and replaying up to the cursor; the observers
+            // are detached meanwhile, so that they are unaffected if the edit is rejected, and their notifications
+            // are recorded for delivery once the edit is accepted
+            let (state, prior) = (self.current_state.clone(), self.cursor);
+            let observers = mem::take(&mut self.observers);
+            let log = Rc::new(RefCell::new(vec![]));
+            if !observers.is_empty() {
+                self.observers.push(Box::new(NotificationRecorder { log: log.clone() }));
+            }
+            let result = self.jump(index).and_then(|()| self.jump(cursor));
+            self.observers = observers;
+            if let Err(err) = result {
+                self.scenario.timeline = timeline;
+                self.current_state = state;
+                self.cursor = prior;
+                self.snapshots.split_off(&(index + 1));
+                self.snapshots.append(&mut dropped);
+                return Err(err);
+            }
+            notifications = log.take();
+        }
+        for &location in dropped.keys() {
+            self.notify_discarded(location);
+        }
+        for notification in notifications {
+            self.deliver(notification);
+        }
+        Ok(())
+    }
+
+    fn deliver(&mut self, notification: Notification<S>) {
+        for observer in &mut self.observers {
+            match &notification {
+                Notification::Before(index, state) => {
+                    observer.before_event(*index, self.scenario.timeline[*index].as_ref(), state);
+                }
+                Notification::After(index, state) => {
+                    observer.after_event(*index, self.scenario.timeline[*index].as_ref(), state);
+                }
+                Notification::Insertions(index, insertions) => {
+                    observer.on_insertions(*index, self.scenario.timeline[*index].as_ref(), insertions);
+                }
+                Notification::Relocate(location) => observer.on_relocate(*location),
+                Notification::Snapshot(location) => observer.on_snapshot(*location),
+                Notification::SnapshotDiscarded(location) => observer.on_snapshot_discarded(*location),
+            }
+        }
+    }
+}
+
+/// An observer notification withheld while an edit is re-derived. Evaluated events are identified by their
+/// location, as the events preceding the cursor are not moved by the re-derivation.
+enum Notification<S> {
+    Before(usize, S),
+    After(usize, S),
+    Insertions(usize, Vec<(Placement, Rc<dyn Event<State = S>>)>),
+    Relocate(usize),
+    Snapshot(usize),
+    SnapshotDiscarded(usize),
+}
+
+/// Records the notifications issued while an edit is re-derived. Errors are not recorded, as an edit that
+/// fails to re-derive is rejected.
+struct NotificationRecorder<S> {
+    log: Rc<RefCell<Vec<Notification<S>>>>,
+}
+
+impl<S> Debug for NotificationRecorder<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationRecorder").field("notifications", &self.log.borrow().len()).finish()
+    }
+}
+
+impl<S: Clone> SimulationObserver<S> for NotificationRecorder<S> {
+    fn before_event(&mut self, index: usize, _: &dyn Event<State = S>, state: &S) {
+        self.log.borrow_mut().push(Notification::Before(index, state.clone()));
+    }
+
+    fn after_event(&mut self, index: usize, _: &dyn Event<State = S>, state: &S) {
+        self.log.borrow_mut().push(Notification::After(index, state.clone()));
+    }
+
+    fn on_insertions(&mut self, index: usize, _: &dyn Event<State = S>, insertions: &[(Placement, Rc<dyn Event<State = S>>)]) {
+        self.log.borrow_mut().push(Notification::Insertions(index, insertions.to_vec()));
+    }
+
+    fn on_relocate(&mut self, location: usize) {
+        self.log.borrow_mut().push(Notification::Relocate(location));
+    }
+
+    fn on_snapshot(&mut self, location: usize) {
+        self.log.borrow_mut().push(Notification::Snapshot(location));
+    }
+
+    fn on_snapshot_discarded(&mut self, location: usize) {
+        self.log.borrow_mut().push(Notification::SnapshotDiscarded(location));
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,268 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, Append, TestState};
+use crate::stats::{Collector, Statistics};
+use crate::{Cause, Divergence, Event, Queue, Simulation, SnapshotPolicy, StaticNamed, TransitionError, Validation};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+fn ids(sim: &Simulation<TestState>) -> Vec<usize> {
+    sim.scenario().timeline.iter().map(|event| event.to_string().parse().unwrap()).collect()
+}
+
+fn transitions(sim: &Simulation<TestState>) -> Vec<usize> {
+    sim.current_state().transitions.clone()
+}
+
+#[test]
+fn remove_after_cursor() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.step().unwrap();
+    sim.remove_event(2, Validation::Unchecked).unwrap();
+    assert_eq!(vec![0, 1, 3], ids(&sim));
+    assert_eq!(1, sim.cursor());
+    assert_eq!(vec![0], transitions(&sim));
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 3], transitions(&sim));
+}
+
+#[test]
+fn remove_before_cursor() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(3).unwrap();
+    sim.remove_event(1, Validation::Unchecked).unwrap();
+    assert_eq!(vec![0, 2, 3], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![0, 2], transitions(&sim));
+}
+
+#[test]
+fn remove_before_cursor_with_snapshots() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.jump(3).unwrap();
+    assert_eq!(vec![1, 2, 3], sim.snapshots().collect::<Vec<_>>());
+    sim.remove_event(1, Validation::Unchecked).unwrap();
+    assert_eq!(vec![1, 2], sim.snapshots().collect::<Vec<_>>());
+    assert_eq!(vec![0, 2], transitions(&sim));
+    sim.jump(1).unwrap();
+    assert_eq!(vec![0], transitions(&sim));
+}
+
+#[test]
+fn replace_before_cursor() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.replace_event(0, Box::new(Append { id: 9 }), Validation::Unchecked).unwrap();
+    assert_eq!(vec![9, 1, 2, 3], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![9, 1], transitions(&sim));
+}
+
+#[test]
+fn replace_with_failed_rederivation() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.jump(2).unwrap();
+    assert_eq!(
+        Some(TransitionError("duplicate ID 1".into())),
+        sim.replace_event(0, Box::new(Append { id: 1 }), Validation::Unchecked).unwrap_err().transition()
+    );
+
+    // the simulation is unchanged
+    assert_eq!(vec![0, 1, 2, 3], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![0, 1], transitions(&sim));
+    assert_eq!(vec![1, 2], sim.snapshots().collect::<Vec<_>>());
+}
+
+fn counted(sim: &mut Simulation<TestState>) -> Rc<RefCell<Statistics>> {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, _: &TestState| {
+        statistics.counter("events").increment();
+    });
+    let statistics = collector.statistics();
+    sim.add_observer(Box::new(collector));
+    statistics
+}
+
+#[test]
+fn rejected_edit_retains_statistics() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    let statistics = counted(&mut sim);
+    sim.jump(3).unwrap();
+    assert!(sim.replace_event(0, Box::new(Append { id: 1 }), Validation::Unchecked).is_err());
+    assert_eq!(3, statistics.borrow().counters()["events"].count());
+
+    // the checkpoints are intact, so rewinding restores the statistics at the snapshot
+    sim.jump(2).unwrap();
+    assert_eq!(2, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn accepted_edit_replays_statistics() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    let statistics = counted(&mut sim);
+    sim.jump(2).unwrap();
+    sim.remove_event(1, Validation::Unchecked).unwrap();
+    assert_eq!(1, sim.cursor());
+    assert_eq!(1, statistics.borrow().counters()["events"].count());
+    sim.run().unwrap();
+    assert_eq!(3, statistics.borrow().counters()["events"].count());
+}
+
+/// Appends the ID tag, counting its evaluations.
+#[derive(Debug)]
+struct Tally {
+    id: usize,
+    evaluations: Rc<Cell<usize>>,
+}
+
+impl ToString for Tally {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for Tally {
+    fn name() -> &'static str {
+        "tally"
+    }
+}
+
+impl Event for Tally {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        self.evaluations.set(self.evaluations.get() + 1);
+        state.transitions.push(self.id);
+        Ok(())
+    }
+}
+
+#[test]
+fn accepted_edit_rederived_once() {
+    let mut scenario = scenario(&[0, 2]);
+    let evaluations = Rc::new(Cell::new(0));
+    scenario.timeline.insert(1, Box::new(Tally { id: 1, evaluations: evaluations.clone() }));
+    let mut sim = Simulation::from(scenario);
+    let statistics = counted(&mut sim);
+    sim.jump(3).unwrap();
+    sim.remove_event(0, Validation::Unchecked).unwrap();
+    assert_eq!(2, evaluations.get());
+    assert_eq!(vec![1, 2], transitions(&sim));
+    assert_eq!(2, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn move_from_future_into_past() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.move_event(3, 0, Validation::Unchecked).unwrap();
+    assert_eq!(vec![3, 0, 1, 2], ids(&sim));
+    assert_eq!(3, sim.cursor());
+    assert_eq!(vec![3, 0, 1], transitions(&sim));
+}
+
+#[test]
+fn move_from_past_into_future() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.move_event(0, 3, Validation::Unchecked).unwrap();
+    assert_eq!(vec![1, 2, 3, 0], ids(&sim));
+    assert_eq!(1, sim.cursor());
+    assert_eq!(vec![1], transitions(&sim));
+}
+
+#[test]
+fn move_within_past() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.move_event(0, 1, Validation::Unchecked).unwrap();
+    assert_eq!(vec![1, 0, 2, 3], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![1, 0], transitions(&sim));
+}
+
+#[test]
+fn move_within_future() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.move_event(1, 2, Validation::Unchecked).unwrap();
+    assert_eq!(vec![0, 2, 1, 3], ids(&sim));
+    assert_eq!(0, sim.cursor());
+}
+
+#[test]
+fn edit_at_invalid_location() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    assert_eq!(Some(4), sim.remove_event(4, Validation::Unchecked).unwrap_err().invalid_location());
+    assert_eq!(
+        Some(4),
+        sim.replace_event(4, Box::new(Append { id: 9 }), Validation::Unchecked).unwrap_err().invalid_location()
+    );
+    assert_eq!(Some(4), sim.move_event(0, 4, Validation::Unchecked).unwrap_err().invalid_location());
+    assert_eq!(Some(4), sim.move_event(4, 0, Validation::Unchecked).unwrap_err().invalid_location());
+    assert_eq!(vec![0, 1, 2, 3], ids(&sim));
+}
+
+#[test]
+fn checked_edit_without_divergence() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.remove_event(1, Validation::Checked).unwrap();
+    sim.replace_event(2, Box::new(Append { id: 9 }), Validation::Checked).unwrap();
+    sim.move_event(2, 0, Validation::Checked).unwrap();
+    assert_eq!(vec![9, 0, 2], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![9, 0], transitions(&sim));
+}
+
+#[test]
+fn checked_edit_with_divergence() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    assert_eq!(
+        Some(Divergence {
+            index: 3,
+            name: "append".into(),
+            encoded: "3".into(),
+            cause: Cause::Failed(TransitionError("duplicate ID 3".into())),
+        }),
+        sim.replace_event(1, Box::new(Append { id: 3 }), Validation::Checked).unwrap_err().diverged()
+    );
+    assert_eq!(vec![0, 1, 2, 3], ids(&sim));
+
+    // the same edit is permitted without validation
+    sim.replace_event(1, Box::new(Append { id: 3 }), Validation::Unchecked).unwrap();
+    assert_eq!(vec![0, 3, 2, 3], ids(&sim));
+}
+
+#[test]
+fn checked_shared_edit_resubmitted() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    let event: Rc<dyn Event<State = TestState>> = Rc::new(Append { id: 3 });
+    assert!(sim.replace_shared_event(1, event.clone(), Validation::Checked).unwrap_err().diverged().is_some());
+
+    // the rejected event is resubmitted without validation
+    sim.replace_shared_event(1, event.clone(), Validation::Unchecked).unwrap();
+    assert!(Rc::ptr_eq(&event, &sim.scenario().timeline[1]));
+    assert_eq!(vec![0, 3, 2, 3], ids(&sim));
+}
+
+#[test]
+fn checked_edit_before_cursor_with_divergence() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    assert_eq!(
+        Some(Divergence {
+            index: 1,
+            name: "append".into(),
+            encoded: "1".into(),
+            cause: Cause::Failed(TransitionError("duplicate ID 1".into())),
+        }),
+        sim.replace_event(0, Box::new(Append { id: 1 }), Validation::Checked).unwrap_err().diverged()
+    );
+    assert_eq!(vec![0, 1, 2, 3], ids(&sim));
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![0, 1], transitions(&sim));
+}
@@ -0,0 +1,402 @@
+//! Insertion of events into the timeline, validating the effect of the insertion on the future events.
+
+use crate::sim::limits::Guard;
+use crate::{Event, Placement, Simulation, SimulationError, SimulationObserver, TransitionError};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+/// An event insertion that has been validated against the future events, but not yet applied to the
+/// timeline. Created by [`Simulation::insert_event()`]. The insertion is applied with
+/// [`Simulation::accept_insertion()`], or abandoned by simply dropping it.
+pub struct Insertion<S> {
+    index: usize,
+    event: Rc<dyn Event<State = S>>,
+    replayed: usize,
+    divergence: Option<Divergence>,
+
+    /// The cursor and the events in the timeline at the time of validation. Holding on to the events
+    /// ensures that their addresses are not reused while the insertion is pending.
+    cursor: usize,
+    timeline: Vec<Rc<dyn Event<State = S>>>,
+}
+
+impl<S> Insertion<S> {
+    /// The location at which the event is to be inserted.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The event to be inserted.
+    pub fn event(&self) -> &dyn Event<State = S> {
+        &*self.event
+    }
+
+    /// The number of events that were evaluated during validation, including the inserted event.
+    pub fn replayed(&self) -> usize {
+        self.replayed
+    }
+
+    /// The first point at which the future events behaved differently as a result of the insertion,
+    /// or `None` if no difference was detected.
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.divergence.as_ref()
+    }
+}
+
+impl<S> Debug for Insertion<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Insertion")
+            .field("index", &self.index)
+            .field("event", &self.event)
+            .field("replayed", &self.replayed)
+            .field("divergence", &self.divergence)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Describes the first future event that behaved differently as a result of an insertion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// The location in the timeline (after the insertion) from which the future events can no longer
+    /// be relied upon. Truncating the timeline at this location keeps the future up to the divergence.
+    /// This is usually the location of the offending event, unless the offending event was itself
+    /// added to the timeline during validation, in which case it is the location of the next event
+    /// that was already in the timeline. For an [`Insertion`], the location always follows the inserted
+    /// event, which is retained even if it is the offending event.
+    pub index: usize,
+
+    /// The name of the offending event. Taken from [`crate::Named::name`].
+    pub name: String,
+
+    /// The encoded arguments of the offending event. Taken from [`ToString::to_string`].
+    pub encoded: String,
+
+    /// How the offending event diverged.
+    pub cause: Cause,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "event '{}' {} (location {})", self.name, self.cause, self.index)
+    }
+}
+
+/// The cause of a [`Divergence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cause {
+    /// The event failed, having previously succeeded.
+    Failed(TransitionError),
+
+    /// The event made different insertions into the timeline.
+    Insertions,
+}
+
+impl Display for Cause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cause::Failed(err) => write!(f, "failed: {err}"),
+            Cause::Insertions => write!(f, "made different insertions"),
+        }
+    }
+}
+
+/// Determines how much of the future is retained when accepting an [`Insertion`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Acceptance {
+    /// All future events are retained, irrespective of any divergence.
+    #[default]
+    All,
+
+    /// The future events are retained up to the divergence, if there is one. The timeline is
+    /// truncated at [`Divergence::index`].
+    UntilDivergence,
+
+    /// None of the future events are retained. The timeline is truncated after the inserted event.
+    None,
+}
+
+/// The observable behaviour of an evaluated event: the insertions it made (by slot, name and
+/// encoded arguments), or the error it produced.
+type Outcome = Result<Vec<(Slot, String, String)>, TransitionError>;
+
+/// The placement of an inserted event, normalised so that events pushed onto the end of the queue
+/// compare equal irrespective of the length of the queue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Placement(Placement),
+    End,
+}
+
+/// Identifies an evaluated event across the runs compared during validation. An event that was in the
+/// timeline at the start of a run is identified by its address, which the runs share, whereas a generated
+/// event is identified by its generator and its position among the generator's insertions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Identity {
+    Original(*const ()),
+    Generated(Rc<Identity>, usize),
+}
+
+/// A record of an event evaluated during validation.
+struct Step {
+    identity: Identity,
+    name: String,
+    encoded: String,
+    outcome: Outcome,
+}
+
+/// Records the insertions made by each evaluated event.
+#[derive(Debug, Default)]
+struct InsertionRecorder {
+    insertions: Rc<RefCell<Vec<(Placement, String, String)>>>,
+}
+
+impl<S> SimulationObserver<S> for InsertionRecorder {
+    fn on_insertions(&mut self, _: usize, _: &dyn Event<State = S>, insertions: &[(Placement, Rc<dyn Event<State = S>>)]) {
+        self.insertions.borrow_mut().extend(
+            insertions
+                .iter()
+                .map(|(placement, event)| (*placement, event.name().into(), event.to_string())),
+        );
+    }
+}
+
+fn address<S>(event: &Rc<dyn Event<State = S>>) -> *const () {
+    Rc::as_ptr(event).cast()
+}
+
+/// Identifies the events that the event with the given identity inserted into the `future` events, by
+/// matching each previously unseen event to the first unmatched insertion of the same name and encoded
+/// arguments. Such insertions are indistinguishable, so it does not matter which of them is matched.
+fn identify_insertions<S>(
+    generator: &Identity,
+    insertions: &[(Placement, String, String)],
+    future: &[Rc<dyn Event<State = S>>],
+    identities: &mut HashMap<*const (), (Identity, Rc<dyn Event<State = S>>)>,
+) {
+    if insertions.is_empty() {
+        return;
+    }
+    let generator = Rc::new(generator.clone());
+    let mut matched = vec![false; insertions.len()];
+    for event in future {
+        if identities.contains_key(&address(event)) {
+            continue;
+        }
+        let (name, encoded) = (event.name(), event.to_string());
+        let position = insertions
+            .iter()
+            .zip(&matched)
+            .position(|((_, inserted_name, inserted_encoded), &matched)| !matched && *inserted_name == name && *inserted_encoded == encoded)
+            .expect("an unseen event must have been inserted");
+        matched[position] = true;
+        identities.insert(address(event), (Identity::Generated(generator.clone(), position), event.clone()));
+    }
+}
+
+impl<S: Clone> Simulation<S> {
+    /// Validates the insertion of an event at the given location, which must lie at or after the cursor.
+    /// Unlike [`Simulation::push_event()`], the future events are retained. To establish the effect of the
+    /// insertion, the remaining events are evaluated (on copies of the simulation) both with and without the
+    /// inserted event, stopping at the first event that either fails where it previously succeeded, or makes
+    /// different insertions into the timeline. This simulation is unaffected. An insertion at the end of the
+    /// timeline has no future events to affect, so nothing is evaluated.
+    ///
+    /// The returned [`Insertion`] may then be applied with [`Simulation::accept_insertion()`].
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::InvalidLocation`], if the location precedes the cursor or exceeds the length
+    ///   of the timeline.
+    /// * [`SimulationError::StepLimitExceeded`], [`SimulationError::TimelineLimitExceeded`] or
+    ///   [`SimulationError::WallClockLimitExceeded`], if a limit was exceeded during validation.
+    pub fn insert_event(&self, index: usize, event: Box<dyn Event<State = S>>) -> Result<Insertion<S>, SimulationError<S>> {
+        self.insert_shared_event(index, event.into())
+    }
+
+    /// As per [`Simulation::insert_event()`], for an event that may be shared with the caller. A caller that
+    /// retains the event may still apply it otherwise (e.g., after truncating the timeline) if validation fails.
+    ///
+    /// # Errors
+    /// As per [`Simulation::insert_event()`].
+    pub fn insert_shared_event(&self, index: usize, event: Rc<dyn Event<State = S>>) -> Result<Insertion<S>, SimulationError<S>> {
+        if index < self.cursor || index > self.scenario.timeline.len() {
+            return Err(SimulationError::InvalidLocation(index));
+        }
+        let (replayed, divergence) = if index == self.scenario.timeline.len() {
+            (0, None)
+        } else {
+            let mut candidate = self.fork();
+            candidate.scenario.timeline.insert_shared(index, event.clone());
+            self.diverge(self.cursor, candidate)?
+        };
+        let divergence = divergence.map(|divergence| Divergence {
+            index: divergence.index.max(index + 1),
+            ..divergence
+        });
+        Ok(Insertion {
+            index,
+            event,
+            replayed,
+            divergence,
+            cursor: self.cursor,
+            timeline: self.scenario.timeline.iter().cloned().collect(),
+        })
+    }
+
+    /// Evaluates the events from the given location onward, both in a copy of this simulation and in
+    /// the given candidate, which holds an edited copy of the timeline. The edit must not precede the given
+    /// location. The candidate's snapshots beyond that location are dropped, as they may not reflect the
+    /// edit. Returns the number of events evaluated in the candidate, and the first point at which the
+    /// candidate diverged, if any.
+    ///
+    /// Any event that is referenced by the candidate, but not by this simulation, must be kept alive by
+    /// the caller for the duration of the call, as events are identified by their addresses.
+    pub(super) fn diverge(&self, from: usize, mut candidate: Simulation<S>) -> Result<(usize, Option<Divergence>), SimulationError<S>> {
+        let mut baseline = self.fork();
+        baseline.jump(from)?;
+        candidate.snapshots.split_off(&(from + 1));
+        candidate.jump(from)?;
+
+        // events that were in the timeline prior to the edit
+        let originals = baseline.scenario.timeline[from..]
+            .iter()
+            .map(|event| Identity::Original(address(event)))
+            .collect::<HashSet<_>>();
+
+        // locations of the events in the edited timeline, keyed by their addresses
+        let locations = candidate.scenario.timeline[from..]
+            .iter()
+            .enumerate()
+            .map(|(offset, event)| (address(event), from + offset))
+            .collect::<HashMap<_, _>>();
+        let locate = |identity: &Identity| match identity {
+            Identity::Original(address) => locations.get(address).copied(),
+            Identity::Generated(..) => None,
+        };
+        let len = candidate.scenario.timeline.len();
+
+        let baseline = self.trace(baseline)?.into_iter().map(|step| (step.identity, step.outcome)).collect::<HashMap<_, _>>();
+        let trace = self.trace(candidate)?;
+
+        for (position, step) in trace.iter().enumerate() {
+            let location = locate(&step.identity);
+            let cause = match (location, baseline.get(&step.identity), &step.outcome) {
+                // an event that was not reached previously ends the comparison
+                (Some(_), None, _) if originals.contains(&step.identity) => break,
+
+                // a new event, or one that succeeded previously, now fails
+                (_, None | Some(Ok(_)), Err(err)) => Cause::Failed(err.clone()),
+
+                // a previously evaluated event makes different insertions
+                (_, Some(Ok(before)), Ok(after)) if before != after => Cause::Insertions,
+
+                // an event that failed previously continues to fail, ending the comparison
+                (_, Some(Err(_)), Err(_)) => break,
+
+                _ => continue,
+            };
+            let index = location.unwrap_or_else(|| {
+                // attribute the divergence to the next event that was already in the timeline
+                trace[position + 1..]
+                    .iter()
+                    .find_map(|step| locate(&step.identity))
+                    .unwrap_or(len)
+            });
+            let divergence = Divergence {
+                index,
+                name: step.name.clone(),
+                encoded: step.encoded.clone(),
+                cause,
+            };
+            return Ok((trace.len(), Some(divergence)));
+        }
+        Ok((trace.len(), None))
+    }
+
+    /// Evaluates the remaining events in the given simulation, returning a record of each evaluated
+    /// event. Stops at the first failing event or when the timeline is exhausted.
+    #[allow(clippy::needless_pass_by_value)]
+    fn trace(&self, mut sim: Simulation<S>) -> Result<Vec<Step>, SimulationError<S>> {
+        let recorder = InsertionRecorder::default();
+        let insertions = recorder.insertions.clone();
+        sim.add_observer(Box::new(recorder));
+
+        // the identities of the events seen so far, keyed by their addresses; the events are retained,
+        // so that their addresses are not reused by later insertions
+        let mut identities = sim.scenario.timeline[sim.cursor..]
+            .iter()
+            .map(|event| (address(event), (Identity::Original(address(event)), event.clone())))
+            .collect::<HashMap<_, _>>();
+        let mut guard = Guard::new(&self.limits);
+        let mut trace = vec![];
+        while sim.cursor < sim.scenario.timeline.len() {
+            guard.check(sim.scenario.timeline.len())?;
+            let event = &sim.scenario.timeline[sim.cursor];
+            let (identity, name, encoded) = (identities[&address(event)].0.clone(), event.name().into(), event.to_string());
+            let future = sim.scenario.timeline.len() - sim.cursor - 1;
+            let outcome = match sim.step() {
+                Ok(()) => {
+                    let insertions = insertions.take();
+                    identify_insertions(&identity, &insertions, &sim.scenario.timeline[sim.cursor..], &mut identities);
+                    Ok(insertions
+                        .into_iter()
+                        .enumerate()
+                        .map(|(position, (placement, name, encoded))| {
+                            let slot = match placement {
+                                Placement::Index(index) if index == future + position => Slot::End,
+                                _ => Slot::Placement(placement),
+                            };
+                            (slot, name, encoded)
+                        })
+                        .collect())
+                }
+                Err(SimulationError::Transition(err)) => Err(err),
+                Err(err) => return Err(err),
+            };
+            let failed = outcome.is_err();
+            trace.push(Step {
+                identity,
+                name,
+                encoded,
+                outcome,
+            });
+            if failed {
+                break;
+            }
+        }
+        Ok(trace)
+    }
+
+    /// Applies a validated [`Insertion`], inserting the event into the timeline and retaining the
+    /// future events as per the given [`Acceptance`]. The cursor is left in place. Snapshots beyond
+    /// the insertion location are dropped.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::InvalidLocation`], if the insertion location is no longer valid; i.e.,
+    ///   the cursor or the timeline has changed since the insertion was validated.
+    pub fn accept_insertion(&mut self, insertion: Insertion<S>, acceptance: Acceptance) -> Result<(), SimulationError<S>> {
+        let index = insertion.index;
+        let unchanged = insertion.cursor == self.cursor
+            && insertion.timeline.iter().map(address).eq(self.scenario.timeline.iter().map(address));
+        if !unchanged {
+            return Err(SimulationError::InvalidLocation(index));
+        }
+        self.scenario.timeline.insert_shared(index, insertion.event);
+        self.discard_snapshots_after(index);
+        match (acceptance, insertion.divergence) {
+            (Acceptance::UntilDivergence, Some(divergence)) => self.scenario.timeline.truncate(divergence.index),
+            (Acceptance::None, _) => self.scenario.timeline.truncate(index + 1),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,266 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, Append, TestState};
+use crate::{Acceptance, Cause, Divergence, Event, Limits, Queue, Simulation, StaticNamed, TransitionError, Validation};
+use std::rc::Rc;
+
+/// Appends an ID tag that is 100 greater than the last one.
+#[derive(Debug)]
+struct EchoLast;
+
+impl ToString for EchoLast {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for EchoLast {
+    fn name() -> &'static str {
+        "echo-last"
+    }
+}
+
+impl Event for EchoLast {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        let last = state.transitions.last().copied().unwrap_or_default();
+        queue.push_later(Box::new(Append { id: last + 100 }));
+        Ok(())
+    }
+}
+
+/// Schedules an [`Append`] with the given ID tag at the end of the timeline.
+#[derive(Debug)]
+struct Spawn {
+    id: usize,
+}
+
+impl ToString for Spawn {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for Spawn {
+    fn name() -> &'static str {
+        "spawn"
+    }
+}
+
+impl Event for Spawn {
+    type State = TestState;
+
+    fn apply(&self, _: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        queue.push_later(Box::new(Append { id: self.id }));
+        Ok(())
+    }
+}
+
+fn transitions(sim: &Simulation<TestState>) -> Vec<usize> {
+    sim.current_state().transitions.clone()
+}
+
+#[test]
+fn insert_without_divergence() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    sim.step().unwrap();
+    let insertion = sim.insert_event(1, Box::new(Append { id: 5 })).unwrap();
+    assert_eq!(1, insertion.index());
+    assert_eq!("5", insertion.event().to_string());
+    assert_eq!(3, insertion.replayed());
+    assert!(insertion.divergence().is_none());
+
+    // validation does not affect the simulation
+    assert_eq!(3, sim.scenario().timeline.len());
+    assert_eq!(1, sim.cursor());
+
+    sim.accept_insertion(insertion, Acceptance::UntilDivergence).unwrap();
+    assert_eq!(4, sim.scenario().timeline.len());
+    assert_eq!(1, sim.cursor());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 5, 1, 2], transitions(&sim));
+}
+
+#[test]
+fn insert_with_failure() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    let insertion = sim.insert_event(1, Box::new(Append { id: 2 })).unwrap();
+    let divergence = Divergence {
+        index: 3,
+        name: "append".into(),
+        encoded: "2".into(),
+        cause: Cause::Failed(TransitionError("duplicate ID 2".into())),
+    };
+    assert_eq!(Some(&divergence), insertion.divergence());
+    assert_eq!(4, insertion.replayed());
+    assert_eq!("event 'append' failed: duplicate ID 2 (location 3)", divergence.to_string());
+
+    sim.accept_insertion(insertion, Acceptance::UntilDivergence).unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![0, 2, 1], transitions(&sim));
+}
+
+#[test]
+fn insert_accepting_all() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    let insertion = sim.insert_event(1, Box::new(Append { id: 2 })).unwrap();
+    assert!(insertion.divergence().is_some());
+    sim.accept_insertion(insertion, Acceptance::All).unwrap();
+    assert_eq!(4, sim.scenario().timeline.len());
+    assert!(sim.run().is_err());
+}
+
+#[test]
+fn insert_accepting_none() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    sim.step().unwrap();
+    let insertion = sim.insert_event(1, Box::new(Append { id: 5 })).unwrap();
+    assert!(insertion.divergence().is_none());
+    sim.accept_insertion(insertion, Acceptance::None).unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![0, 5], transitions(&sim));
+}
+
+#[test]
+fn insert_with_different_insertions() {
+    let mut scenario = scenario(&[0]);
+    scenario.timeline.push(Box::new(EchoLast));
+    let mut sim = Simulation::from(scenario);
+    let insertion = sim.insert_event(1, Box::new(Append { id: 7 })).unwrap();
+    assert_eq!(
+        Some(&Divergence {
+            index: 2,
+            name: "echo-last".into(),
+            encoded: String::default(),
+            cause: Cause::Insertions,
+        }),
+        insertion.divergence()
+    );
+    assert_eq!("event 'echo-last' made different insertions (location 2)", insertion.divergence().unwrap().to_string());
+
+    sim.accept_insertion(insertion, Acceptance::UntilDivergence).unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![0, 7], transitions(&sim));
+}
+
+#[test]
+fn insert_with_failing_generated_event() {
+    let mut scenario = scenario(&[1]);
+    scenario.timeline.insert(0, Box::new(Spawn { id: 3 }));
+    let sim = Simulation::from(scenario);
+    let insertion = sim.insert_event(1, Box::new(Append { id: 3 })).unwrap();
+    assert_eq!(
+        Some(&Divergence {
+            index: 3,
+            name: "append".into(),
+            encoded: "3".into(),
+            cause: Cause::Failed(TransitionError("duplicate ID 3".into())),
+        }),
+        insertion.divergence()
+    );
+}
+
+#[test]
+fn insert_with_generated_event_failing_as_before() {
+    // the generated event fails both with and without the insertion
+    let mut scenario = scenario(&[3]);
+    scenario.timeline.push(Box::new(Spawn { id: 3 }));
+    let sim = Simulation::from(scenario);
+    let insertion = sim.insert_event(0, Box::new(Append { id: 5 })).unwrap();
+    assert!(insertion.divergence().is_none());
+    assert_eq!(4, insertion.replayed());
+}
+
+#[test]
+fn insert_with_failing_inserted_event() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    sim.step().unwrap();
+    let insertion = sim.insert_event(1, Box::new(Append { id: 0 })).unwrap();
+    assert_eq!(2, insertion.divergence().unwrap().index);
+    assert_eq!(1, insertion.replayed());
+
+    // the inserted event is retained; only the future beyond it is discarded
+    sim.accept_insertion(insertion, Acceptance::UntilDivergence).unwrap();
+    assert_eq!(
+        vec!["0", "0"],
+        sim.scenario().timeline.iter().map(|event| event.to_string()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn insert_before_existing_failure() {
+    let sim = Simulation::from(scenario(&[0, 0]));
+    let insertion = sim.insert_event(0, Box::new(Append { id: 5 })).unwrap();
+    assert!(insertion.divergence().is_none());
+    assert_eq!(3, insertion.replayed());
+}
+
+#[test]
+fn insert_at_end_without_evaluation() {
+    // the inserted event would fail if evaluated, but there is no future for it to affect
+    let sim = Simulation::from(scenario(&[0, 1]));
+    let insertion = sim.insert_shared_event(2, Rc::new(Append { id: 0 })).unwrap();
+    assert!(insertion.divergence().is_none());
+    assert_eq!(0, insertion.replayed());
+}
+
+#[test]
+fn insert_at_invalid_location() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    sim.step().unwrap();
+    assert_eq!(Some(0), sim.insert_event(0, Box::new(Append { id: 5 })).unwrap_err().invalid_location());
+    assert_eq!(Some(3), sim.insert_event(3, Box::new(Append { id: 5 })).unwrap_err().invalid_location());
+
+    // the simulation has moved on since the insertion was validated
+    let insertion = sim.insert_event(1, Box::new(Append { id: 5 })).unwrap();
+    sim.run().unwrap();
+    assert_eq!(Some(1), sim.accept_insertion(insertion, Acceptance::All).unwrap_err().invalid_location());
+}
+
+#[test]
+fn insert_after_timeline_changed() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+
+    // the timeline is edited in place, leaving its length and the cursor unchanged
+    let insertion = sim.insert_event(1, Box::new(Append { id: 5 })).unwrap();
+    sim.replace_event(2, Box::new(Append { id: 6 }), Validation::Unchecked).unwrap();
+    assert_eq!(Some(1), sim.accept_insertion(insertion, Acceptance::All).unwrap_err().invalid_location());
+
+    // the cursor moves, but remains before the insertion location
+    let insertion = sim.insert_event(2, Box::new(Append { id: 5 })).unwrap();
+    sim.step().unwrap();
+    assert_eq!(Some(2), sim.accept_insertion(insertion, Acceptance::All).unwrap_err().invalid_location());
+
+    // neither has changed
+    let insertion = sim.insert_event(2, Box::new(Append { id: 5 })).unwrap();
+    sim.accept_insertion(insertion, Acceptance::All).unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 5, 6], transitions(&sim));
+}
+
+#[test]
+fn insert_subject_to_limits() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    sim.set_limits(Limits {
+        max_steps: Some(2),
+        ..Limits::default()
+    });
+    assert_eq!(
+        Some(2),
+        sim.insert_event(0, Box::new(Append { id: 5 })).unwrap_err().step_limit_exceeded()
+    );
+}
+
+#[test]
+fn insert_drops_snapshots_beyond_location() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    sim.set_snapshot_policy(crate::SnapshotPolicy::Every(1));
+    sim.run().unwrap();
+    sim.jump(1).unwrap();
+    let insertion = sim.insert_event(2, Box::new(Append { id: 5 })).unwrap();
+    sim.accept_insertion(insertion, Acceptance::All).unwrap();
+    assert_eq!(vec![1, 2], sim.snapshots().collect::<Vec<_>>());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 5, 2], transitions(&sim));
+}
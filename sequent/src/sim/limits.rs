@@ -0,0 +1,74 @@
+//! Limits that guard against runaway simulations.
+
+use crate::{Simulation, SimulationError};
+use std::time::{Duration, Instant};
+
+/// Limits on the batch evaluation of the timeline, guarding against runaway simulations; e.g., where
+/// a defective model has events that schedule new events indefinitely. Limits are enforced by
+/// [`Simulation::run()`], [`Simulation::run_with()`] and [`Simulation::run_until()`], with the step
+/// count and the wall-clock budget applying to each run individually. All limits are disabled by default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of events that may be evaluated in a single run.
+    pub max_steps: Option<usize>,
+
+    /// The maximum length of the timeline, beyond which no further events may be evaluated.
+    pub max_timeline_len: Option<usize>,
+
+    /// The maximum elapsed (real) time of a single run.
+    pub wall_clock: Option<Duration>,
+}
+
+/// Tracks the progress of a single run against the [`Limits`].
+pub(crate) struct Guard {
+    limits: Limits,
+    started: Instant,
+    steps: usize,
+}
+
+impl Guard {
+    pub(crate) fn new(limits: &Limits) -> Self {
+        Self {
+            limits: limits.clone(),
+            started: Instant::now(),
+            steps: 0,
+        }
+    }
+
+    /// Tests the limits prior to evaluating the next event, counting the event as a step if no
+    /// limit has been exceeded.
+    pub(crate) fn check<S>(&mut self, timeline_len: usize) -> Result<(), SimulationError<S>> {
+        if let Some(max_steps) = self.limits.max_steps {
+            if self.steps == max_steps {
+                return Err(SimulationError::StepLimitExceeded(max_steps));
+            }
+        }
+        if let Some(max_timeline_len) = self.limits.max_timeline_len {
+            if timeline_len > max_timeline_len {
+                return Err(SimulationError::TimelineLimitExceeded(max_timeline_len));
+            }
+        }
+        if let Some(wall_clock) = self.limits.wall_clock {
+            if self.started.elapsed() > wall_clock {
+                return Err(SimulationError::WallClockLimitExceeded(wall_clock));
+            }
+        }
+        self.steps += 1;
+        Ok(())
+    }
+}
+
+impl<S> Simulation<S> {
+    /// The current limits.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Assigns new limits, which take effect from the next run.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,94 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, TestState};
+use crate::{Event, Limits, Queue, RunOptions, Simulation, StaticNamed, TransitionError};
+use std::time::Duration;
+
+/// Appends its ID tag and schedules a copy of itself with the next ID tag, indefinitely.
+#[derive(Debug)]
+struct Perpetual {
+    id: usize,
+}
+
+impl ToString for Perpetual {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for Perpetual {
+    fn name() -> &'static str {
+        "perpetual"
+    }
+}
+
+impl Event for Perpetual {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.transitions.push(self.id);
+        queue.push_later(Box::new(Perpetual { id: self.id + 1 }));
+        Ok(())
+    }
+}
+
+fn perpetual_fixture(limits: Limits) -> Simulation<TestState> {
+    let mut sim = Simulation::from(scenario(&[]));
+    sim.push_event(Box::new(Perpetual { id: 0 })).unwrap();
+    sim.set_limits(limits);
+    sim
+}
+
+#[test]
+fn no_limits_by_default() {
+    let sim = Simulation::from(scenario(&[0]));
+    assert_eq!(&Limits::default(), sim.limits());
+}
+
+#[test]
+fn max_steps() {
+    let mut sim = perpetual_fixture(Limits {
+        max_steps: Some(5),
+        ..Limits::default()
+    });
+    assert_eq!(Some(5), sim.run().unwrap_err().step_limit_exceeded());
+    assert_eq!(5, sim.cursor());
+    assert_eq!(vec![0, 1, 2, 3, 4], sim.current_state().transitions);
+
+    // the step count applies to each run individually
+    assert_eq!(Some(5), sim.run().unwrap_err().step_limit_exceeded());
+    assert_eq!(10, sim.cursor());
+}
+
+#[test]
+fn max_steps_not_exceeded() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    sim.set_limits(Limits {
+        max_steps: Some(3),
+        ..Limits::default()
+    });
+    sim.run().unwrap();
+    assert_eq!(3, sim.cursor());
+}
+
+#[test]
+fn max_timeline_len() {
+    let mut sim = perpetual_fixture(Limits {
+        max_timeline_len: Some(4),
+        ..Limits::default()
+    });
+    let err = sim.run_with(&RunOptions::default()).unwrap_err();
+    assert_eq!(Some(4), err.timeline_limit_exceeded());
+    assert_eq!(4, sim.cursor());
+    assert_eq!(5, sim.scenario().timeline.len());
+}
+
+#[test]
+fn wall_clock() {
+    let mut sim = perpetual_fixture(Limits {
+        wall_clock: Some(Duration::ZERO),
+        ..Limits::default()
+    });
+    let err = sim.run_until(|_, _, _| false).unwrap_err();
+    assert_eq!(Some(Duration::ZERO), err.wall_clock_limit_exceeded());
+}
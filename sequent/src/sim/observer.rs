@@ -0,0 +1,86 @@
+//! Observation of event evaluation.
+
+use crate::{Event, Placement, Simulation, TransitionError};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Receives callbacks from a [`Simulation`] around the evaluation of every event, enabling
+/// metrics collection, logging and similar concerns without wrapping individual events.
+///
+/// Each callback is given the timeline location of the event being evaluated. All callbacks
+/// have empty default implementations, so an observer need only implement the ones it requires.
+/// Aside from the notifications of captured and discarded snapshots, observers are only invoked when
+/// events are evaluated (stepping forward) or skipped over (see [`SimulationObserver::on_skip()`]); they
+/// are not invoked when events are reversed. Instead, an observer is notified via
+/// [`SimulationObserver::on_relocate()`] whenever the cursor is otherwise moved without evaluating events.
+pub trait SimulationObserver<S>: Debug {
+    /// Invoked before the event is applied, with the state prior to the transition.
+    fn before_event(&mut self, _index: usize, _event: &dyn Event<State = S>, _state: &S) {}
+
+    /// Invoked after the event has been successfully applied, with the state following the
+    /// transition. Any insertions made by the event are already reflected in the timeline.
+    fn after_event(&mut self, _index: usize, _event: &dyn Event<State = S>, _state: &S) {}
+
+    /// Invoked when the event could not be applied. Depending on the [`crate::TransactionMode`],
+    /// the given state may have been partially mutated by the event.
+    fn on_error(&mut self, _index: usize, _event: &dyn Event<State = S>, _state: &S, _error: &TransitionError) {}
+
+    /// Invoked after a failing event has been skipped over without being applied, as per the
+    /// [`crate::ErrorPolicy`] of a run. The given state is unaffected by the event, and the cursor now
+    /// points to the location following the event.
+    fn on_skip(&mut self, _index: usize, _event: &dyn Event<State = S>, _state: &S) {}
+
+    /// Invoked after the event has been successfully applied, if the event inserted new events
+    /// into the queue. The insertions are given before they are merged into the timeline; the inserted
+    /// events are shared with the timeline.
+    fn on_insertions(
+        &mut self,
+        _index: usize,
+        _event: &dyn Event<State = S>,
+        _insertions: &[(Placement, Rc<dyn Event<State = S>>)],
+    ) {
+    }
+
+    /// Invoked when the cursor is moved to the given location without evaluating the intervening
+    /// events; e.g., when the simulation is reset, resumes from a snapshot or reverses an event.
+    /// An observer that accumulates results should discard those obtained at and beyond the location.
+    fn on_relocate(&mut self, _location: usize) {}
+
+    /// Invoked after a snapshot of the state is captured at the given location, replacing any existing
+    /// snapshot at that location. When rewinding, the simulation resumes from the nearest snapshot and
+    /// replays the timeline from there, so an observer that accumulates results need only retain them
+    /// at the snapshot locations.
+    fn on_snapshot(&mut self, _location: usize) {}
+
+    /// Invoked after the snapshot at the given location is discarded.
+    fn on_snapshot_discarded(&mut self, _location: usize) {}
+
+    /// Returns `true` if the observer can follow the in-place reversal of an event (see
+    /// [`Simulation::step_back()`]) through [`SimulationObserver::on_relocate()`] alone. An observer that
+    /// retains its results only at snapshot locations should return `false`, in which case the simulation
+    /// steps back by resuming from a snapshot and replaying the timeline instead.
+    fn follows_reversal(&self) -> bool {
+        true
+    }
+}
+
+impl<S> Simulation<S> {
+    /// Registers an observer, which will be notified of every subsequent event evaluation.
+    /// Observers are notified in the order of their registration.
+    pub fn add_observer(&mut self, observer: Box<dyn SimulationObserver<S>>) {
+        self.observers.push(observer);
+    }
+
+    /// Deregisters all observers.
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    /// The number of registered observers.
+    pub fn observers(&self) -> usize {
+        self.observers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests;
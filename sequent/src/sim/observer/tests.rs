@@ -0,0 +1,164 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{scenario, Append, TestState};
+use crate::{ErrorPolicy, Event, Placement, Queue, RunOptions, SimulationObserver, Simulation, StaticNamed, TransitionError};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Default)]
+struct Recorder {
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl SimulationObserver<TestState> for Recorder {
+    fn before_event(&mut self, index: usize, event: &dyn Event<State = TestState>, state: &TestState) {
+        self.log.borrow_mut().push(format!("before {index} {} {:?}", event.to_string(), state.transitions));
+    }
+
+    fn after_event(&mut self, index: usize, event: &dyn Event<State = TestState>, state: &TestState) {
+        self.log.borrow_mut().push(format!("after {index} {} {:?}", event.to_string(), state.transitions));
+    }
+
+    fn on_error(&mut self, index: usize, event: &dyn Event<State = TestState>, _: &TestState, error: &TransitionError) {
+        self.log.borrow_mut().push(format!("error {index} {} {error}", event.to_string()));
+    }
+
+    fn on_insertions(
+        &mut self,
+        index: usize,
+        event: &dyn Event<State = TestState>,
+        insertions: &[(Placement, Rc<dyn Event<State = TestState>>)],
+    ) {
+        self.log.borrow_mut().push(format!("insertions {index} {} {}", event.to_string(), insertions.len()));
+    }
+}
+
+/// Appends the ID tag and schedules an [`Append`] with the next ID tag.
+#[derive(Debug)]
+struct Spawn {
+    id: usize,
+}
+
+impl ToString for Spawn {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for Spawn {
+    fn name() -> &'static str {
+        "spawn"
+    }
+}
+
+impl Event for Spawn {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.transitions.push(self.id);
+        queue.push_later(Box::new(Append { id: self.id + 1 }));
+        Ok(())
+    }
+}
+
+#[test]
+fn observe_events() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut sim = Simulation::from(scenario(&[0, 1, 1]));
+    sim.add_observer(Box::new(Recorder { log: log.clone() }));
+    assert_eq!(1, sim.observers());
+    assert!(sim.run().unwrap_err().transition().is_some());
+    assert_eq!(
+        vec![
+            "before 0 0 []",
+            "after 0 0 [0]",
+            "before 1 1 [0]",
+            "after 1 1 [0, 1]",
+            "before 2 1 [0, 1]",
+            "error 2 1 duplicate ID 1",
+        ],
+        *log.borrow()
+    );
+
+    sim.clear_observers();
+    assert_eq!(0, sim.observers());
+    sim.reset();
+    sim.step().unwrap();
+    assert_eq!(6, log.borrow().len());
+}
+
+#[test]
+fn observe_insertions() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut sim = Simulation::from(scenario(&[]));
+    sim.push_event(Box::new(Spawn { id: 0 })).unwrap();
+    sim.add_observer(Box::new(Recorder { log: log.clone() }));
+    sim.run().unwrap();
+    assert_eq!(
+        vec![
+            "before 0 0 []",
+            "insertions 0 0 1",
+            "after 0 0 [0]",
+            "before 1 1 [0]",
+            "after 1 1 [0, 1]",
+        ],
+        *log.borrow()
+    );
+}
+
+#[test]
+fn default_callbacks_do_nothing() {
+    #[derive(Debug)]
+    struct Silent;
+    impl SimulationObserver<TestState> for Silent {}
+
+    let mut sim = Simulation::from(scenario(&[0, 0]));
+    sim.add_observer(Box::new(Silent));
+    assert!(sim.run().is_err());
+    assert_eq!(1, sim.cursor());
+}
+
+#[test]
+fn observe_relocations() {
+    #[derive(Debug)]
+    struct Relocations(Rc<RefCell<Vec<usize>>>);
+    impl SimulationObserver<TestState> for Relocations {
+        fn on_relocate(&mut self, location: usize) {
+            self.0.borrow_mut().push(location);
+        }
+    }
+
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.add_observer(Box::new(Relocations(log.clone())));
+    sim.jump(2).unwrap();
+    sim.snapshot();
+    sim.run().unwrap();
+    assert!(log.borrow().is_empty());
+
+    sim.jump(3).unwrap();
+    sim.step_back().unwrap();
+    sim.reset();
+    assert_eq!(vec![2, 2, 0], *log.borrow());
+}
+
+#[test]
+fn observe_skips() {
+    #[derive(Debug)]
+    struct Skips(Rc<RefCell<Vec<String>>>);
+    impl SimulationObserver<TestState> for Skips {
+        fn on_skip(&mut self, index: usize, _: &dyn Event<State = TestState>, state: &TestState) {
+            self.0.borrow_mut().push(format!("{index}: {:?}", state.transitions));
+        }
+    }
+
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut sim = Simulation::from(scenario(&[0, 0, 1]));
+    sim.add_observer(Box::new(Skips(log.clone())));
+    sim.run_with(&RunOptions {
+        error_policy: ErrorPolicy::Skip,
+        ..RunOptions::default()
+    })
+    .unwrap();
+    assert_eq!(vec!["1: [0]"], *log.borrow());
+}
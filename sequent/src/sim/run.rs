@@ -0,0 +1,186 @@
+//! Batch evaluation of the timeline, with configurable handling of failing events and breakpoints.
+
+use crate::sim::limits::Guard;
+use crate::{BreakpointHit, Event, Simulation, SimulationError, Timing, TransactionMode, TransitionError};
+use std::mem;
+
+/// Options for [`Simulation::run_with()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// What to do when an event cannot be evaluated.
+    pub error_policy: ErrorPolicy,
+
+    /// Whether the registered breakpoints should be ignored.
+    pub ignore_breakpoints: bool,
+}
+
+/// Determines how [`Simulation::run_with()`] responds to events that cannot be evaluated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Halt at the first failing event, recording the failure. The cursor is left at the failing event.
+    #[default]
+    Halt,
+
+    /// Skip over failing events and continue. Skipped events are counted, but the failures are not recorded.
+    /// Events are evaluated under [`TransactionMode::CloneAndCommit`] for the duration of the run, so
+    /// that a failing event leaves no trace in the current state.
+    Skip,
+
+    /// Skip over failing events and continue, recording every failure. As with [`ErrorPolicy::Skip`],
+    /// events are evaluated under [`TransactionMode::CloneAndCommit`] for the duration of the run.
+    Collect,
+}
+
+/// The outcome of [`Simulation::run_with()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunReport {
+    /// The number of events that were successfully evaluated.
+    pub steps: usize,
+
+    /// The number of failing events that were skipped over.
+    pub skipped: usize,
+
+    /// The recorded failures, in the order in which they occurred.
+    pub failures: Vec<Failure>,
+
+    /// The breakpoint that suspended the run, if any.
+    pub breakpoint: Option<BreakpointHit>,
+}
+
+/// A record of an event that could not be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// The location of the event in the timeline.
+    pub index: usize,
+
+    /// The name of the event. Taken from [`crate::Named::name`].
+    pub name: String,
+
+    /// The encoded event arguments. Taken from [`ToString::to_string`].
+    pub encoded: String,
+
+    /// The error produced by the event.
+    pub error: TransitionError,
+}
+
+impl<S> Simulation<S> {
+    /// Evaluates the remaining events in the timeline, handling failing events as per the given
+    /// [`RunOptions`]. Unless disabled in the options, the run is suspended upon encountering a
+    /// registered breakpoint. Breakpoints that would suspend the run before the event at the starting
+    /// cursor location are disregarded, so that a suspended run can be resumed.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error other than a [`SimulationError::Transition`] occurs, including
+    /// when one of the configured [`crate::Limits`] is exceeded. Failing events are reported in the
+    /// returned [`RunReport`] instead.
+    pub fn run_with(&mut self, options: &RunOptions) -> Result<RunReport, SimulationError<S>> {
+        if options.error_policy == ErrorPolicy::Halt {
+            return self.run_with_current_mode(options);
+        }
+        let transaction_mode = mem::replace(&mut self.transaction_mode, TransactionMode::CloneAndCommit);
+        let result = self.run_with_current_mode(options);
+        self.transaction_mode = transaction_mode;
+        result
+    }
+
+    fn run_with_current_mode(&mut self, options: &RunOptions) -> Result<RunReport, SimulationError<S>> {
+        let mut report = RunReport::default();
+        let mut guard = Guard::new(&self.limits);
+        let start = self.cursor;
+        while self.cursor < self.scenario.timeline.len() {
+            let index = self.cursor;
+            if !options.ignore_breakpoints && index != start {
+                report.breakpoint = self.test_breakpoints(index, Timing::Before);
+                if report.breakpoint.is_some() {
+                    break;
+                }
+            }
+            guard.check(self.scenario.timeline.len())?;
+            match self.step() {
+                Ok(()) => {
+                    report.steps += 1;
+                    if !options.ignore_breakpoints {
+                        report.breakpoint = self.test_breakpoints(index, Timing::After);
+                        if report.breakpoint.is_some() {
+                            break;
+                        }
+                    }
+                }
+                Err(SimulationError::Transition(error)) => {
+                    if options.error_policy != ErrorPolicy::Skip {
+                        let event = &self.scenario.timeline[self.cursor];
+                        report.failures.push(Failure {
+                            index: self.cursor,
+                            name: event.name().into(),
+                            encoded: event.to_string(),
+                            error,
+                        });
+                    }
+                    if options.error_policy == ErrorPolicy::Halt {
+                        break;
+                    }
+                    self.skip()?;
+                    report.skipped += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Evaluates the remaining events in the timeline until the given predicate matches. The predicate
+    /// is tested before each event is evaluated, being given the current state, the event and its location
+    /// in the timeline. The run is suspended before the matching event, returning its location, or `None`
+    /// if the timeline was exhausted. The predicate is not tested against the event at the starting
+    /// cursor location, so that a suspended run can be resumed. Registered breakpoints are not honoured.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::Transition`], if an event could not be evaluated.
+    /// * [`SimulationError::StepLimitExceeded`], [`SimulationError::TimelineLimitExceeded`] or
+    ///   [`SimulationError::WallClockLimitExceeded`], if a limit was exceeded.
+    pub fn run_until(
+        &mut self,
+        mut predicate: impl FnMut(&S, &dyn Event<State = S>, usize) -> bool,
+    ) -> Result<Option<usize>, SimulationError<S>> {
+        let mut guard = Guard::new(&self.limits);
+        let start = self.cursor;
+        while self.cursor < self.scenario.timeline.len() {
+            let index = self.cursor;
+            if index != start && predicate(&self.current_state, &*self.scenario.timeline[index], index) {
+                return Ok(Some(index));
+            }
+            guard.check(self.scenario.timeline.len())?;
+            self.step()?;
+        }
+        Ok(None)
+    }
+
+    /// Skips over the next event in the timeline without evaluating it, advancing the cursor and
+    /// leaving the current state unchanged. The current state thereby departs from a replay of the
+    /// preceding events, so this is reserved for the skipping error policies. The observers are notified
+    /// of the skip, and a snapshot is captured if the snapshot policy calls for one, so that the
+    /// simulation can later resume past the skipped event.
+    ///
+    /// # Errors
+    /// [`SimulationError`] if an error occurs. Expected variants:
+    ///
+    /// * [`SimulationError::TimelineExhausted`], if the cursor is already parked at the end of the timeline.
+    pub(crate) fn skip(&mut self) -> Result<(), SimulationError<S>> {
+        if self.cursor == self.scenario.timeline.len() {
+            return Err(SimulationError::TimelineExhausted);
+        }
+        let index = self.cursor;
+        self.cursor += 1;
+        let event = &self.scenario.timeline[index];
+        for observer in &mut self.observers {
+            observer.on_skip(index, event.as_ref(), &self.current_state);
+        }
+        self.auto_snapshot();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
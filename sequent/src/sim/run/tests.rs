@@ -0,0 +1,174 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{partially_faulty_scenario, scenario, PartiallyFaulty, TestState};
+use crate::stats::{Collector, Statistics};
+use crate::{ErrorPolicy, Event, Failure, RunOptions, RunReport, Scenario, Simulation, SnapshotPolicy, TransactionMode, TransitionError};
+
+fn options(error_policy: ErrorPolicy) -> RunOptions {
+    RunOptions {
+        error_policy,
+        ..RunOptions::default()
+    }
+}
+
+fn duplicate_failure(index: usize, id: usize) -> Failure {
+    Failure {
+        index,
+        name: "append".into(),
+        encoded: id.to_string(),
+        error: TransitionError(format!("duplicate ID {id}").into()),
+    }
+}
+
+#[test]
+fn run_with_no_failures() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2]));
+    let report = sim.run_with(&RunOptions::default()).unwrap();
+    assert_eq!(
+        RunReport {
+            steps: 3,
+            skipped: 0,
+            failures: vec![],
+            breakpoint: None,
+        },
+        report
+    );
+    assert_eq!(3, sim.cursor());
+}
+
+#[test]
+fn run_with_halt() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 0, 2, 1]));
+    let report = sim.run_with(&options(ErrorPolicy::Halt)).unwrap();
+    assert_eq!(
+        RunReport {
+            steps: 2,
+            skipped: 0,
+            failures: vec![duplicate_failure(2, 0)],
+            breakpoint: None,
+        },
+        report
+    );
+    assert_eq!(2, sim.cursor());
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+}
+
+#[test]
+fn run_with_skip() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 0, 2, 1]));
+    let report = sim.run_with(&options(ErrorPolicy::Skip)).unwrap();
+    assert_eq!(
+        RunReport {
+            steps: 3,
+            skipped: 2,
+            failures: vec![],
+            breakpoint: None,
+        },
+        report
+    );
+    assert_eq!(5, sim.cursor());
+    assert_eq!(vec![0, 1, 2], sim.current_state().transitions);
+}
+
+#[test]
+fn run_with_collect() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 0, 2, 1]));
+    let report = sim.run_with(&options(ErrorPolicy::Collect)).unwrap();
+    assert_eq!(
+        RunReport {
+            steps: 3,
+            skipped: 2,
+            failures: vec![duplicate_failure(2, 0), duplicate_failure(4, 1)],
+            breakpoint: None,
+        },
+        report
+    );
+    assert_eq!(5, sim.cursor());
+    assert_eq!(vec![0, 1, 2], sim.current_state().transitions);
+}
+
+#[test]
+fn run_with_skip_discards_partial_changes() {
+    for error_policy in [ErrorPolicy::Skip, ErrorPolicy::Collect] {
+        let mut sim = Simulation::from(partially_faulty_scenario());
+        assert_eq!(&TransactionMode::InPlace, sim.transaction_mode());
+        let report = sim.run_with(&options(error_policy.clone())).unwrap();
+        assert_eq!(1, report.skipped, "for {error_policy:?}");
+        assert_eq!(vec![0, 2], sim.current_state().transitions, "for {error_policy:?}");
+        assert_eq!(3, sim.scenario().timeline.len(), "for {error_policy:?}");
+        assert_eq!(&TransactionMode::InPlace, sim.transaction_mode(), "for {error_policy:?}");
+    }
+}
+
+#[test]
+fn run_with_halt_keeps_transaction_mode() {
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: vec![Box::new(PartiallyFaulty) as Box<dyn Event<State = _>>].into(),
+        seed: 0,
+    });
+    let report = sim.run_with(&options(ErrorPolicy::Halt)).unwrap();
+    assert_eq!(1, report.failures.len());
+    assert_eq!(vec![42], sim.current_state().transitions);
+}
+
+#[test]
+fn skip() {
+    let mut sim = Simulation::from(scenario(&[0, 1]));
+    sim.skip().unwrap();
+    assert_eq!(1, sim.cursor());
+    assert_eq!(vec![] as Vec<usize>, sim.current_state().transitions);
+    sim.skip().unwrap();
+    assert!(sim.skip().unwrap_err().is_timeline_exhausted());
+    assert_eq!(2, sim.cursor());
+}
+
+#[test]
+fn run_with_skip_then_rewind_statistics() {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, _: &TestState| {
+        statistics.counter("events").increment();
+    });
+    let statistics = collector.statistics();
+    let mut sim = Simulation::from(scenario(&[0, 0, 1, 2]));
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.add_observer(Box::new(collector));
+    let report = sim.run_with(&options(ErrorPolicy::Skip)).unwrap();
+    assert_eq!(1, report.skipped);
+    assert_eq!(3, statistics.borrow().counters()["events"].count());
+
+    // resumes from the snapshot captured after the skipped event
+    assert_eq!(vec![1, 2, 3, 4], sim.snapshots().collect::<Vec<_>>());
+    sim.jump(2).unwrap();
+    assert_eq!(vec![0], sim.current_state().transitions);
+    assert_eq!(1, statistics.borrow().counters()["events"].count());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 2], sim.current_state().transitions);
+    assert_eq!(3, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn run_until() {
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3, 4]));
+    assert_eq!(
+        Some(2),
+        sim.run_until(|_, event, _| event.to_string() == "2").unwrap()
+    );
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+
+    // resumes past the matching event
+    assert_eq!(
+        Some(4),
+        sim.run_until(|state, _, _| state.transitions.len() == 4).unwrap()
+    );
+    assert_eq!(vec![0, 1, 2, 3], sim.current_state().transitions);
+
+    assert_eq!(None, sim.run_until(|_, _, index| index == 1).unwrap());
+    assert_eq!(5, sim.cursor());
+}
+
+#[test]
+fn run_until_transition_error() {
+    let mut sim = Simulation::from(scenario(&[0, 0]));
+    assert!(sim.run_until(|_, _, _| false).unwrap_err().transition().is_some());
+    assert_eq!(1, sim.cursor());
+}
@@ -1,14 +1,14 @@
 // $coverage:ignore-start
 
+use crate::graph::WriteGraphError;
 use crate::persistence::{ReadScenarioError, WriteScenarioError};
-use crate::{Event, Queue, Scenario, Simulation, SimulationError, StaticNamed, TransitionError};
+use crate::test_fixtures::{partially_faulty_scenario, random_scenario, ReversibleAppend, TestState};
+use crate::{Breakpoint, Cause, Condition, Divergence, Event, Provenance, Queue, Scenario, Simulation, SimulationError, SnapshotPolicy, StaticNamed, Time, Timeline, TransactionMode, TransitionError, Validation};
+use std::cell::Cell;
 use std::io;
 use std::io::ErrorKind;
-
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-struct TestState {
-    transitions: Vec<usize>,
-}
+use std::rc::Rc;
+use std::time::Duration;
 
 #[derive(Debug)]
 struct Append {
@@ -47,6 +47,7 @@ fn fixture() -> Scenario<TestState> {
         timeline: (0..EVENTS)
             .map(|id| Box::new(Append { id }) as Box<dyn Event<State = TestState>>)
             .collect(),
+        seed: 0,
     }
 }
 
@@ -125,6 +126,180 @@ fn jump() {
     assert_eq!(0, sim.cursor());
 }
 
+/// Like [`Append`], but also counts the number of times it was applied.
+#[derive(Debug)]
+struct CountedAppend {
+    id: usize,
+    applications: Rc<Cell<usize>>,
+}
+
+impl ToString for CountedAppend {
+    fn to_string(&self) -> String {
+        format!("{}", self.id)
+    }
+}
+
+impl StaticNamed for CountedAppend {
+    fn name() -> &'static str {
+        "counted-append"
+    }
+}
+
+impl Event for CountedAppend {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        self.applications.set(self.applications.get() + 1);
+        state.transitions.push(self.id);
+        Ok(())
+    }
+}
+
+fn counted_fixture(events: usize) -> (Scenario<TestState>, Rc<Cell<usize>>) {
+    let applications = Rc::new(Cell::new(0));
+    let scenario = Scenario {
+        initial: TestState::default(),
+        timeline: (0..events)
+            .map(|id| {
+                Box::new(CountedAppend {
+                    id,
+                    applications: applications.clone(),
+                }) as Box<dyn Event<State = TestState>>
+            })
+            .collect(),
+        seed: 0,
+    };
+    (scenario, applications)
+}
+
+#[test]
+fn jump_back_resumes_from_snapshot() {
+    let (scenario, applications) = counted_fixture(10);
+    let mut sim = Simulation::from(scenario);
+    sim.set_snapshot_policy(SnapshotPolicy::Every(4));
+    assert_eq!(&SnapshotPolicy::Every(4), sim.snapshot_policy());
+    sim.run().unwrap();
+    assert_eq!(vec![4, 8], sim.snapshots().collect::<Vec<_>>());
+    assert_eq!(10, applications.get());
+
+    applications.set(0);
+    sim.jump(6).unwrap();
+    assert_eq!(vec![0, 1, 2, 3, 4, 5], sim.current_state().transitions);
+    assert_eq!(6, sim.cursor());
+    assert_eq!(2, applications.get()); // replayed from snapshot at 4
+
+    applications.set(0);
+    sim.jump(4).unwrap();
+    assert_eq!(vec![0, 1, 2, 3], sim.current_state().transitions);
+    assert_eq!(4, sim.cursor());
+    assert_eq!(0, applications.get()); // restored from snapshot at 4
+
+    applications.set(0);
+    sim.jump(3).unwrap();
+    assert_eq!(vec![0, 1, 2], sim.current_state().transitions);
+    assert_eq!(3, sim.cursor());
+    assert_eq!(3, applications.get()); // no snapshot at or before 3; replayed from 0
+}
+
+#[test]
+fn explicit_snapshot() {
+    let (scenario, applications) = counted_fixture(4);
+    let mut sim = Simulation::from(scenario);
+    assert_eq!(&SnapshotPolicy::Explicit, sim.snapshot_policy());
+    sim.run().unwrap();
+    assert_eq!(0, sim.snapshots().count());
+
+    sim.jump(3).unwrap();
+    sim.snapshot();
+    assert_eq!(vec![3], sim.snapshots().collect::<Vec<_>>());
+
+    applications.set(0);
+    sim.jump(4).unwrap();
+    sim.jump(3).unwrap();
+    assert_eq!(vec![0, 1, 2], sim.current_state().transitions);
+    assert_eq!(1, applications.get()); // only the forward jump applied an event
+
+    sim.clear_snapshots();
+    assert_eq!(0, sim.snapshots().count());
+}
+
+#[test]
+fn budget_snapshot_policy() {
+    let (scenario, _) = counted_fixture(16);
+    let mut sim = Simulation::from(scenario);
+    sim.set_snapshot_policy(SnapshotPolicy::Budget(4));
+    sim.jump(4).unwrap();
+    assert_eq!(vec![1, 2, 3, 4], sim.snapshots().collect::<Vec<_>>());
+
+    sim.step().unwrap();
+    assert_eq!(vec![2, 4], sim.snapshots().collect::<Vec<_>>());
+
+    sim.run().unwrap();
+    assert_eq!(vec![4, 8, 12, 16], sim.snapshots().collect::<Vec<_>>());
+
+    sim.jump(13).unwrap();
+    assert_eq!((0..13).collect::<Vec<_>>(), sim.current_state().transitions);
+}
+
+#[test]
+fn budget_snapshot_policy_excludes_start_of_timeline() {
+    let (scenario, _) = counted_fixture(8);
+    let mut sim = Simulation::from(scenario);
+    sim.snapshot();
+    sim.set_snapshot_policy(SnapshotPolicy::Budget(0));
+    sim.run().unwrap();
+    assert_eq!(vec![0], sim.snapshots().collect::<Vec<_>>());
+
+    sim.set_snapshot_policy(SnapshotPolicy::Budget(2));
+    sim.jump(0).unwrap();
+    sim.run().unwrap();
+    assert_eq!(vec![0, 4, 8], sim.snapshots().collect::<Vec<_>>());
+}
+
+#[test]
+fn truncate_drops_snapshots_beyond_cursor() {
+    let (scenario, _) = counted_fixture(8);
+    let mut sim = Simulation::from(scenario);
+    sim.set_snapshot_policy(SnapshotPolicy::Every(2));
+    sim.run().unwrap();
+    assert_eq!(vec![2, 4, 6, 8], sim.snapshots().collect::<Vec<_>>());
+
+    sim.jump(4).unwrap();
+    sim.truncate();
+    assert_eq!(vec![2, 4], sim.snapshots().collect::<Vec<_>>());
+
+    sim.set_scenario(fixture());
+    assert_eq!(0, sim.snapshots().count());
+}
+
+#[test]
+fn insertions_drop_snapshots_beyond_insertion_point() {
+    let timeline: Vec<Box<dyn Event<State = TestState>>> = vec![
+        Box::new(Append { id: 0 }),
+        Box::new(Append { id: 1 }),
+        Box::new(UpdateQueue {
+            insert_index: 0,
+            id_to_insert: 100,
+        }),
+        Box::new(Append { id: 3 }),
+    ];
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: timeline.into(),
+        seed: 0,
+    });
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.run().unwrap();
+    assert_eq!(vec![1, 2, 3, 4, 5], sim.snapshots().collect::<Vec<_>>());
+
+    // replaying the queue update inserts another event, invalidating the later snapshots
+    sim.jump(2).unwrap();
+    sim.step().unwrap();
+    assert_eq!(vec![1, 2, 3], sim.snapshots().collect::<Vec<_>>());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1, 100, 100, 3], sim.current_state().transitions);
+}
+
 #[test]
 fn run() {
     let mut sim = Simulation::from(fixture());
@@ -291,6 +466,87 @@ fn error_variants() {
     );
     assert!(write_scenario_error().write_scenario().is_some());
     assert!(write_scenario_error().read_scenario().is_none());
+    assert!(!write_scenario_error().is_limit_exceeded());
+
+    let write_graph_error = || SimulationError::<TestState>::WriteGraph(WriteGraphError::Io(io::Error::new(
+        ErrorKind::BrokenPipe,
+        "broken pipe",
+    )));
+    assert_eq!("write graph: io: broken pipe", write_graph_error().to_string());
+    assert!(write_graph_error().write_graph().is_some());
+    assert!(write_graph_error().write_scenario().is_none());
+    assert!(write_scenario_error().write_graph().is_none());
+
+    assert_eq!(
+        "invalid location 7",
+        SimulationError::<TestState>::InvalidLocation(7).to_string()
+    );
+    assert_eq!(Some(7), SimulationError::<TestState>::InvalidLocation(7).invalid_location());
+    assert!(SimulationError::<TestState>::InvalidLocation(7).step_limit_exceeded().is_none());
+    assert!(!SimulationError::<TestState>::InvalidLocation(7).is_limit_exceeded());
+
+    let divergence = Divergence {
+        index: 3,
+        name: "append".into(),
+        encoded: "3".into(),
+        cause: Cause::Insertions,
+    };
+    assert_eq!(
+        "edit rejected: event 'append' made different insertions (location 3)",
+        SimulationError::<TestState>::Diverged(divergence.clone()).to_string()
+    );
+    assert_eq!(Some(divergence.clone()), SimulationError::<TestState>::Diverged(divergence).diverged());
+    assert!(SimulationError::<TestState>::InvalidLocation(7).diverged().is_none());
+
+    assert_eq!(
+        "step limit of 10 exceeded",
+        SimulationError::<TestState>::StepLimitExceeded(10).to_string()
+    );
+    assert!(SimulationError::<TestState>::StepLimitExceeded(10).is_limit_exceeded());
+    assert_eq!(Some(10), SimulationError::<TestState>::StepLimitExceeded(10).step_limit_exceeded());
+    assert!(SimulationError::<TestState>::StepLimitExceeded(10).timeline_limit_exceeded().is_none());
+
+    assert_eq!(
+        "timeline length limit of 20 exceeded",
+        SimulationError::<TestState>::TimelineLimitExceeded(20).to_string()
+    );
+    assert!(SimulationError::<TestState>::TimelineLimitExceeded(20).is_limit_exceeded());
+    assert_eq!(Some(20), SimulationError::<TestState>::TimelineLimitExceeded(20).timeline_limit_exceeded());
+    assert!(SimulationError::<TestState>::TimelineLimitExceeded(20).wall_clock_limit_exceeded().is_none());
+
+    assert_eq!(
+        "wall-clock limit of 1.5s exceeded",
+        SimulationError::<TestState>::WallClockLimitExceeded(Duration::from_millis(1500)).to_string()
+    );
+    assert!(SimulationError::<TestState>::WallClockLimitExceeded(Duration::ZERO).is_limit_exceeded());
+    assert_eq!(
+        Some(Duration::ZERO),
+        SimulationError::<TestState>::WallClockLimitExceeded(Duration::ZERO).wall_clock_limit_exceeded()
+    );
+    assert!(SimulationError::<TestState>::WallClockLimitExceeded(Duration::ZERO).step_limit_exceeded().is_none());
+}
+
+#[test]
+fn clone() {
+    let mut sim = Simulation::from(fixture());
+    sim.add_breakpoint(Breakpoint::before(Condition::Index(2)));
+    sim.jump(1).unwrap();
+    sim.snapshot();
+
+    let mut clone = sim.clone();
+    assert_eq!(1, clone.cursor());
+    assert_eq!(sim.current_state(), clone.current_state());
+    assert_eq!(vec![1], clone.snapshots().collect::<Vec<_>>());
+    assert_eq!(1, clone.breakpoints().count());
+    assert_eq!(1, clone.add_breakpoint(Breakpoint::before(Condition::Index(0))));
+    for (original, cloned) in sim.scenario().timeline.iter().zip(clone.scenario().timeline.iter()) {
+        assert!(Rc::ptr_eq(original, cloned));
+    }
+
+    clone.run().unwrap();
+    assert_eq!(4, clone.cursor());
+    assert_eq!(1, sim.cursor());
+    assert_eq!(vec![0], sim.current_state().transitions);
 }
 
 #[test]
@@ -336,7 +592,7 @@ impl Event for UpdateQueue {
     }
 }
 
-fn slice_to_string(slice: &[Box<dyn Event<State = TestState>>]) -> String {
+fn slice_to_string(slice: &[Rc<dyn Event<State = TestState>>]) -> String {
     let strings = slice
         .iter()
         .map(|item| item.to_string())
@@ -354,7 +610,8 @@ fn insert_queue() {
         })];
         let scenario = Scenario {
             initial: TestState::default(),
-            timeline,
+            timeline: timeline.into(),
+            seed: 0,
         };
         let mut sim = Simulation::from(scenario);
         sim.step().unwrap();
@@ -373,7 +630,8 @@ fn insert_queue() {
         ];
         let scenario = Scenario {
             initial: TestState::default(),
-            timeline,
+            timeline: timeline.into(),
+            seed: 0,
         };
         let mut sim = Simulation::from(scenario);
         sim.step().unwrap();
@@ -392,10 +650,351 @@ fn insert_queue() {
         ];
         let scenario = Scenario {
             initial: TestState::default(),
-            timeline,
+            timeline: timeline.into(),
+            seed: 0,
         };
         let mut sim = Simulation::from(scenario);
         sim.step().unwrap();
         assert_eq!("[1|100, 6|600, 100]", slice_to_string(&sim.scenario.timeline));
     }
 }
+
+#[derive(Debug)]
+struct ScheduleAppend {
+    delay: Time,
+    id_to_schedule: usize,
+}
+
+impl ToString for ScheduleAppend {
+    fn to_string(&self) -> String {
+        format!("{}|{}", self.delay, self.id_to_schedule)
+    }
+}
+
+impl StaticNamed for ScheduleAppend {
+    fn name() -> &'static str {
+        "schedule-append"
+    }
+}
+
+impl Event for ScheduleAppend {
+    type State = TestState;
+
+    fn apply(&self, _: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        queue.schedule_in(self.delay, Box::new(Append { id: self.id_to_schedule }));
+        Ok(())
+    }
+}
+
+#[test]
+fn now() {
+    let mut timeline = Timeline::default();
+    timeline.push_at(1.0, Box::new(ScheduleAppend { delay: 2.0, id_to_schedule: 100 }));
+    timeline.push_at(2.0, Box::new(Append { id: 1 }));
+    timeline.push_at(4.0, Box::new(Append { id: 2 }));
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline,
+        seed: 0,
+    });
+    assert_eq!(0.0, sim.now());
+
+    sim.step().unwrap();
+    assert_eq!(1.0, sim.now());
+    assert_eq!(&[1.0, 2.0, 3.0, 4.0], sim.scenario().timeline.times());
+
+    sim.run().unwrap();
+    assert_eq!(vec![1, 100, 2], sim.current_state().transitions);
+    assert_eq!(4.0, sim.now());
+
+    sim.push_event(Box::new(Append { id: 3 })).unwrap();
+    assert_eq!(&[1.0, 2.0, 3.0, 4.0, 4.0], sim.scenario().timeline.times());
+
+    sim.jump(2).unwrap();
+    assert_eq!(2.0, sim.now());
+}
+
+#[test]
+fn provenance() {
+    let mut timeline = Timeline::default();
+    timeline.push_at(1.0, Box::new(ScheduleAppend { delay: 2.0, id_to_schedule: 100 }));
+    timeline.push_at(2.0, Box::new(ScheduleAppend { delay: 0.5, id_to_schedule: 200 }));
+    timeline.push_at(4.0, Box::new(Append { id: 2 }));
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline,
+        seed: 0,
+    });
+    sim.run().unwrap();
+    assert_eq!(vec![200, 100, 2], sim.current_state().transitions);
+    assert_eq!(
+        vec![
+            Provenance::User,
+            Provenance::User,
+            Provenance::Generated(1),
+            Provenance::Generated(0),
+            Provenance::User
+        ],
+        sim.scenario().timeline.provenance()
+    );
+    assert_eq!(vec![1], sim.scenario().timeline.ancestry(2));
+    assert!(sim.scenario().timeline.ancestry(1).is_empty());
+
+    // provenance follows the generating event as the timeline is edited
+    sim.reset();
+    sim.move_event(2, 4, Validation::Unchecked).unwrap();
+    assert_eq!(
+        vec![
+            Provenance::User,
+            Provenance::User,
+            Provenance::Generated(0),
+            Provenance::User,
+            Provenance::Generated(1)
+        ],
+        sim.scenario().timeline.provenance()
+    );
+    sim.remove_event(0, Validation::Unchecked).unwrap();
+    assert_eq!(
+        vec![Provenance::User, Provenance::Orphaned, Provenance::User, Provenance::Generated(0)],
+        sim.scenario().timeline.provenance()
+    );
+
+    sim.truncate_generated();
+    assert_eq!(vec![Provenance::User, Provenance::User], sim.scenario().timeline.provenance());
+    sim.run().unwrap();
+    assert_eq!(vec![200, 2], sim.current_state().transitions);
+}
+
+/// Like [`CountedAppend`], but reversible.
+#[test]
+fn step_back_reversible() {
+    let applications = Rc::new(Cell::new(0));
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: (0..3)
+            .map(|id| {
+                Box::new(ReversibleAppend {
+                    id,
+                    applications: applications.clone(),
+                }) as Box<dyn Event<State = TestState>>
+            })
+            .collect(),
+        seed: 0,
+    });
+    assert!(sim.step_back().unwrap_err().is_timeline_exhausted());
+
+    sim.run().unwrap();
+    applications.set(0);
+    sim.step_back().unwrap();
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+    assert_eq!(2, sim.cursor());
+
+    sim.step_back().unwrap();
+    sim.step_back().unwrap();
+    assert_eq!(vec![] as Vec<usize>, sim.current_state().transitions);
+    assert_eq!(0, sim.cursor());
+    assert_eq!(0, applications.get()); // no replay
+
+    assert!(sim.step_back().unwrap_err().is_timeline_exhausted());
+    assert_eq!(0, sim.cursor());
+}
+
+#[test]
+fn step_back_irreversible_falls_back_to_replay() {
+    let (scenario, applications) = counted_fixture(3);
+    let mut sim = Simulation::from(scenario);
+    sim.run().unwrap();
+    applications.set(0);
+    sim.step_back().unwrap();
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+    assert_eq!(2, sim.cursor());
+    assert_eq!(2, applications.get());
+}
+
+#[test]
+fn step_back_fails_to_reverse() {
+    let applications = Rc::new(Cell::new(0));
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState { transitions: vec![42] },
+        timeline: vec![Box::new(ReversibleAppend { id: 0, applications }) as Box<dyn Event<State = _>>].into(),
+        seed: 0,
+    });
+    sim.step().unwrap();
+    sim.current_state.transitions.push(1);
+    assert_eq!(
+        TransitionError("cannot unapply 0".into()),
+        sim.step_back().unwrap_err().transition().unwrap()
+    );
+    assert_eq!(1, sim.cursor());
+}
+
+#[test]
+fn failed_step_in_place() {
+    let mut sim = Simulation::from(partially_faulty_scenario());
+    assert_eq!(&TransactionMode::InPlace, sim.transaction_mode());
+    sim.step().unwrap();
+    assert_eq!(
+        TransitionError("boom".into()),
+        sim.step().unwrap_err().transition().unwrap()
+    );
+    assert_eq!(vec![0, 42], sim.current_state().transitions);
+    assert_eq!(1, sim.cursor());
+    assert_eq!(3, sim.scenario().timeline.len());
+}
+
+#[test]
+fn failed_step_clone_and_commit() {
+    let mut sim = Simulation::from(partially_faulty_scenario());
+    sim.set_transaction_mode(TransactionMode::CloneAndCommit);
+    assert_eq!(&TransactionMode::CloneAndCommit, sim.transaction_mode());
+    sim.step().unwrap();
+    assert_eq!(
+        TransitionError("boom".into()),
+        sim.step().unwrap_err().transition().unwrap()
+    );
+    assert_eq!(vec![0], sim.current_state().transitions);
+    assert_eq!(1, sim.cursor());
+    assert_eq!(3, sim.scenario().timeline.len());
+}
+
+#[test]
+fn failed_step_back_clone_and_commit() {
+    let applications = Rc::new(Cell::new(0));
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: vec![Box::new(ReversibleAppend { id: 0, applications }) as Box<dyn Event<State = _>>].into(),
+        seed: 0,
+    });
+    sim.set_transaction_mode(TransactionMode::CloneAndCommit);
+    sim.step().unwrap();
+    sim.current_state.transitions.push(1);
+    assert!(sim.step_back().unwrap_err().transition().is_some());
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+    assert_eq!(1, sim.cursor());
+}
+
+/// Drives a simulation without requiring a cloneable state.
+fn run_unbounded<S>(sim: &mut Simulation<S>) -> Result<(), SimulationError<S>> {
+    sim.step()?;
+    sim.step_back()?;
+    sim.run()
+}
+
+#[test]
+fn run_without_clone_bound() {
+    let mut sim = Simulation::from(fixture());
+    sim.set_transaction_mode(TransactionMode::CloneAndCommit);
+    sim.set_snapshot_policy(SnapshotPolicy::Every(2));
+    run_unbounded(&mut sim).unwrap();
+    assert_eq!(vec![0, 1, 2, 3], sim.current_state().transitions);
+    assert_eq!(vec![2, 4], sim.snapshots().collect::<Vec<_>>());
+}
+
+fn random_fixture(seed: u64) -> Simulation<TestState> {
+    Simulation::from(random_scenario(10, 1_000_000, seed))
+}
+
+#[test]
+fn random_draws_are_reproducible() {
+    let mut sim = random_fixture(42);
+    sim.set_snapshot_policy(SnapshotPolicy::Every(3));
+    sim.run().unwrap();
+    let original = sim.current_state().clone();
+    assert_eq!(10, original.transitions.len());
+
+    // replay from the initial state
+    sim.reset();
+    sim.run().unwrap();
+    assert_eq!(&original, sim.current_state());
+
+    // replay from a snapshot
+    sim.jump(7).unwrap();
+    assert_eq!(&original.transitions[..7], sim.current_state().transitions);
+    sim.run().unwrap();
+    assert_eq!(&original, sim.current_state());
+
+    // replay by stepping back and forth
+    sim.step_back().unwrap();
+    sim.step().unwrap();
+    assert_eq!(&original, sim.current_state());
+
+    // a different simulation with the same seed
+    let mut other = random_fixture(42);
+    other.run().unwrap();
+    assert_eq!(&original, other.current_state());
+
+    // a different seed
+    let mut other = random_fixture(43);
+    other.run().unwrap();
+    assert_ne!(&original, other.current_state());
+}
+
+#[test]
+fn random_draws_change_after_edit() {
+    let mut sim = random_fixture(42);
+    sim.run().unwrap();
+    let original = sim.current_state().clone();
+
+    sim.reset();
+    sim.remove_event(5, Validation::Unchecked).unwrap();
+    sim.run().unwrap();
+    let edited = &sim.current_state().transitions;
+    assert_eq!(9, edited.len());
+
+    // events before the edit keep their draws; those after it move up a location, drawing from new streams
+    assert_eq!(original.transitions[..5], edited[..5]);
+    for (before, after) in original.transitions[6..].iter().zip(&edited[5..]) {
+        assert_ne!(before, after);
+    }
+}
+
+/// Cancels the future events with the given ID tag.
+#[derive(Debug)]
+struct CancelAppend {
+    id: usize,
+}
+
+impl ToString for CancelAppend {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for CancelAppend {
+    fn name() -> &'static str {
+        "cancel-append"
+    }
+}
+
+impl Event for CancelAppend {
+    type State = TestState;
+
+    fn apply(&self, _: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        let id = self.id.to_string();
+        queue.cancel_where(|event, _| event.name() == "append" && event.to_string() == id);
+        Ok(())
+    }
+}
+
+#[test]
+fn cancellation() {
+    let timeline: Vec<Box<dyn Event<State = TestState>>> = vec![
+        Box::new(Append { id: 0 }),
+        Box::new(CancelAppend { id: 2 }),
+        Box::new(Append { id: 1 }),
+        Box::new(Append { id: 2 }),
+    ];
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: timeline.into(),
+        seed: 0,
+    });
+    sim.set_snapshot_policy(SnapshotPolicy::Every(1));
+    sim.jump(1).unwrap();
+    sim.snapshot();
+    sim.step().unwrap();
+    assert_eq!(vec![1, 2], sim.snapshots().collect::<Vec<_>>());
+    assert_eq!(3, sim.scenario().timeline.len());
+    sim.run().unwrap();
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+}
@@ -0,0 +1,417 @@
+//! Collection of simulation statistics: counters, tallies, histograms and time-weighted accumulators.
+//!
+//! Statistics are ordinarily kept in the simulation state, where events update them in the course of
+//! their transitions. Being part of the state, they are captured in snapshots and restored along with
+//! the rest of the state when the simulation is reset or jumps backward; rewinding the simulation
+//! therefore never double counts. States that carry [`Statistics`] should implement [`Measured`],
+//! which exposes them to generic tooling, such as the REPL.
+//!
+//! Alternatively, statistics may be gathered from outside the state by a [`Collector`], which is a
+//! [`SimulationObserver`] that checkpoints its statistics after every event, rolling them back when the
+//! simulation is rewound.
+
+use crate::{Event, SimulationObserver, Time};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Counts occurrences.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counter {
+    count: u64,
+}
+
+impl Counter {
+    /// Increments the count by one.
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    /// Increments the count by the given amount.
+    pub fn add(&mut self, amount: u64) {
+        self.count += amount;
+    }
+
+    /// The current count.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Accumulates the summary statistics of a series of observations: count, mean, variance, minimum and
+/// maximum. The mean and variance are computed incrementally using Welford's algorithm; the observations
+/// themselves are not retained.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Tally {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Tally {
+    /// Records an observation.
+    pub fn record(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    /// The number of recorded observations.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The arithmetic mean of the observations, or [`None`] if nothing has been recorded.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The sample variance of the observations, or [`None`] if fewer than two observations have been
+    /// recorded.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// The sample standard deviation of the observations, or [`None`] if fewer than two observations
+    /// have been recorded.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// The smallest observation, or [`None`] if nothing has been recorded.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// The largest observation, or [`None`] if nothing has been recorded.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+}
+
+/// Sorts observations into equal-width bins spanning a fixed range. Observations that fall outside the
+/// range are counted separately, as underflow and overflow. A [`Tally`] of all observations, including
+/// those that fall outside the range, is also kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    lower: f64,
+    upper: f64,
+    bins: Vec<u64>,
+    underflow: u64,
+    overflow: u64,
+    tally: Tally,
+}
+
+impl Histogram {
+    /// Creates a histogram with the given number of bins, spanning the half-open range `lower..upper`.
+    ///
+    /// # Panics
+    /// If the range is empty or there are no bins.
+    pub fn new(lower: f64, upper: f64, bins: usize) -> Self {
+        assert!(lower < upper, "lower bound ({lower}) must be less than upper bound ({upper})");
+        assert!(bins > 0, "number of bins must be greater than 0");
+        Self {
+            lower,
+            upper,
+            bins: vec![0; bins],
+            underflow: 0,
+            overflow: 0,
+            tally: Tally::default(),
+        }
+    }
+
+    /// Records an observation.
+    pub fn record(&mut self, value: f64) {
+        self.tally.record(value);
+        if value < self.lower {
+            self.underflow += 1;
+        } else if value >= self.upper {
+            self.overflow += 1;
+        } else {
+            let width = (self.upper - self.lower) / self.bins.len() as f64;
+            let bin = (((value - self.lower) / width) as usize).min(self.bins.len() - 1);
+            self.bins[bin] += 1;
+        }
+    }
+
+    /// The number of observations in each bin.
+    pub fn bins(&self) -> &[u64] {
+        &self.bins
+    }
+
+    /// The range of values spanned by the bin at the given index.
+    ///
+    /// # Panics
+    /// If the index is out of bounds.
+    pub fn bin_range(&self, index: usize) -> Range<f64> {
+        let len = self.bins.len();
+        assert!(index < len, "index ({index}) must be less than number of bins ({len})");
+        let width = (self.upper - self.lower) / len as f64;
+        let start = self.lower + width * index as f64;
+        let end = if index == len - 1 { self.upper } else { start + width };
+        start..end
+    }
+
+    /// The number of observations below the lower bound.
+    pub fn underflow(&self) -> u64 {
+        self.underflow
+    }
+
+    /// The number of observations at or above the upper bound.
+    pub fn overflow(&self) -> u64 {
+        self.overflow
+    }
+
+    /// Summary statistics of all recorded observations.
+    pub fn summary(&self) -> &Tally {
+        &self.tally
+    }
+}
+
+/// Accumulates the time-weighted statistics of a piecewise-constant quantity, such as the length of a
+/// queue or the number of busy servers. Each update sets the value of the quantity from the given time
+/// onward; the mean is the time-average of the quantity from its first update.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeWeighted {
+    updates: u64,
+    start: Time,
+    last_time: Time,
+    value: f64,
+    area: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TimeWeighted {
+    /// Sets the value of the quantity at the given time.
+    ///
+    /// # Panics
+    /// If the time precedes the time of the previous update.
+    pub fn record(&mut self, time: Time, value: f64) {
+        if self.updates == 0 {
+            self.start = time;
+            self.min = value;
+            self.max = value;
+        } else {
+            let last_time = self.last_time;
+            assert!(time >= last_time, "time ({time}) must not precede the previous update ({last_time})");
+            self.area += self.value * (time - last_time);
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+        self.updates += 1;
+        self.last_time = time;
+        self.value = value;
+    }
+
+    /// The number of updates.
+    pub fn updates(&self) -> u64 {
+        self.updates
+    }
+
+    /// The current value of the quantity, or [`None`] if it has never been updated.
+    pub fn value(&self) -> Option<f64> {
+        (self.updates > 0).then_some(self.value)
+    }
+
+    /// The time-average of the quantity from its first update until `now`, or [`None`] if it has never
+    /// been updated. If no time has elapsed since the first update, the current value is returned.
+    ///
+    /// # Panics
+    /// If `now` precedes the time of the last update.
+    pub fn mean(&self, now: Time) -> Option<f64> {
+        if self.updates == 0 {
+            return None;
+        }
+        let last_time = self.last_time;
+        assert!(now >= last_time, "time ({now}) must not precede the last update ({last_time})");
+        let elapsed = now - self.start;
+        if elapsed == 0.0 {
+            Some(self.value)
+        } else {
+            Some((self.area + self.value * (now - last_time)) / elapsed)
+        }
+    }
+
+    /// The smallest value taken by the quantity, or [`None`] if it has never been updated.
+    pub fn min(&self) -> Option<f64> {
+        (self.updates > 0).then_some(self.min)
+    }
+
+    /// The largest value taken by the quantity, or [`None`] if it has never been updated.
+    pub fn max(&self) -> Option<f64> {
+        (self.updates > 0).then_some(self.max)
+    }
+}
+
+/// A collection of named statistics. Each statistic is created upon its first access, so that events
+/// may update statistics without registering them upfront.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    counters: BTreeMap<String, Counter>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tallies: BTreeMap<String, Tally>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    histograms: BTreeMap<String, Histogram>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    time_weighted: BTreeMap<String, TimeWeighted>,
+}
+
+impl Statistics {
+    /// A mutable reference to the named counter, creating it if it does not exist.
+    pub fn counter(&mut self, name: &str) -> &mut Counter {
+        entry(&mut self.counters, name, Counter::default)
+    }
+
+    /// A mutable reference to the named tally, creating it if it does not exist.
+    pub fn tally(&mut self, name: &str) -> &mut Tally {
+        entry(&mut self.tallies, name, Tally::default)
+    }
+
+    /// A mutable reference to the named histogram, creating it with the given range and number of bins
+    /// if it does not exist. (See [`Histogram::new()`].) The range and bins of an existing histogram
+    /// are unaffected.
+    pub fn histogram(&mut self, name: &str, lower: f64, upper: f64, bins: usize) -> &mut Histogram {
+        entry(&mut self.histograms, name, || Histogram::new(lower, upper, bins))
+    }
+
+    /// A mutable reference to the named time-weighted accumulator, creating it if it does not exist.
+    pub fn time_weighted(&mut self, name: &str) -> &mut TimeWeighted {
+        entry(&mut self.time_weighted, name, TimeWeighted::default)
+    }
+
+    /// The counters, keyed by name.
+    pub fn counters(&self) -> &BTreeMap<String, Counter> {
+        &self.counters
+    }
+
+    /// The tallies, keyed by name.
+    pub fn tallies(&self) -> &BTreeMap<String, Tally> {
+        &self.tallies
+    }
+
+    /// The histograms, keyed by name.
+    pub fn histograms(&self) -> &BTreeMap<String, Histogram> {
+        &self.histograms
+    }
+
+    /// The time-weighted accumulators, keyed by name.
+    pub fn time_weighted_accumulators(&self) -> &BTreeMap<String, TimeWeighted> {
+        &self.time_weighted
+    }
+
+    /// Returns `true` if no statistics have been created.
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.tallies.is_empty() && self.histograms.is_empty() && self.time_weighted.is_empty()
+    }
+
+    /// Discards all statistics.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn entry<'a, V>(map: &'a mut BTreeMap<String, V>, name: &str, init: impl FnOnce() -> V) -> &'a mut V {
+    map.entry(name.to_owned()).or_insert_with(init)
+}
+
+/// Implemented by simulation states that carry [`Statistics`].
+pub trait Measured {
+    /// A reference to the statistics.
+    fn statistics(&self) -> &Statistics;
+}
+
+impl Measured for Statistics {
+    fn statistics(&self) -> &Statistics {
+        self
+    }
+}
+
+/// The signature of the closure used by a [`Collector`] to update its statistics.
+type Update<S> = dyn FnMut(&mut Statistics, usize, &dyn Event<State = S>, &S);
+
+/// A [`SimulationObserver`] that gathers statistics after the evaluation of every event. The statistics
+/// are shared with the creator of the collector (see [`Collector::statistics()`]).
+///
+/// The collector checkpoints its statistics whenever the simulation captures a snapshot, and discards the
+/// checkpoint along with the snapshot, so that its memory use is governed by the [`crate::SnapshotPolicy`].
+/// When the simulation is rewound, it resumes from a snapshot and replays the timeline from there; the
+/// statistics are restored from the checkpoint at the snapshot location, or are cleared if there is no such
+/// checkpoint (e.g., the simulation is reset, or the snapshot precedes the registration of the collector),
+/// and the replayed events are then observed as usual.
+pub struct Collector<S> {
+    statistics: Rc<RefCell<Statistics>>,
+    checkpoints: BTreeMap<usize, Statistics>,
+    update: Box<Update<S>>,
+}
+
+impl<S> Collector<S> {
+    /// Creates a collector that invokes the given closure after every successfully applied event, with
+    /// the statistics to update, the location of the event, the event and the state following the
+    /// transition.
+    pub fn new(update: impl FnMut(&mut Statistics, usize, &dyn Event<State = S>, &S) + 'static) -> Self {
+        Self {
+            statistics: Rc::default(),
+            checkpoints: BTreeMap::default(),
+            update: Box::new(update),
+        }
+    }
+
+    /// A shared reference to the statistics, which remains valid after the collector is registered
+    /// with a simulation.
+    pub fn statistics(&self) -> Rc<RefCell<Statistics>> {
+        self.statistics.clone()
+    }
+}
+
+impl<S> Debug for Collector<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Collector")
+            .field("statistics", &self.statistics)
+            .field("checkpoints", &self.checkpoints.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> SimulationObserver<S> for Collector<S> {
+    fn after_event(&mut self, index: usize, event: &dyn Event<State = S>, state: &S) {
+        let mut statistics = self.statistics.borrow_mut();
+        (self.update)(&mut statistics, index, event, state);
+    }
+
+    fn on_relocate(&mut self, location: usize) {
+        *self.statistics.borrow_mut() = self.checkpoints.get(&location).cloned().unwrap_or_default();
+    }
+
+    fn on_snapshot(&mut self, location: usize) {
+        self.checkpoints.insert(location, self.statistics.borrow().clone());
+    }
+
+    fn on_snapshot_discarded(&mut self, location: usize) {
+        self.checkpoints.remove(&location);
+    }
+
+    fn follows_reversal(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests;
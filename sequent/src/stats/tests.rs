@@ -0,0 +1,308 @@
+// $coverage:ignore-start
+
+use crate::stats::{Collector, Counter, Histogram, Measured, Statistics, Tally, TimeWeighted};
+use crate::test_fixtures::{scenario, ReversibleAppend, TestState};
+use crate::{Event, Queue, Scenario, Simulation, SnapshotPolicy, StaticNamed, TransitionError};
+use std::rc::Rc;
+
+#[test]
+fn counter() {
+    let mut counter = Counter::default();
+    assert_eq!(0, counter.count());
+    counter.increment();
+    counter.add(3);
+    assert_eq!(4, counter.count());
+}
+
+#[test]
+fn tally() {
+    let mut tally = Tally::default();
+    assert_eq!(0, tally.count());
+    assert_eq!(None, tally.mean());
+    assert_eq!(None, tally.variance());
+    assert_eq!(None, tally.min());
+    assert_eq!(None, tally.max());
+
+    tally.record(2.0);
+    assert_eq!(Some(2.0), tally.mean());
+    assert_eq!(None, tally.std_dev());
+
+    for value in [4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        tally.record(value);
+    }
+    assert_eq!(8, tally.count());
+    assert_eq!(Some(5.0), tally.mean());
+    assert_eq!(Some(32.0 / 7.0), tally.variance());
+    assert_eq!(Some((32.0_f64 / 7.0).sqrt()), tally.std_dev());
+    assert_eq!(Some(2.0), tally.min());
+    assert_eq!(Some(9.0), tally.max());
+}
+
+#[test]
+fn histogram() {
+    let mut histogram = Histogram::new(0.0, 10.0, 4);
+    for value in [-1.0, 0.0, 2.4, 2.5, 5.0, 9.99, 10.0, 12.0] {
+        histogram.record(value);
+    }
+    assert_eq!(&[2, 1, 1, 1], histogram.bins());
+    assert_eq!(1, histogram.underflow());
+    assert_eq!(2, histogram.overflow());
+    assert_eq!(8, histogram.summary().count());
+    assert_eq!(Some(-1.0), histogram.summary().min());
+    assert_eq!(0.0..2.5, histogram.bin_range(0));
+    assert_eq!(7.5..10.0, histogram.bin_range(3));
+}
+
+#[test]
+#[should_panic(expected = "lower bound (1) must be less than upper bound (1)")]
+fn histogram_empty_range() {
+    Histogram::new(1.0, 1.0, 4);
+}
+
+#[test]
+#[should_panic(expected = "number of bins must be greater than 0")]
+fn histogram_no_bins() {
+    Histogram::new(0.0, 1.0, 0);
+}
+
+#[test]
+#[should_panic(expected = "index (4) must be less than number of bins (4)")]
+fn histogram_bin_range_out_of_bounds() {
+    Histogram::new(0.0, 1.0, 4).bin_range(4);
+}
+
+#[test]
+fn time_weighted() {
+    let mut time_weighted = TimeWeighted::default();
+    assert_eq!(None, time_weighted.mean(0.0));
+    assert_eq!(None, time_weighted.value());
+
+    time_weighted.record(1.0, 2.0);
+    assert_eq!(Some(2.0), time_weighted.mean(1.0));
+
+    time_weighted.record(3.0, 0.0);
+    time_weighted.record(4.0, 6.0);
+    assert_eq!(3, time_weighted.updates());
+    assert_eq!(Some(6.0), time_weighted.value());
+    // 2 for 2 time units, 0 for 1 unit, then 6 for 1 unit
+    assert_eq!(Some(10.0 / 4.0), time_weighted.mean(5.0));
+    assert_eq!(Some(0.0), time_weighted.min());
+    assert_eq!(Some(6.0), time_weighted.max());
+}
+
+#[test]
+#[should_panic(expected = "time (1) must not precede the previous update (2)")]
+fn time_weighted_out_of_order() {
+    let mut time_weighted = TimeWeighted::default();
+    time_weighted.record(2.0, 1.0);
+    time_weighted.record(1.0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "time (1) must not precede the last update (2)")]
+fn time_weighted_mean_before_last_update() {
+    let mut time_weighted = TimeWeighted::default();
+    time_weighted.record(2.0, 1.0);
+    time_weighted.mean(1.0);
+}
+
+#[test]
+fn statistics_create_on_access() {
+    let mut statistics = Statistics::default();
+    assert!(statistics.is_empty());
+    statistics.counter("arrivals").increment();
+    statistics.counter("arrivals").increment();
+    statistics.tally("wait").record(1.5);
+    statistics.histogram("wait", 0.0, 1.0, 2).record(0.75);
+    // the range and bins of an existing histogram are unaffected
+    statistics.histogram("wait", 0.0, 10.0, 10).record(0.25);
+    statistics.time_weighted("queue").record(0.0, 1.0);
+
+    assert!(!statistics.is_empty());
+    assert_eq!(2, statistics.counters()["arrivals"].count());
+    assert_eq!(1, statistics.tallies()["wait"].count());
+    assert_eq!(&[1, 1], statistics.histograms()["wait"].bins());
+    assert_eq!(1, statistics.time_weighted_accumulators()["queue"].updates());
+    assert_eq!(&statistics, statistics.statistics());
+
+    statistics.clear();
+    assert!(statistics.is_empty());
+}
+
+#[test]
+fn statistics_serde_round_trip() {
+    let mut statistics = Statistics::default();
+    statistics.counter("arrivals").increment();
+    statistics.histogram("wait", 0.0, 1.0, 2).record(0.75);
+    let yaml = serde_yaml::to_string(&statistics).unwrap();
+    assert!(!yaml.contains("tallies"));
+    assert_eq!(statistics, serde_yaml::from_str(&yaml).unwrap());
+    assert_eq!(Statistics::default(), serde_yaml::from_str("{}").unwrap());
+}
+
+#[derive(Debug, Clone, Default)]
+struct MeasuredState {
+    statistics: Statistics,
+}
+
+impl Measured for MeasuredState {
+    fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+}
+
+/// Counts arrivals and tracks the time between them.
+#[derive(Debug)]
+struct Arrive;
+
+impl ToString for Arrive {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for Arrive {
+    fn name() -> &'static str {
+        "arrive"
+    }
+}
+
+impl Event for Arrive {
+    type State = MeasuredState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        let count = state.statistics.counter("arrivals").count();
+        state.statistics.counter("arrivals").increment();
+        state.statistics.time_weighted("arrived").record(queue.now(), (count + 1) as f64);
+        Ok(())
+    }
+}
+
+#[test]
+fn statistics_in_state_rewind_with_simulation() {
+    let mut timeline = crate::Timeline::default();
+    for time in [1.0, 2.0, 4.0] {
+        timeline.push_at(time, Box::new(Arrive) as Box<dyn Event<State = _>>);
+    }
+    let mut sim = Simulation::from(Scenario {
+        initial: MeasuredState::default(),
+        timeline,
+        seed: 0,
+    });
+    sim.run().unwrap();
+    let statistics = sim.current_state().statistics();
+    assert_eq!(3, statistics.counters()["arrivals"].count());
+    assert_eq!(Some(5.0 / 3.0), statistics.time_weighted_accumulators()["arrived"].mean(sim.now()));
+
+    sim.jump(1).unwrap();
+    assert_eq!(1, sim.current_state().statistics().counters()["arrivals"].count());
+    sim.run().unwrap();
+    assert_eq!(3, sim.current_state().statistics().counters()["arrivals"].count());
+
+    sim.reset();
+    assert!(sim.current_state().statistics().is_empty());
+}
+
+#[test]
+fn collector_rewinds_with_simulation() {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, state: &TestState| {
+        statistics.counter("events").increment();
+        statistics.tally("transitions").record(state.transitions.len() as f64);
+    });
+    let statistics = collector.statistics();
+    assert!(format!("{collector:?}").contains("Collector"));
+
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.add_observer(Box::new(collector));
+    sim.jump(2).unwrap();
+    sim.snapshot();
+    sim.run().unwrap();
+    assert_eq!(4, statistics.borrow().counters()["events"].count());
+    assert_eq!(Some(2.5), statistics.borrow().tallies()["transitions"].mean());
+
+    // resumes from the snapshot at location 2
+    sim.jump(3).unwrap();
+    assert_eq!(3, statistics.borrow().counters()["events"].count());
+    sim.run().unwrap();
+    assert_eq!(4, statistics.borrow().counters()["events"].count());
+
+    sim.reset();
+    assert!(statistics.borrow().is_empty());
+    sim.run().unwrap();
+    assert_eq!(4, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn collector_checkpoints_at_snapshots() {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, _: &TestState| {
+        statistics.counter("events").increment();
+    });
+    let statistics = collector.statistics();
+
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3, 4, 5, 6, 7]));
+    sim.set_snapshot_policy(SnapshotPolicy::Budget(2));
+    sim.add_observer(Box::new(collector));
+    sim.run().unwrap();
+    assert_eq!(vec![4, 8], sim.snapshots().collect::<Vec<_>>());
+    assert!(format!("{sim:?}").contains("checkpoints: 2"));
+
+    // resumes from the snapshot at location 4
+    sim.jump(6).unwrap();
+    assert_eq!(6, statistics.borrow().counters()["events"].count());
+
+    sim.truncate();
+    assert_eq!(vec![4], sim.snapshots().collect::<Vec<_>>());
+    assert!(format!("{sim:?}").contains("checkpoints: 1"));
+
+    sim.clear_snapshots();
+    assert!(format!("{sim:?}").contains("checkpoints: 0"));
+    sim.jump(5).unwrap();
+    assert_eq!(5, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn collector_replays_reversible_events() {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, _: &TestState| {
+        statistics.counter("events").increment();
+    });
+    let statistics = collector.statistics();
+
+    let mut sim = Simulation::from(Scenario {
+        initial: TestState::default(),
+        timeline: (0..4)
+            .map(|id| {
+                Box::new(ReversibleAppend {
+                    id,
+                    applications: Rc::default(),
+                }) as Box<dyn Event<State = TestState>>
+            })
+            .collect(),
+        seed: 0,
+    });
+    sim.add_observer(Box::new(collector));
+    sim.run().unwrap();
+    sim.step_back().unwrap();
+    sim.step_back().unwrap();
+    assert_eq!(vec![0, 1], sim.current_state().transitions);
+    assert_eq!(2, statistics.borrow().counters()["events"].count());
+}
+
+#[test]
+fn collector_registered_midway() {
+    let collector = Collector::new(|statistics: &mut Statistics, _, _: &dyn Event<State = TestState>, _: &TestState| {
+        statistics.counter("events").increment();
+    });
+    let statistics = collector.statistics();
+
+    let mut sim = Simulation::from(scenario(&[0, 1, 2, 3]));
+    sim.jump(2).unwrap();
+    sim.add_observer(Box::new(collector));
+    sim.run().unwrap();
+    assert_eq!(2, statistics.borrow().counters()["events"].count());
+
+    // replays from the initial state, which precedes the registration
+    sim.jump(1).unwrap();
+    assert_eq!(1, statistics.borrow().counters()["events"].count());
+    sim.run().unwrap();
+    assert_eq!(4, statistics.borrow().counters()["events"].count());
+}
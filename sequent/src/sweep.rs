@@ -0,0 +1,255 @@
+//! Parameter sweeps over the initial state of a scenario.
+
+use crate::{parallel, Metric, Scenario, Simulation};
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+use thiserror::Error;
+
+/// A variation of the initial state, labelled with the parameter values that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant<S> {
+    /// The (name, value) pairs of the parameters.
+    pub parameters: Vec<(String, String)>,
+
+    /// The initial state of the variant.
+    pub initial: S,
+}
+
+/// A parameter of a [`grid()`] sweep, comprising a name, the values it takes and a function that
+/// assigns a value to a state.
+pub struct Axis<S> {
+    name: String,
+    #[allow(clippy::type_complexity)]
+    values: Vec<(String, Box<dyn Fn(&mut S)>)>,
+}
+
+impl<S> Axis<S> {
+    /// Creates an axis for the named parameter, which takes each of the given values in turn. The
+    /// `assign` function applies a value to the state; the value's [`Display`] form labels it in the results.
+    pub fn new<V: Display + 'static>(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+        assign: impl Fn(&mut S, &V) + 'static,
+    ) -> Self {
+        let assign = Rc::new(assign);
+        let values = values
+            .into_iter()
+            .map(|value| {
+                let assign = assign.clone();
+                let label = value.to_string();
+                (label, Box::new(move |state: &mut S| assign(state, &value)) as Box<dyn Fn(&mut S)>)
+            })
+            .collect();
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+
+    /// The name of the parameter.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S> Debug for Axis<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Axis")
+            .field("name", &self.name)
+            .field("values", &self.values.iter().map(|(label, _)| label).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Expands a set of axes into the variants comprising their Cartesian product, each derived from
+/// the `base` state. The last axis varies fastest. An empty set of axes yields a single, unaltered variant.
+pub fn grid<S: Clone>(base: &S, axes: &[Axis<S>]) -> Vec<Variant<S>> {
+    let mut variants = vec![Variant {
+        parameters: vec![],
+        initial: base.clone(),
+    }];
+    for axis in axes {
+        variants = variants
+            .into_iter()
+            .flat_map(|variant| {
+                axis.values.iter().map(move |(label, assign)| {
+                    let mut parameters = variant.parameters.clone();
+                    parameters.push((axis.name.clone(), label.clone()));
+                    let mut initial = variant.initial.clone();
+                    assign(&mut initial);
+                    Variant { parameters, initial }
+                })
+            })
+            .collect();
+    }
+    variants
+}
+
+/// Runs each variant to completion, in sequence, over a copy of the `base` scenario with its initial
+/// state replaced by that of the variant, measuring the final state with the given metrics.
+///
+/// # Errors
+/// [`SweepError`] if a variant could not be run to completion, in which case the remaining variants
+/// are abandoned.
+pub fn sweep<S: Clone>(base: &Scenario<S>, variants: Vec<Variant<S>>, metrics: &[Metric<S>]) -> Result<SweepTable, SweepError> {
+    let mut table = SweepTable::new(&variants, metrics);
+    for (index, variant) in variants.into_iter().enumerate() {
+        let scenario = Scenario {
+            initial: variant.initial,
+            timeline: base.timeline.clone(),
+            seed: base.seed,
+        };
+        table.rows[index].1 = run_variant(index, scenario, metrics)?;
+    }
+    Ok(table)
+}
+
+/// Runs each variant to completion in parallel, otherwise behaving like [`sweep()`]. As scenarios
+/// cannot be shared between threads, each run uses a base scenario obtained from `scenario_factory`.
+/// The number of workers is bounded by the available parallelism.
+///
+/// # Errors
+/// [`SweepError`] if a variant could not be run to completion, in which case the remaining variants
+/// are abandoned. If more than one variant failed, the error for the earliest is returned.
+pub fn sweep_parallel<S: Clone + Send>(
+    scenario_factory: impl Fn() -> Scenario<S> + Sync,
+    variants: Vec<Variant<S>>,
+    metrics: &[Metric<S>],
+) -> Result<SweepTable, SweepError> {
+    let mut table = SweepTable::new(&variants, metrics);
+    let initials = variants.into_iter().map(|variant| variant.initial).collect();
+    let outcomes = parallel::map(initials, |index, initial| {
+        let scenario = Scenario {
+            initial,
+            ..scenario_factory()
+        };
+        run_variant(index, scenario, metrics)
+    })?;
+    for (row, outcome) in table.rows.iter_mut().zip(outcomes) {
+        row.1 = outcome;
+    }
+    Ok(table)
+}
+
+fn run_variant<S: Clone>(index: usize, scenario: Scenario<S>, metrics: &[Metric<S>]) -> Result<Vec<f64>, SweepError> {
+    let mut sim = Simulation::from(scenario);
+    sim.run().map_err(|err| SweepError {
+        variant: index,
+        message: err.to_string(),
+    })?;
+    Ok(metrics.iter().map(|metric| metric.measure(sim.current_state())).collect())
+}
+
+/// Produced by [`sweep()`] and [`sweep_parallel()`] if a variant could not be run to completion.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("variant {variant} failed: {message}")]
+pub struct SweepError {
+    /// The index of the failed variant.
+    pub variant: usize,
+
+    /// The description of the underlying [`crate::SimulationError`].
+    pub message: String,
+}
+
+/// The results of a sweep, with one row per variant. The columns comprise the parameters, in order
+/// of their first appearance among the variants, followed by the metrics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepTable {
+    parameters: Vec<String>,
+    metrics: Vec<String>,
+    rows: Vec<(Vec<Option<String>>, Vec<f64>)>,
+}
+
+impl SweepTable {
+    fn new<S>(variants: &[Variant<S>], metrics: &[Metric<S>]) -> Self {
+        let mut parameters: Vec<String> = vec![];
+        for variant in variants {
+            for (name, _) in &variant.parameters {
+                if !parameters.contains(name) {
+                    parameters.push(name.clone());
+                }
+            }
+        }
+        let rows = variants
+            .iter()
+            .map(|variant| {
+                let values = parameters
+                    .iter()
+                    .map(|parameter| {
+                        variant
+                            .parameters
+                            .iter()
+                            .find(|(name, _)| name == parameter)
+                            .map(|(_, value)| value.clone())
+                    })
+                    .collect();
+                (values, vec![])
+            })
+            .collect();
+        Self {
+            parameters,
+            metrics: metrics.iter().map(|metric| metric.name().into()).collect(),
+            rows,
+        }
+    }
+
+    /// The parameter names.
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    /// The metric names.
+    pub fn metrics(&self) -> &[String] {
+        &self.metrics
+    }
+
+    /// The rows, each comprising the parameter values (`None` where a variant does not specify a
+    /// parameter) and the measured outcomes.
+    pub fn rows(&self) -> &[(Vec<Option<String>>, Vec<f64>)] {
+        &self.rows
+    }
+
+    /// Writes the table in CSV format (RFC 4180), including a header row.
+    ///
+    /// # Errors
+    /// [`io::Error`] if the table could not be written.
+    pub fn write_csv(&self, w: &mut impl Write) -> Result<(), io::Error> {
+        let header = self
+            .parameters
+            .iter()
+            .chain(self.metrics.iter())
+            .map(|name| csv_field(name))
+            .collect::<Vec<_>>();
+        write!(w, "{}\r\n", header.join(","))?;
+        for (values, outcomes) in &self.rows {
+            let fields = values
+                .iter()
+                .map(|value| value.as_deref().map(csv_field).unwrap_or_default())
+                .chain(outcomes.iter().map(ToString::to_string))
+                .collect::<Vec<_>>();
+            write!(w, "{}\r\n", fields.join(","))?;
+        }
+        Ok(())
+    }
+
+    /// The table in CSV format. See [`SweepTable::write_csv()`].
+    pub fn to_csv(&self) -> String {
+        let mut buf = vec![];
+        self.write_csv(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+/// Quotes a CSV field if it contains a delimiter, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,172 @@
+// $coverage:ignore-start
+
+use crate::test_fixtures::{random_scenario, TestState};
+use crate::{grid, sweep, sweep_parallel, Axis, Event, Metric, Queue, Scenario, StaticNamed, SweepError, Timeline, TransitionError, Variant};
+
+/// A climber that ascends a wall of a given height in fixed steps.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Climber {
+    height: u32,
+    step: u32,
+    progress: u32,
+    climbs: u32,
+}
+
+/// Climbs one step, scheduling the next climb until the top of the wall is reached.
+#[derive(Debug)]
+struct Climb;
+
+impl ToString for Climb {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for Climb {
+    fn name() -> &'static str {
+        "climb"
+    }
+}
+
+impl Event for Climb {
+    type State = Climber;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        if state.step == 0 {
+            return Err(TransitionError("zero step".into()));
+        }
+        state.progress += state.step;
+        state.climbs += 1;
+        if state.progress < state.height {
+            queue.schedule_in(1.0, Box::new(Climb));
+        }
+        Ok(())
+    }
+}
+
+fn base() -> Scenario<Climber> {
+    let mut timeline = Timeline::default();
+    timeline.push(Box::new(Climb));
+    Scenario {
+        initial: Climber::default(),
+        timeline,
+        seed: 0,
+    }
+}
+
+fn axes() -> Vec<Axis<Climber>> {
+    vec![
+        Axis::new("height", [10, 20], |state: &mut Climber, &height| state.height = height),
+        Axis::new("step", [2, 3, 5], |state: &mut Climber, &step| state.step = step),
+    ]
+}
+
+fn metrics() -> Vec<Metric<Climber>> {
+    vec![Metric::new("climbs", |state: &Climber| f64::from(state.climbs))]
+}
+
+#[test]
+fn grid_product() {
+    let variants = grid(&Climber::default(), &axes());
+    assert_eq!(6, variants.len());
+    assert_eq!(
+        vec![("height".to_string(), "10".to_string()), ("step".to_string(), "2".to_string())],
+        variants[0].parameters
+    );
+    assert_eq!((10, 2), (variants[0].initial.height, variants[0].initial.step));
+    assert_eq!((20, 5), (variants[5].initial.height, variants[5].initial.step));
+
+    let variants = grid(&Climber::default(), &[]);
+    assert_eq!(vec![Variant { parameters: vec![], initial: Climber::default() }], variants);
+}
+
+#[test]
+fn axis_implements_debug() {
+    assert_eq!(
+        "Axis { name: \"step\", values: [\"2\", \"3\", \"5\"] }",
+        format!("{:?}", axes()[1])
+    );
+}
+
+#[test]
+fn sweep_grid() {
+    let table = sweep(&base(), grid(&Climber::default(), &axes()), &metrics()).unwrap();
+    assert_eq!(&["height", "step"], table.parameters());
+    assert_eq!(&["climbs"], table.metrics());
+    assert_eq!(
+        "height,step,climbs\r\n\
+         10,2,5\r\n\
+         10,3,4\r\n\
+         10,5,2\r\n\
+         20,2,10\r\n\
+         20,3,7\r\n\
+         20,5,4\r\n",
+        table.to_csv()
+    );
+}
+
+#[test]
+fn sweep_parallel_matches_sequential() {
+    let variants = grid(&Climber::default(), &axes());
+    let sequential = sweep(&base(), variants.clone(), &metrics()).unwrap();
+    let parallel = sweep_parallel(base, variants, &metrics()).unwrap();
+    assert_eq!(sequential, parallel);
+}
+
+#[test]
+fn sweep_list() {
+    let variants = vec![
+        Variant {
+            parameters: vec![("label".into(), "tall, \"slow\"".into())],
+            initial: Climber {
+                height: 100,
+                step: 1,
+                ..Climber::default()
+            },
+        },
+        Variant {
+            parameters: vec![("step".into(), "50".into())],
+            initial: Climber {
+                height: 100,
+                step: 50,
+                ..Climber::default()
+            },
+        },
+    ];
+    let table = sweep(&base(), variants, &metrics()).unwrap();
+    assert_eq!(2, table.rows().len());
+    assert_eq!(
+        "label,step,climbs\r\n\
+         \"tall, \"\"slow\"\"\",,100\r\n\
+         ,50,2\r\n",
+        table.to_csv()
+    );
+}
+
+#[test]
+fn sweep_failure() {
+    let variants = grid(&Climber::default(), &[Axis::new("step", [1, 0, 0], |state: &mut Climber, &step| state.step = step)]);
+    let expected = SweepError {
+        variant: 1,
+        message: "transition: zero step".into(),
+    };
+    assert_eq!(expected, sweep(&base(), variants.clone(), &metrics()).unwrap_err());
+    assert_eq!(expected, sweep_parallel(base, variants, &metrics()).unwrap_err());
+    assert_eq!("variant 1 failed: transition: zero step", expected.to_string());
+}
+
+#[test]
+fn sweep_shares_random_draws_across_variants() {
+    let base = || random_scenario(5, 100, 42);
+    let variants = grid(
+        &TestState::default(),
+        &[Axis::new("offset", [0, 1_000], |state: &mut TestState, &offset| state.transitions.push(offset))],
+    );
+    #[allow(clippy::cast_precision_loss)]
+    let metrics = [Metric::new("draws", |state: &TestState| state.transitions[1..].iter().sum::<usize>() as f64)];
+    let table = sweep(&base(), variants.clone(), &metrics).unwrap();
+
+    // every variant runs under the scenario seed, so the draws are common to all variants
+    assert_eq!(table.rows()[0].1, table.rows()[1].1);
+    assert_eq!(table, sweep_parallel(base, variants, &metrics).unwrap());
+}
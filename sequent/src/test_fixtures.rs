@@ -0,0 +1,192 @@
+//! Reusable test fixtures.
+
+// $coverage:ignore-start
+
+use crate::{Event, ParseEventError, Queue, ReversibleEvent, Scenario, StaticNamed, TransitionError};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+pub mod persistence;
+
+/// Test state, which simply accumulates a vector of ID tags corresponding to the
+/// [`Append`] events that have been applied to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestState {
+    pub transitions: Vec<usize>,
+}
+
+/// Appends an ID tag to [`TestState::transitions`]. Fails if the tag is already present.
+#[derive(Debug)]
+pub struct Append {
+    pub id: usize,
+}
+
+impl ToString for Append {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for Append {
+    fn name() -> &'static str {
+        "append"
+    }
+}
+
+impl FromStr for Append {
+    type Err = ParseEventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.parse().map_err(|_| ParseEventError(format!("invalid ID '{s}'").into()))?;
+        Ok(Self { id })
+    }
+}
+
+impl Event for Append {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        if state.transitions.contains(&self.id) {
+            Err(TransitionError(format!("duplicate ID {}", self.id).into()))
+        } else {
+            state.transitions.push(self.id);
+            Ok(())
+        }
+    }
+}
+
+/// Creates a scenario with a timeline of [`Append`] events with the given ID tags.
+pub fn scenario(ids: &[usize]) -> Scenario<TestState> {
+    Scenario {
+        initial: TestState::default(),
+        timeline: ids
+            .iter()
+            .map(|&id| Box::new(Append { id }) as Box<dyn Event<State = TestState>>)
+            .collect(),
+        seed: 0,
+    }
+}
+
+/// Mutates the state and the queue before failing.
+#[derive(Debug)]
+pub struct PartiallyFaulty;
+
+impl ToString for PartiallyFaulty {
+    fn to_string(&self) -> String {
+        String::default()
+    }
+}
+
+impl StaticNamed for PartiallyFaulty {
+    fn name() -> &'static str {
+        "partially-faulty"
+    }
+}
+
+impl Event for PartiallyFaulty {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.transitions.push(42);
+        queue.push_later(Box::new(Append { id: 42 }));
+        Err(TransitionError("boom".into()))
+    }
+}
+
+/// Creates a scenario with a [`PartiallyFaulty`] event sandwiched between two [`Append`] events.
+pub fn partially_faulty_scenario() -> Scenario<TestState> {
+    Scenario {
+        initial: TestState::default(),
+        timeline: vec![
+            Box::new(Append { id: 0 }) as Box<dyn Event<State = _>>,
+            Box::new(PartiallyFaulty),
+            Box::new(Append { id: 2 }),
+        ]
+        .into(),
+        seed: 0,
+    }
+}
+
+/// Appends an ID tag to [`TestState::transitions`], counting its applications. Reversible by popping
+/// the tag off again.
+#[derive(Debug)]
+pub struct ReversibleAppend {
+    pub id: usize,
+    pub applications: Rc<Cell<usize>>,
+}
+
+impl ToString for ReversibleAppend {
+    fn to_string(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl StaticNamed for ReversibleAppend {
+    fn name() -> &'static str {
+        "reversible-append"
+    }
+}
+
+impl Event for ReversibleAppend {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        self.applications.set(self.applications.get() + 1);
+        state.transitions.push(self.id);
+        Ok(())
+    }
+
+    fn as_reversible(&self) -> Option<&dyn ReversibleEvent<State = Self::State>> {
+        Some(self)
+    }
+}
+
+impl ReversibleEvent for ReversibleAppend {
+    fn unapply(&self, state: &mut Self::State) -> Result<(), TransitionError> {
+        match state.transitions.pop() {
+            Some(id) if id == self.id => Ok(()),
+            _ => Err(TransitionError(format!("cannot unapply {}", self.id).into())),
+        }
+    }
+}
+
+/// Appends a random ID tag in the range [0, `bound`).
+#[derive(Debug)]
+pub struct RandomAppend {
+    pub bound: u64,
+}
+
+impl ToString for RandomAppend {
+    fn to_string(&self) -> String {
+        self.bound.to_string()
+    }
+}
+
+impl StaticNamed for RandomAppend {
+    fn name() -> &'static str {
+        "random-append"
+    }
+}
+
+impl Event for RandomAppend {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, queue: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.transitions.push(usize::try_from(queue.rng().below(self.bound)).unwrap());
+        Ok(())
+    }
+}
+
+/// Creates a scenario with a timeline of `len` [`RandomAppend`] events, each drawing from [0, `bound`),
+/// under the given seed.
+pub fn random_scenario(len: usize, bound: u64, seed: u64) -> Scenario<TestState> {
+    Scenario {
+        initial: TestState::default(),
+        timeline: (0..len)
+            .map(|_| Box::new(RandomAppend { bound }) as Box<dyn Event<State = TestState>>)
+            .collect(),
+        seed,
+    }
+}
@@ -0,0 +1,114 @@
+//! Fixtures for testing the persistence backends.
+
+// $coverage:ignore-start
+
+use crate::{Decoder, Event, ParseEventError, Parser, Payload, Queue, Scenario, SerdeParser, Simulation, StaticNamed, TransitionError};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Test state, which accumulates the words and weights of the [`TestEvent`] and [`StructuredEvent`]
+/// events that have been applied to it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestState {
+    pub some_string: String,
+    pub some_f64: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub some_option: Option<f64>,
+}
+
+impl TestState {
+    /// Creates a state with the given string and float, and no optional value.
+    pub fn new(some_string: &str, some_f64: f64) -> Self {
+        Self {
+            some_string: some_string.into(),
+            some_f64,
+            some_option: None,
+        }
+    }
+}
+
+/// An event encoded as a string of whitespace-separated words, which it appends to [`TestState::some_string`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestEvent(pub Vec<String>);
+
+impl StaticNamed for TestEvent {
+    fn name() -> &'static str {
+        "test"
+    }
+}
+
+impl ToString for TestEvent {
+    fn to_string(&self) -> String {
+        self.0.join(" ")
+    }
+}
+
+impl Event for TestEvent {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        for word in &self.0 {
+            state.some_string.push(' ');
+            state.some_string.push_str(word);
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TestEvent {
+    type Err = ParseEventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.split_whitespace().map(ToString::to_string).collect()))
+    }
+}
+
+/// An event with a structured payload, which appends its label to [`TestState::some_string`] and adds
+/// its weights to [`TestState::some_f64`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredEvent {
+    pub label: String,
+    pub weights: Vec<f64>,
+}
+
+impl StaticNamed for StructuredEvent {
+    fn name() -> &'static str {
+        "structured"
+    }
+}
+
+impl ToString for StructuredEvent {
+    fn to_string(&self) -> String {
+        Payload::encode(self).unwrap().to_string()
+    }
+}
+
+impl Event for StructuredEvent {
+    type State = TestState;
+
+    fn apply(&self, state: &mut Self::State, _: &mut Queue<Self::State>) -> Result<(), TransitionError> {
+        state.some_string.push(' ');
+        state.some_string.push_str(&self.label);
+        state.some_f64 += self.weights.iter().sum::<f64>();
+        Ok(())
+    }
+
+    fn payload(&self) -> Option<Payload> {
+        Payload::encode(self).ok()
+    }
+}
+
+/// A decoder for both [`TestEvent`] and [`StructuredEvent`].
+pub fn decoder() -> Decoder<TestState> {
+    Decoder::new(vec![
+        Box::new(Parser::<TestEvent>::default()),
+        Box::new(SerdeParser::<StructuredEvent>::default()),
+    ])
+}
+
+/// Runs the scenario to completion, returning the final state.
+pub fn replay(scenario: Scenario<TestState>) -> TestState {
+    let mut sim = Simulation::from(scenario);
+    sim.run().unwrap();
+    sim.current_state().clone()
+}